@@ -0,0 +1,102 @@
+#![allow(missing_docs, missing_debug_implementations)]
+
+use nacfahi::{
+    GenericArray, U,
+    models::{
+        FitModel, FitModelSum, FitModelXDeriv,
+        basic::Constant,
+        utility::{AutoDiff, AutoDiffModel, AutoDiffScalar, Composition},
+    },
+};
+use static_assertions::assert_impl_all;
+
+/// Logistic function $\dfrac{a}{1 + \exp(-k (x - x_0))}$
+#[derive(Debug, Clone, Copy)]
+pub struct Logistic {
+    pub a: f64,
+    pub k: f64,
+    pub x_0: f64,
+}
+
+impl AutoDiffModel for Logistic {
+    type Scalar = f64;
+    type ParamCount = U<3>;
+
+    fn evaluate<T: AutoDiffScalar<f64>>(&self, params: &GenericArray<T, U<3>>, x: T) -> T {
+        let [a, k, x_0] = (*params).into_array();
+        a / (T::one() + (-k * (x - x_0)).exp())
+    }
+
+    fn set_params(&mut self, new_params: GenericArray<f64, U<3>>) {
+        [self.a, self.k, self.x_0] = new_params.into_array();
+    }
+
+    fn get_params(&self) -> impl Into<GenericArray<f64, U<3>>> {
+        [self.a, self.k, self.x_0]
+    }
+}
+
+#[derive(FitModelSum)]
+#[scalar_type(f64)]
+pub struct Step {
+    pub step: AutoDiff<Logistic>,
+    pub background: Constant<f64>,
+}
+
+assert_impl_all!(AutoDiff<Logistic>: FitModel<Scalar = f64>, FitModelXDeriv);
+assert_impl_all!(Step: FitModel<Scalar = f64>);
+assert_impl_all!(Composition<AutoDiff<Logistic>, AutoDiff<Logistic>>: FitModel<Scalar = f64>);
+
+#[test]
+fn fits_step() {
+    use approx::assert_relative_eq;
+    use nacfahi::*;
+
+    let truth = Logistic {
+        a: 2.0,
+        k: 1.5,
+        x_0: 0.3,
+    };
+    let x: [f64; 41] = core::array::from_fn(|i| -5.0 + i as f64 * 0.25);
+    let y = x.map(|x| AutoDiff(truth).evaluate(&x) + 0.5);
+
+    let mut model = Step {
+        step: AutoDiff(Logistic {
+            a: 1.0,
+            k: 1.0,
+            x_0: 0.0,
+        }),
+        background: Constant { c: 0.0 },
+    };
+    let report = fit!(&mut model, x, y);
+
+    assert!(
+        report.termination.was_successful(),
+        "Fit should be successful {report:?}"
+    );
+    assert_relative_eq!(model.step.0.a, truth.a, max_relative = 1e-8);
+    assert_relative_eq!(model.step.0.k, truth.k, max_relative = 1e-8);
+    assert_relative_eq!(model.step.0.x_0, truth.x_0, max_relative = 1e-8);
+    assert_relative_eq!(model.background.c, 0.5, max_relative = 1e-8);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn errors_are_model() {
+    use nacfahi::*;
+
+    let x: [f64; 21] = core::array::from_fn(|i| -5.0 + i as f64 * 0.5);
+    let y = x.map(|x| 2.0 / (1.0 + (-1.5 * (x - 0.3)).exp()) + 0.01 * (x * 7.0).sin());
+
+    let mut model = AutoDiff(Logistic {
+        a: 1.0,
+        k: 1.0,
+        x_0: 0.0,
+    });
+    let stat = fit_stat!(&mut model, x, y);
+    let errors: Logistic = stat.errors;
+
+    assert!(errors.a > 0.0 && errors.a < 0.1);
+    assert!(errors.k > 0.0 && errors.k < 0.1);
+    assert!(errors.x_0 > 0.0 && errors.x_0 < 0.1);
+}
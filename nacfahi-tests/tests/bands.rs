@@ -0,0 +1,77 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use approx::assert_relative_eq;
+use common::{X, Y};
+use nacfahi::{models::basic::Linear, *};
+
+#[test]
+fn linear_regression_bands() {
+    let n = X.len() as f64;
+    let x_mean = X.iter().sum::<f64>() / n;
+    let sxx = X.iter().map(|x| (x - x_mean).powi(2)).sum::<f64>();
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y);
+    assert!(stat.report.termination.was_successful());
+    assert_eq!(stat.dof, 6);
+    assert!(!stat.absolute_sigma);
+
+    let s = stat.reduced_chi2.sqrt();
+    // t(0.975, 6)
+    let t = 2.446_911_851_144_969;
+    assert_relative_eq!(stat.interval_quantile(0.95), t, max_relative = 1e-10);
+
+    let points = [-1.0, 0.0, 3.5, 8.0];
+    for (band, x0) in stat.bands(&line, points, 0.95).zip(points) {
+        let leverage = 1.0 / n + (x0 - x_mean).powi(2) / sxx;
+        let std_error = s * leverage.sqrt();
+        let prediction_std_error = s * (1.0 + leverage).sqrt();
+
+        assert_relative_eq!(band.x, x0);
+        assert_relative_eq!(band.value, line.a * x0 + line.b);
+        assert_relative_eq!(band.std_error, std_error, max_relative = 1e-8);
+        assert_relative_eq!(
+            band.prediction_std_error,
+            prediction_std_error,
+            max_relative = 1e-8
+        );
+        assert_relative_eq!(
+            *band.confidence.start(),
+            band.value - t * std_error,
+            max_relative = 1e-8
+        );
+        assert_relative_eq!(
+            *band.confidence.end(),
+            band.value + t * std_error,
+            max_relative = 1e-8
+        );
+        assert_relative_eq!(
+            *band.prediction.end(),
+            band.value + t * prediction_std_error,
+            max_relative = 1e-8
+        );
+    }
+}
+
+#[test]
+fn known_errors_use_normal_quantile() {
+    let sigma = [0.2; 8];
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y, sigma = sigma);
+    assert!(stat.absolute_sigma);
+    assert_relative_eq!(
+        stat.interval_quantile(0.95),
+        1.959_963_984_540_054,
+        max_relative = 1e-10
+    );
+
+    let band = stat.band_sigma(&line, 2.0, 0.2, 0.95);
+    assert_relative_eq!(
+        band.prediction_std_error,
+        (band.std_error.powi(2) + 0.04).sqrt(),
+        max_relative = 1e-12
+    );
+}
@@ -0,0 +1,64 @@
+#![allow(missing_docs, missing_debug_implementations)]
+
+use nacfahi::models::{
+    FitModel, FitModelSum,
+    basic::{Constant, Exponent, Gaussian, Linear},
+    utility::{Bounded, Composition},
+};
+use static_assertions::assert_impl_all;
+
+#[derive(FitModelSum)]
+#[scalar_type(f64)]
+pub struct BoundedPeak {
+    pub peak: Bounded<Gaussian<f64>>,
+    pub background: Constant<f64>,
+}
+
+assert_impl_all!(BoundedPeak: FitModel<Scalar = f64>);
+assert_impl_all!(Bounded<BoundedPeak>: FitModel<Scalar = f64>);
+assert_impl_all!([Bounded<Gaussian<f64>>; 3]: FitModel<Scalar = f64>);
+assert_impl_all!(Bounded<[Gaussian<f64>; 3]>: FitModel<Scalar = f64>);
+assert_impl_all!(Bounded<Composition<Linear<f64>, Exponent<f64>>>: FitModel<Scalar = f64>);
+assert_impl_all!(Composition<Bounded<Linear<f64>>, Bounded<Exponent<f64>>>: FitModel<Scalar = f64>);
+
+#[test]
+fn stays_within_bounds() {
+    use nacfahi::{models::utility::ParamBound, *};
+
+    // best unbounded slope would be 2.0
+    let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+    let y = [1.0, 3.0, 5.0, 7.0, 9.0];
+
+    let line = Linear { a: 0.5, b: 0.0 };
+    let mut bounded = Bounded::new(line, [ParamBound::Both(0.0, 1.0), ParamBound::Free]);
+
+    let _report = fit!(&mut bounded, x, y);
+    let line = bounded.into_inner();
+
+    assert!((0.0..=1.0).contains(&line.a), "Slope should stay in bounds");
+    approx::assert_abs_diff_eq!(line.a, 1.0, epsilon = 1e-3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn errors_in_original_space() {
+    use approx::assert_relative_eq;
+    use nacfahi::{models::utility::ParamBound, *};
+
+    let x = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = [1.1, 2.9, 5.2, 6.8, 9.1, 11.0];
+
+    let mut free = Linear { a: 1.0, b: 0.0 };
+    let free_stat = fit_stat!(&mut free, x, y);
+
+    let line = Linear { a: 1.0, b: 0.0 };
+    let mut bounded = Bounded::new(line, [ParamBound::Lower(0.0), ParamBound::Both(-5.0, 5.0)]);
+    let bounded_errors = fit_stat!(&mut bounded, x, y).errors;
+    let line = bounded.into_inner();
+
+    assert_relative_eq!(line.a, free.a, max_relative = 1e-8);
+    assert_relative_eq!(line.b, free.b, max_relative = 1e-8);
+    // errors are reported for the actual parameters, not the transformed ones
+    assert_relative_eq!(bounded_errors.a, free_stat.errors.a, max_relative = 1e-6);
+    assert_relative_eq!(bounded_errors.b, free_stat.errors.b, max_relative = 1e-6);
+}
@@ -0,0 +1,12 @@
+//! Data shared between integration tests. Each test only uses some of it.
+#![allow(dead_code)]
+
+/// Noisy line, roughly `2x + 1`
+pub const X: [f64; 8] = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+/// See [`X`]
+pub const Y: [f64; 8] = [1.2, 2.7, 5.3, 6.9, 9.2, 10.8, 13.1, 14.7];
+
+/// `N` evenly spaced points, starting at `from`.
+pub fn grid<const N: usize>(from: f64, step: f64) -> [f64; N] {
+    core::array::from_fn(|i| from + step * i as f64)
+}
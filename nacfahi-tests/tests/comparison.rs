@@ -0,0 +1,166 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use approx::assert_relative_eq;
+use common::{X, Y};
+use nacfahi::{
+    models::{
+        FitModel,
+        basic::{Gaussian, Linear},
+    },
+    *,
+};
+
+#[test]
+fn criteria_unknown_sigma() {
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y);
+
+    let n = X.len() as f64;
+    let rss = X
+        .iter()
+        .zip(&Y)
+        .map(|(x, y)| (line.a * x + line.b - y).powi(2))
+        .sum::<f64>();
+    assert_eq!(stat.points, 8);
+    assert_eq!(stat.free_params, 2);
+    assert_eq!(stat.dof, 6);
+    assert_relative_eq!(stat.rss, rss, max_relative = 1e-10);
+    assert_relative_eq!(stat.chi2, rss, max_relative = 1e-10);
+
+    let log_likelihood = -n / 2.0 * ((2.0 * core::f64::consts::PI * rss / n).ln() + 1.0);
+    assert_relative_eq!(stat.log_likelihood, log_likelihood, max_relative = 1e-10);
+    // slope, offset and data variance
+    let k = 3.0;
+    assert_relative_eq!(
+        stat.aic(),
+        2.0 * k - 2.0 * log_likelihood,
+        max_relative = 1e-10
+    );
+    assert_relative_eq!(
+        stat.aicc(),
+        stat.aic() + 2.0 * k * (k + 1.0) / (n - k - 1.0),
+        max_relative = 1e-10
+    );
+    assert_relative_eq!(
+        stat.bic(),
+        k * n.ln() - 2.0 * log_likelihood,
+        max_relative = 1e-10
+    );
+}
+
+#[test]
+fn criteria_known_sigma() {
+    let sigma = [0.1, 0.2, 0.1, 0.3, 0.2, 0.1, 0.2, 0.3];
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y, sigma = sigma);
+
+    let n = X.len() as f64;
+    let rss = X
+        .iter()
+        .zip(&Y)
+        .map(|(x, y)| (line.a * x + line.b - y).powi(2))
+        .sum::<f64>();
+    assert_relative_eq!(stat.rss, rss, max_relative = 1e-10);
+
+    let log_likelihood = -stat.chi2 / 2.0
+        - sigma.iter().map(|s: &f64| s.ln()).sum::<f64>()
+        - n / 2.0 * (2.0 * core::f64::consts::PI).ln();
+    assert_relative_eq!(stat.log_likelihood, log_likelihood, max_relative = 1e-10);
+    assert_relative_eq!(stat.aic(), 4.0 - 2.0 * log_likelihood, max_relative = 1e-10);
+    assert_relative_eq!(
+        stat.bic(),
+        2.0 * n.ln() - 2.0 * log_likelihood,
+        max_relative = 1e-10
+    );
+
+    // too few points for AICc
+    let mut line = Linear::<f64> { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(
+        &mut line,
+        [0.0, 1.0, 2.0],
+        [0.0, 1.1, 1.9],
+        sigma = [0.1; 3]
+    );
+    assert!(stat.aicc().is_infinite());
+}
+
+fn peaks(second: f64) -> ([f64; 60], [f64; 60]) {
+    let truth = [
+        Gaussian::<f64> {
+            a: 2.0,
+            x_c: -1.0,
+            sigma: 0.8,
+        },
+        Gaussian::<f64> {
+            a: second,
+            x_c: 1.5,
+            sigma: 0.7,
+        },
+    ];
+    let x: [f64; 60] = core::array::from_fn(|i| -5.0 + i as f64 / 6.0);
+    let y = x.map(|x| {
+        // deterministic "noise"
+        let noise = 0.02 * (x * 12.9898).sin();
+        truth.iter().map(|g| g.evaluate(&x)).sum::<f64>() + noise
+    });
+    (x, y)
+}
+
+fn compare(second: f64) -> (FTest<f64>, [f64; 2], [f64; 2]) {
+    let (x, y) = peaks(second);
+    let mut one = [Gaussian::<f64> {
+        a: 1.0,
+        x_c: 0.0,
+        sigma: 1.0,
+    }];
+    let simple = fit_stat!(&mut one, x, y);
+    let mut two = [
+        Gaussian::<f64> {
+            a: 1.5,
+            x_c: -1.2,
+            sigma: 0.7,
+        },
+        Gaussian::<f64> {
+            a: 0.8,
+            x_c: 1.7,
+            sigma: 0.6,
+        },
+    ];
+    let complex = fit_stat!(&mut two, x, y);
+    assert!(complex.chi2 <= simple.chi2);
+
+    let test = simple.f_test(&complex);
+    assert_eq!(test.dof, (3, 54));
+    (
+        test,
+        [simple.aicc(), complex.aicc()],
+        [simple.bic(), complex.bic()],
+    )
+}
+
+#[test]
+fn two_peaks() {
+    let (test, aicc, bic) = compare(1.0);
+    assert!(test.p_value < 1e-10, "{test:?}");
+    assert!(aicc[1] < aicc[0]);
+    assert!(bic[1] < bic[0]);
+}
+
+#[test]
+fn one_peak() {
+    // second peak is buried in the noise
+    let (test, _, bic) = compare(0.002);
+    assert!(test.p_value > 0.01, "{test:?}");
+    assert!(bic[0] < bic[1]);
+}
+
+#[test]
+fn f_test_not_nested() {
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y);
+    let test = stat.f_test(&stat);
+    assert!(test.statistic.is_nan());
+    assert!(test.p_value.is_nan());
+}
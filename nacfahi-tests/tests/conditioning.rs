@@ -0,0 +1,78 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use approx::assert_relative_eq;
+use common::{X, Y};
+use nacfahi::{
+    models::{
+        basic::Linear,
+        utility::{FnModel, NumericJacobian},
+    },
+    *,
+};
+
+#[test]
+fn regular() {
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y);
+
+    // normal matrix is [[sum x^2, sum x], [sum x, n]]
+    let sxx = X.iter().map(|x| x * x).sum::<f64>();
+    let sx = X.iter().sum::<f64>();
+    let n = X.len() as f64;
+    let half_trace = (sxx + n) / 2.0;
+    let root = (half_trace * half_trace - (sxx * n - sx * sx)).sqrt();
+    let expected = ((half_trace + root) / (half_trace - root)).sqrt();
+
+    let Conditioning::Regular { condition_number } = stat.conditioning else {
+        panic!("Expected regular fit, got {:?}", stat.conditioning);
+    };
+    assert_relative_eq!(condition_number, expected, max_relative = 1e-10);
+    assert!(stat.conditioning.is_regular());
+
+    let correlation = stat.correlation_matrix();
+    let covariance = &stat.covariance_matrix;
+    assert_relative_eq!(correlation[(0, 0)], 1.0, max_relative = 1e-12);
+    assert_relative_eq!(correlation[(1, 1)], 1.0, max_relative = 1e-12);
+    assert_relative_eq!(correlation[(0, 1)], correlation[(1, 0)]);
+    assert_relative_eq!(
+        correlation[(0, 1)],
+        covariance[(0, 1)] / (covariance[(0, 0)] * covariance[(1, 1)]).sqrt(),
+        max_relative = 1e-12
+    );
+    // slope and offset correlate, but not that much
+    assert_eq!(stat.degenerate_pairs(0.99).count(), 0);
+    assert_eq!(stat.degenerate_pairs(0.5).count(), 1);
+}
+
+#[test]
+fn nearly_degenerate() {
+    // far from the origin, slope and offset are hard to tell apart
+    let x = X.map(|x| 1000.0 + x / 10.0);
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, x, Y);
+
+    let Conditioning::IllConditioned { condition_number } = stat.conditioning else {
+        panic!("Expected ill-conditioned fit, got {:?}", stat.conditioning);
+    };
+    assert!(condition_number > 1e4);
+
+    let pairs: Vec<_> = stat.degenerate_pairs(0.99).collect();
+    assert_eq!(pairs.len(), 1);
+    let (i, j, correlation) = pairs[0];
+    assert_eq!((i, j), (0, 1));
+    assert!(correlation < -0.99);
+}
+
+#[test]
+fn singular() {
+    // only the sum of parameters matters
+    let model = FnModel::new([1.0, 1.0], |[a, b]: [f64; 2], x: f64| (a + b) * x);
+    let mut model = NumericJacobian::new(model);
+    let stat = fit_stat!(&mut model, X, Y);
+
+    assert_eq!(stat.conditioning, Conditioning::Singular { rank: 1 });
+    assert!(stat.conditioning.condition_number().is_infinite());
+    assert!(!stat.conditioning.is_regular());
+}
@@ -1,8 +1,14 @@
 #![allow(missing_docs)]
 #![cfg(feature = "alloc")]
 
-use approx::assert_ulps_eq;
-use nacfahi::{FitStat, LevenbergMarquardt, fit_stat, models::basic::Constant};
+use approx::{assert_relative_eq, assert_ulps_eq};
+use nacfahi::{
+    FitStat, LevenbergMarquardt, fit_stat,
+    models::{
+        FitModel,
+        basic::{Constant, Gaussian, GenericGaussian},
+    },
+};
 use rand::{Rng, rng};
 
 #[test]
@@ -48,3 +54,61 @@ fn constant_stdev() {
     assert_ulps_eq!(model.c, y_mean, epsilon = 1e-10);
     assert_ulps_eq!(errors.c, y_err, epsilon = 1e-1); // idk what exactly am I doing at this point :death_emoji:
 }
+
+#[test]
+fn gaussian_error_order() {
+    // errors come out in parameter order: a, x_c, sigma, s_p
+    let x: [f64; 41] = core::array::from_fn(|i| -4.0 + 0.2 * i as f64);
+    let noise = |x: f64| 0.05 * (37.0 * x).sin();
+
+    let truth = Gaussian::<f64> {
+        a: 5.0,
+        x_c: 0.3,
+        sigma: 1.2,
+    };
+    let y = x.map(|x| truth.evaluate(&x) + noise(x));
+    let mut model = truth;
+    let FitStat {
+        errors,
+        covariance_matrix,
+        ..
+    } = fit_stat!(&mut model, x, y);
+    assert_relative_eq!(errors.a_err, covariance_matrix[(0, 0)].sqrt());
+    assert_relative_eq!(errors.x_c_err, covariance_matrix[(1, 1)].sqrt());
+    assert_relative_eq!(errors.s_err, covariance_matrix[(2, 2)].sqrt());
+
+    let truth = GenericGaussian::<f64, true, true, false> {
+        a: 5.0,
+        x_c: 0.3,
+        sigma: 1.2,
+        s_p: 0.4,
+    };
+    let y = x.map(|x| truth.evaluate(&x) + noise(x));
+    let mut model = truth;
+    let FitStat {
+        errors,
+        covariance_matrix,
+        ..
+    } = fit_stat!(&mut model, x, y);
+    assert_relative_eq!(errors.a_err, covariance_matrix[(0, 0)].sqrt());
+    assert_relative_eq!(errors.x_c_err, covariance_matrix[(1, 1)].sqrt());
+    assert_relative_eq!(errors.s_err, covariance_matrix[(2, 2)].sqrt());
+
+    let truth = GenericGaussian::<f64, true, true, true> {
+        a: 5.0,
+        x_c: 0.3,
+        sigma: 1.2,
+        s_p: 0.4,
+    };
+    let y = x.map(|x| truth.evaluate(&x) + noise(x));
+    let mut model = truth;
+    let FitStat {
+        errors,
+        covariance_matrix,
+        ..
+    } = fit_stat!(&mut model, x, y);
+    assert_relative_eq!(errors.a_err, covariance_matrix[(0, 0)].sqrt());
+    assert_relative_eq!(errors.x_c_err, covariance_matrix[(1, 1)].sqrt());
+    assert_relative_eq!(errors.s_err, covariance_matrix[(2, 2)].sqrt());
+    assert_relative_eq!(errors.s_p_err, covariance_matrix[(3, 3)].sqrt());
+}
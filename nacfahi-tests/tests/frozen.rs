@@ -0,0 +1,117 @@
+#![allow(missing_docs)]
+#![cfg(feature = "alloc")]
+
+use nacfahi::models::{FitModel, basic::Gaussian};
+
+fn peak_data() -> ([f64; 30], [f64; 30]) {
+    let truth = Gaussian::<f64> {
+        a: 2.0,
+        x_c: 0.4,
+        sigma: 0.7,
+    };
+    let x: [f64; 30] = core::array::from_fn(|i| -3.0 + i as f64 * 0.2);
+    let y = x.map(|x| truth.evaluate(&x) + 0.01 * (x * 12.9898).sin());
+    (x, y)
+}
+
+#[test]
+fn same_as_const_freezing() {
+    use approx::assert_relative_eq;
+    use nacfahi::*;
+
+    let (x, y) = peak_data();
+
+    // sigma frozen at compile time
+    let mut fixed = Gaussian::<f64, false> {
+        a: 1.0,
+        x_c: 0.0,
+        sigma: 0.65,
+    };
+    let fixed_stat = fit_stat!(&mut fixed, x, y);
+
+    // and at runtime
+    let mut frozen = Gaussian::<f64> {
+        a: 1.0,
+        x_c: 0.0,
+        sigma: 0.65,
+    };
+    let frozen_stat = fit_stat_frozen(
+        &mut frozen,
+        x,
+        y,
+        [false, false, true],
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+
+    assert!(
+        frozen_stat.report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+    assert_eq!(frozen.sigma, 0.65, "Frozen parameter should not change");
+    assert_relative_eq!(frozen.a, fixed.a, max_relative = 1e-8);
+    assert_relative_eq!(frozen.x_c, fixed.x_c, max_relative = 1e-8);
+    assert_relative_eq!(
+        frozen_stat.reduced_chi2,
+        fixed_stat.reduced_chi2,
+        max_relative = 1e-6
+    );
+    assert_relative_eq!(
+        frozen_stat.errors.a_err,
+        fixed_stat.errors.a_err,
+        max_relative = 1e-6
+    );
+    assert_relative_eq!(
+        frozen_stat.errors.x_c_err,
+        fixed_stat.errors.x_c_err,
+        max_relative = 1e-6
+    );
+    assert_eq!(frozen_stat.errors.s_err, 0.0);
+
+    let covariance = frozen_stat.covariance_matrix;
+    for i in 0..3 {
+        assert_eq!(covariance[(i, 2)], 0.0);
+        assert_eq!(covariance[(2, i)], 0.0);
+    }
+}
+
+#[test]
+fn mask_decided_at_runtime() {
+    use nacfahi::*;
+
+    let (x, y) = peak_data();
+    let initial = Gaussian::<f64> {
+        a: 1.0,
+        x_c: 0.0,
+        sigma: 1.0,
+    };
+
+    for param in 0..3 {
+        let mut frozen = [false; 3];
+        frozen[param] = true;
+
+        let mut model = initial;
+        let report = fit_frozen(
+            &mut model,
+            x.as_slice(),
+            y.as_slice(),
+            frozen,
+            LevenbergMarquardt::new(),
+            default_weights,
+        );
+        assert!(
+            report.termination.was_successful(),
+            "Approximation should be successful"
+        );
+
+        let initial: [f64; 3] = initial.get_params().into().into_array();
+        let fitted: [f64; 3] = model.get_params().into().into_array();
+        for i in 0..3 {
+            if i == param {
+                assert_eq!(fitted[i], initial[i], "Frozen parameter should not change");
+            } else {
+                assert_ne!(fitted[i], initial[i], "Free parameter should change");
+            }
+        }
+    }
+}
@@ -48,5 +48,6 @@ pub fn fit_err_generic2<
 
     model.params[0] = 0.0; // test for "borrowed model"
 
+    core::hint::black_box(model);
     core::hint::black_box(stat);
 }
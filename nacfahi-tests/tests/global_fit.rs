@@ -0,0 +1,92 @@
+#![allow(missing_docs)]
+
+#[cfg(feature = "alloc")]
+#[test]
+fn shared_linewidth() {
+    use approx::assert_abs_diff_eq;
+    use nacfahi::{
+        models::{FitModel, basic::Gaussian},
+        *,
+    };
+
+    const SIGMA: f64 = 0.8;
+    let peaks = [(1.0, -1.0), (2.5, 0.0), (0.5, 1.5), (4.0, -0.5), (3.0, 0.3)];
+
+    // spectra of different lengths
+    let data: Vec<(Vec<f64>, Vec<f64>)> = peaks
+        .iter()
+        .enumerate()
+        .map(|(i, &(a, x_c))| {
+            let truth = Gaussian::<f64> {
+                a,
+                x_c,
+                sigma: SIGMA,
+            };
+            let x: Vec<f64> = (0..30 + 5 * i).map(|j| -4.0 + j as f64 * 0.25).collect();
+            let y = x.iter().map(|x| truth.evaluate(x)).collect();
+            (x, y)
+        })
+        .collect();
+
+    let mut models: Vec<Gaussian<f64>> = peaks
+        .iter()
+        .map(|&(a, x_c)| Gaussian {
+            a: a * 0.8,
+            x_c: x_c + 0.2,
+            sigma: 1.0,
+        })
+        .collect();
+
+    let result = fit_global(
+        models
+            .iter_mut()
+            .zip(&data)
+            .map(|(model, (x, y))| (model, x.as_slice(), y.as_slice())),
+        [false, false, true],
+        LevenbergMarquardt::new(),
+    );
+    assert!(
+        result.report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+    drop(result);
+
+    // shared parameter is written back to each of the models
+    for (model, &(a, x_c)) in models.iter().zip(&peaks) {
+        assert_abs_diff_eq!(model.sigma, SIGMA, epsilon = 1e-9);
+        assert_abs_diff_eq!(model.a, a, epsilon = 1e-9);
+        assert_abs_diff_eq!(model.x_c, x_c, epsilon = 1e-9);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owned_models() {
+    use approx::assert_ulps_eq;
+    use nacfahi::{models::basic::Linear, *};
+
+    // same offset, different slopes
+    let x = [0.0, 1.0, 2.0, 3.0];
+    let y1 = [1.0, 2.0, 3.0, 4.0];
+    let y2 = [1.0, 4.0, 7.0, 10.0];
+    let y3 = [1.0, -1.0, -3.0, -5.0];
+
+    let line = Linear { a: 0.0, b: 0.0 };
+    let result = fit_global(
+        [(line, x, y1), (line, x, y2), (line, x, y3)],
+        [false, true],
+        LevenbergMarquardt::new(),
+    );
+
+    assert!(
+        result.report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+    let slopes: Vec<f64> = result.models.iter().map(|line| line.a).collect();
+    assert_ulps_eq!(slopes[0], 1.0);
+    assert_ulps_eq!(slopes[1], 3.0);
+    assert_ulps_eq!(slopes[2], -2.0);
+    for line in &result.models {
+        assert_ulps_eq!(line.b, 1.0);
+    }
+}
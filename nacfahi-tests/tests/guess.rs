@@ -0,0 +1,166 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use approx::{assert_abs_diff_eq, assert_relative_eq};
+use common::grid;
+use nacfahi::{
+    models::{
+        FitModel,
+        basic::{Constant, Exponent, Gaussian, GenericGaussian, Linear, Polynomial},
+        utility::{Tie, Tied},
+    },
+    *,
+};
+
+#[test]
+fn exact_guesses() {
+    let x = grid::<9>(-2.0, 0.5);
+
+    let y = x.map(|_| 4.0);
+    let mut constant = Constant { c: 0.0 };
+    guess_params(&mut constant, &x, &y);
+    assert_relative_eq!(constant.c, 4.0);
+
+    let y = x.map(|x| 3.0 * x - 2.0);
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    guess_params(&mut line, &x, &y);
+    assert_relative_eq!(line.a, 3.0, max_relative = 1e-12);
+    assert_relative_eq!(line.b, -2.0, max_relative = 1e-12);
+
+    let y = x.map(|x| -2.5 * (0.7 * x).exp());
+    let mut exponent = Exponent { a: 1.0, b: 0.0 };
+    guess_params(&mut exponent, &x, &y);
+    assert_relative_eq!(exponent.a, -2.5, max_relative = 1e-10);
+    assert_relative_eq!(exponent.b, 0.7, max_relative = 1e-10);
+
+    // far from zero, so powers of x are poorly conditioned
+    let x = grid::<9>(1000.0, 0.5);
+    let y = x.map(|x| 1.0 - 0.5 * (x - 1001.0) + 0.25 * (x - 1001.0).powi(2));
+    let mut polynomial = Polynomial::<3, f64> { params: [0.0; 3] };
+    guess_params(&mut polynomial, &x, &y);
+    for (&x, &y) in x.iter().zip(&y) {
+        assert_relative_eq!(polynomial.evaluate(&x), y, max_relative = 1e-6);
+    }
+}
+
+#[test]
+fn empty_data() {
+    let mut line = Linear { a: 1.0, b: 2.0 };
+    guess_params(&mut line, &[0.0f64; 0], &[0.0f64; 0]);
+    assert_eq!(line, Linear { a: 1.0, b: 2.0 });
+
+    let mut gaussian = Gaussian::<f64> {
+        a: 1.0,
+        x_c: 2.0,
+        sigma: 3.0,
+    };
+    guess_params(&mut gaussian, &[1.0, 2.0], &[0.0, 0.0]);
+    assert_eq!(
+        gaussian,
+        Gaussian::<f64> {
+            a: 1.0,
+            x_c: 2.0,
+            sigma: 3.0
+        }
+    );
+}
+
+#[test]
+fn gaussian_moments() {
+    let truth = Gaussian::<f64> {
+        a: -12.0,
+        x_c: 3.3,
+        sigma: 1.5,
+    };
+    let x = grid::<121>(-3.0, 0.1);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut gaussian = Gaussian::<f64> {
+        a: 1.0,
+        x_c: 0.0,
+        sigma: 1.0,
+    };
+    guess_params(&mut gaussian, &x, &y);
+    assert_abs_diff_eq!(gaussian.x_c, truth.x_c, epsilon = 0.05);
+    assert_relative_eq!(gaussian.sigma, truth.sigma, max_relative = 0.1);
+    assert_relative_eq!(gaussian.a, truth.a, max_relative = 0.1);
+}
+
+#[test]
+fn asymmetric_gaussian_moments() {
+    let truth = GenericGaussian::<f64, true, true, true> {
+        a: 5.0,
+        x_c: 1.0,
+        sigma: 0.5,
+        s_p: 0.5,
+    };
+    let x = grid::<121>(-4.0, 0.075);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut gaussian = GenericGaussian::<f64, true, true, true> {
+        a: 1.0,
+        x_c: 0.0,
+        sigma: 1.0,
+        s_p: 0.0,
+    };
+    guess_params(&mut gaussian, &x, &y);
+    assert_abs_diff_eq!(gaussian.x_c, truth.x_c, epsilon = 0.3);
+    assert_relative_eq!(gaussian.sigma, truth.sigma, max_relative = 0.2);
+    assert_abs_diff_eq!(gaussian.s_p, truth.s_p, epsilon = 0.3);
+}
+
+#[test]
+fn two_peaks() {
+    let truth = [
+        Gaussian::<f64> {
+            a: 10.0,
+            x_c: -3.0,
+            sigma: 0.8,
+        },
+        Gaussian::<f64> {
+            a: 6.0,
+            x_c: 4.0,
+            sigma: 1.2,
+        },
+    ];
+    let x = grid::<121>(-7.0, 0.125);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    // both start at the same spot, so fit alone would never split them
+    let start = Gaussian::<f64> {
+        a: 1.0,
+        x_c: 0.0,
+        sigma: 1.0,
+    };
+    let mut peaks = [start; 2];
+    let report = fit!(&mut peaks, x, y, guess = true);
+
+    assert!(report.termination.was_successful());
+    for (peak, truth) in peaks.iter().zip(&truth) {
+        assert_relative_eq!(peak.a, truth.a, max_relative = 1e-6);
+        assert_relative_eq!(peak.x_c, truth.x_c, max_relative = 1e-6);
+        assert_relative_eq!(peak.sigma, truth.sigma, max_relative = 1e-6);
+    }
+}
+
+#[test]
+fn guess_is_optional() {
+    let x = grid::<5>(1.0, 1.0);
+    let y = x.map(|x| 2.0 * x + 1.0);
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, x, y, guess = false, weights = default_weights);
+    assert!(stat.report.termination.was_successful());
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, x, y, guess = true);
+    assert!(stat.report.termination.was_successful());
+    assert_relative_eq!(line.a, 2.0, max_relative = 1e-12);
+    assert_relative_eq!(line.b, 1.0, max_relative = 1e-12);
+
+    // tied model can't guess its parameters, but it does not need to
+    let mut tied = Tied::<_, U<2>>::new(Linear { a: 0.0, b: 0.0 }, [Tie::Free, Tie::Free]);
+    let report = fit!(&mut tied, x, y, guess = false);
+    assert!(report.termination.was_successful());
+}
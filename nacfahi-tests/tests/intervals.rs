@@ -0,0 +1,98 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use approx::assert_relative_eq;
+use common::{X, Y};
+use nacfahi::{
+    models::{
+        basic::Linear,
+        utility::{Bounded, ParamBound},
+    },
+    *,
+};
+
+#[test]
+fn parameter_intervals() {
+    let n = X.len() as f64;
+    let x_mean = X.iter().sum::<f64>() / n;
+    let sxx = X.iter().map(|x| (x - x_mean).powi(2)).sum::<f64>();
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y);
+    assert!(stat.report.termination.was_successful());
+
+    let s = stat.reduced_chi2.sqrt();
+    let a_error = s / sxx.sqrt();
+    let b_error = s * (1.0 / n + x_mean * x_mean / sxx).sqrt();
+    assert_relative_eq!(stat.errors.a, a_error, max_relative = 1e-8);
+    assert_relative_eq!(stat.errors.b, b_error, max_relative = 1e-8);
+
+    // t(0.995, 6)
+    let t = 3.707_428_021_324_907;
+    let [a, b] = stat.parameter_intervals(&line, 0.99).into_array();
+    assert_relative_eq!(*a.start(), line.a - t * a_error, max_relative = 1e-8);
+    assert_relative_eq!(*a.end(), line.a + t * a_error, max_relative = 1e-8);
+    assert_relative_eq!(*b.start(), line.b - t * b_error, max_relative = 1e-8);
+    assert_relative_eq!(*b.end(), line.b + t * b_error, max_relative = 1e-8);
+
+    let errors = stat.confidence_errors(&line, 0.99);
+    assert_relative_eq!(errors.a, t * a_error, max_relative = 1e-8);
+    assert_relative_eq!(errors.b, t * b_error, max_relative = 1e-8);
+}
+
+#[test]
+fn bounded_parameter_intervals() {
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y);
+
+    // optimizer sees transformed parameters, but intervals are of actual ones
+    let mut bounded = Bounded::new(
+        Linear { a: 1.0, b: 0.0 },
+        [ParamBound::Both(0.0, 10.0), ParamBound::Lower(-10.0)],
+    );
+    let bounded_stat = fit_stat!(&mut bounded, X, Y);
+    assert!(bounded_stat.report.termination.was_successful());
+
+    let expected = stat.parameter_intervals(&line, 0.95);
+    let actual = bounded_stat.parameter_intervals(&bounded, 0.95);
+    for (expected, actual) in expected.iter().zip(&actual) {
+        assert_relative_eq!(*actual.start(), *expected.start(), max_relative = 1e-6);
+        assert_relative_eq!(*actual.end(), *expected.end(), max_relative = 1e-6);
+    }
+}
+
+#[test]
+fn goodness_of_fit() {
+    // two degrees of freedom make chi2 distribution an exponential one
+    let x = [0.0, 1.0, 2.0, 3.0];
+    let y = [1.0, 3.2, 4.9, 7.1];
+    let sigma = [0.1; 4];
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, x, y, sigma = sigma);
+    assert_eq!(stat.dof, 2);
+    assert_relative_eq!(
+        stat.p_value(),
+        (-stat.chi2 / 2.0).exp(),
+        max_relative = 1e-10
+    );
+
+    // can't say anything with no degrees of freedom
+    let mut line = Linear::<f64> { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, [0.0, 1.0], [1.0, 2.0], sigma = [0.1; 2]);
+    assert!(stat.p_value().is_nan());
+}
+
+#[test]
+fn no_degrees_of_freedom() {
+    // line through two points leaves nothing to estimate data variance from
+    let mut line = Linear::<f64> { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, [0.0, 1.0], [1.0, 2.0]);
+    assert_eq!(stat.dof, 0);
+    assert!(stat.interval_quantile(0.95).is_nan());
+
+    // with known errors, normal distribution does not need degrees of freedom
+    let stat = fit_stat!(&mut line, [0.0, 1.0], [1.0, 2.0], sigma = [0.1; 2]);
+    assert!(stat.interval_quantile(0.95).is_finite());
+}
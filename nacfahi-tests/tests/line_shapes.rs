@@ -0,0 +1,193 @@
+#![allow(missing_docs)]
+
+use approx::assert_relative_eq;
+use nacfahi::{
+    models::{
+        FitModel,
+        basic::{Gaussian, Lorentzian, PseudoVoigt, Voigt},
+    },
+    *,
+};
+
+/// Trapezoid rule over `[-range, range]`.
+fn area(model: &impl FitModel<Scalar = f64>, range: f64) -> f64 {
+    let steps = 200_000;
+    let h = 2.0 * range / f64::from(steps);
+    (0..=steps)
+        .map(|i| {
+            let x = -range + h * f64::from(i);
+            let weight = if i == 0 || i == steps { 0.5 } else { 1.0 };
+            weight * model.evaluate(&x)
+        })
+        .sum::<f64>()
+        * h
+}
+
+#[test]
+fn unit_area() {
+    let voigt = Voigt {
+        a: 3.0,
+        x_c: 0.5,
+        sigma: 1.0,
+        gamma: 0.5,
+    };
+    // lorentzian tails are heavy: area beyond R is about 2 gamma / (pi R)
+    let tails = 2.0 * 0.5 / (core::f64::consts::PI * 2000.0);
+    assert_relative_eq!(
+        area(&voigt, 2000.0),
+        3.0 * (1.0 - tails),
+        max_relative = 1e-6
+    );
+
+    let lorentzian = Lorentzian {
+        a: 2.0,
+        x_c: 0.0,
+        gamma: 0.1,
+    };
+    let tails = 2.0 * 0.1 / (core::f64::consts::PI * 2000.0);
+    assert_relative_eq!(
+        area(&lorentzian, 2000.0),
+        2.0 * (1.0 - tails),
+        max_relative = 1e-6
+    );
+
+    let pseudo_voigt = PseudoVoigt {
+        a: 1.5,
+        x_c: 0.0,
+        w: 1.0,
+        eta: 0.0,
+    };
+    assert_relative_eq!(area(&pseudo_voigt, 20.0), 1.5, max_relative = 1e-9);
+}
+
+#[test]
+fn voigt_limits() {
+    let gaussian = Gaussian::<f64> {
+        a: 2.0,
+        x_c: 1.0,
+        sigma: 0.7,
+    };
+    let gaussian_limit = Voigt {
+        a: 2.0,
+        x_c: 1.0,
+        sigma: 0.7,
+        gamma: 0.0,
+    };
+    let lorentzian = Lorentzian {
+        a: 2.0,
+        x_c: 1.0,
+        gamma: 0.7,
+    };
+    let lorentzian_limit = Voigt {
+        a: 2.0,
+        x_c: 1.0,
+        sigma: 1e-6,
+        gamma: 0.7,
+    };
+    for x in [-2.0, 0.0, 0.5, 1.0, 1.3, 3.0] {
+        assert_relative_eq!(
+            gaussian_limit.evaluate(&x),
+            gaussian.evaluate(&x),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            lorentzian_limit.evaluate(&x),
+            lorentzian.evaluate(&x),
+            max_relative = 1e-9
+        );
+    }
+
+    let pure_lorentzian = PseudoVoigt {
+        a: 2.0,
+        x_c: 1.0,
+        w: 1.4,
+        eta: 1.0,
+    };
+    for x in [-2.0, 0.0, 1.0, 3.0] {
+        assert_relative_eq!(
+            pure_lorentzian.evaluate(&x),
+            lorentzian.evaluate(&x),
+            max_relative = 1e-12
+        );
+    }
+}
+
+#[test]
+fn half_maximum() {
+    fn check(model: &impl FitModel<Scalar = f64>, x_c: f64, fwhm: f64, max_relative: f64) {
+        let peak = model.evaluate(&x_c);
+        assert_relative_eq!(
+            model.evaluate(&(x_c + fwhm / 2.0)),
+            peak / 2.0,
+            max_relative = max_relative
+        );
+        assert_relative_eq!(
+            model.evaluate(&(x_c - fwhm / 2.0)),
+            peak / 2.0,
+            max_relative = max_relative
+        );
+    }
+
+    let lorentzian = Lorentzian {
+        a: 1.0,
+        x_c: 2.0,
+        gamma: 0.3,
+    };
+    check(&lorentzian, 2.0, lorentzian.fwhm(), 1e-12);
+
+    let pseudo_voigt = PseudoVoigt {
+        a: 1.0,
+        x_c: 2.0,
+        w: 0.6,
+        eta: 0.4,
+    };
+    check(&pseudo_voigt, 2.0, pseudo_voigt.fwhm(), 1e-12);
+
+    let voigt = Voigt {
+        a: 1.0,
+        x_c: 2.0,
+        sigma: 0.3,
+        gamma: 0.2,
+    };
+    // approximation is good to 0.02% in FWHM, which is a bit more in value
+    check(&voigt, 2.0, voigt.fwhm(), 1e-3);
+}
+
+#[test]
+fn fit_voigt() {
+    let truth = Voigt {
+        a: 10.0,
+        x_c: 0.3,
+        sigma: 0.6,
+        gamma: 0.4,
+    };
+    let x: [f64; 81] = core::array::from_fn(|i| -6.0 + 0.15 * i as f64);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut voigt = Voigt {
+        a: 1.0,
+        x_c: 0.0,
+        sigma: 1.0,
+        gamma: 1.0,
+    };
+    let stat = fit_stat!(&mut voigt, x, y, guess = true);
+
+    assert!(stat.report.termination.was_successful());
+    assert_relative_eq!(voigt.a, truth.a, max_relative = 1e-8);
+    assert_relative_eq!(voigt.x_c, truth.x_c, max_relative = 1e-8);
+    assert_relative_eq!(voigt.sigma, truth.sigma, max_relative = 1e-8);
+    assert_relative_eq!(voigt.gamma, truth.gamma, max_relative = 1e-8);
+    assert!(stat.errors.sigma < 1e-6);
+
+    // pseudo-voigt is not exact, but should land close
+    let mut pseudo_voigt = PseudoVoigt {
+        a: 1.0,
+        x_c: 0.0,
+        w: 1.0,
+        eta: 0.5,
+    };
+    let report = fit!(&mut pseudo_voigt, x, y, guess = true);
+    assert!(report.termination.was_successful());
+    assert_relative_eq!(pseudo_voigt.a, truth.a, max_relative = 0.03);
+    assert_relative_eq!(pseudo_voigt.fwhm(), truth.fwhm(), max_relative = 0.02);
+}
@@ -0,0 +1,138 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use approx::assert_relative_eq;
+use common::{X, Y};
+use nacfahi::{
+    models::{
+        FitModel,
+        basic::{Linear, Polynomial},
+    },
+    *,
+};
+
+#[test]
+fn matches_iterative() {
+    let mut iterative = Linear { a: 0.0, b: 0.0 };
+    let expected = fit_stat!(&mut iterative, X, Y);
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_linear(&mut line, X, Y, default_weights);
+
+    assert!(stat.report.termination.was_successful());
+    assert_eq!(stat.report.number_of_evaluations, 1);
+    assert_relative_eq!(line.a, iterative.a, max_relative = 1e-10);
+    assert_relative_eq!(line.b, iterative.b, max_relative = 1e-10);
+    assert_relative_eq!(stat.chi2, expected.chi2, max_relative = 1e-10);
+    assert_relative_eq!(
+        stat.report.objective_function,
+        stat.chi2 / 2.0,
+        max_relative = 1e-12
+    );
+    assert_relative_eq!(stat.errors.a, expected.errors.a, max_relative = 1e-8);
+    assert_relative_eq!(stat.errors.b, expected.errors.b, max_relative = 1e-8);
+    assert!(stat.conditioning.is_regular());
+}
+
+#[test]
+fn sigma() {
+    let sigma = [0.1, 0.2, 0.1, 0.3, 0.1, 0.2, 0.5, 0.1];
+
+    let mut iterative = Linear { a: 0.0, b: 0.0 };
+    let expected = fit_stat!(&mut iterative, X, Y, sigma = sigma);
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_linear_sigma(&mut line, X, Y, sigma, default_weights);
+
+    assert!(stat.absolute_sigma);
+    assert_relative_eq!(line.a, iterative.a, max_relative = 1e-10);
+    assert_relative_eq!(line.b, iterative.b, max_relative = 1e-10);
+    assert_relative_eq!(stat.chi2, expected.chi2, max_relative = 1e-10);
+    assert_relative_eq!(stat.errors.a, expected.errors.a, max_relative = 1e-8);
+}
+
+#[test]
+fn far_from_origin() {
+    // raw powers of x span 12 orders of magnitude here
+    let x: [f64; 10] = core::array::from_fn(|i| 1000.0 + i as f64);
+    let truth = Polynomial {
+        params: [3.0e6, -6.0e3, 3.0, 1.0e-6],
+    };
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut polynomial = Polynomial::<4, f64> { params: [0.0; 4] };
+    let stat = fit_linear(&mut polynomial, x, y, default_weights);
+
+    assert!(stat.report.termination.was_successful());
+    for (&x, &y) in x.iter().zip(&y) {
+        assert_relative_eq!(polynomial.evaluate(&x), y, epsilon = 1e-6);
+    }
+}
+
+#[test]
+fn dependent_parameters() {
+    // with a single x value, slope and offset can't be told apart
+    let x = [2.0; 4];
+    let y = [3.0, 5.0, 4.0, 4.0];
+
+    let mut line = Linear { a: 1.0, b: 1.0 };
+    let stat = fit_linear(&mut line, x, y, default_weights);
+
+    assert!(stat.report.termination.was_successful());
+    assert_relative_eq!(line.evaluate(&2.0), 4.0, max_relative = 1e-12);
+    assert_eq!(line.b, 0.0);
+    assert!(matches!(
+        stat.conditioning,
+        Conditioning::Singular { rank: 1 }
+    ));
+}
+
+#[test]
+fn sum_of_models() {
+    let y = X.map(|x| 0.5 * x + 2.0);
+
+    // two lines are fully degenerate, so only their sum is meaningful
+    let mut lines = [Linear { a: 0.0, b: 0.0 }; 2];
+    let stat = fit_linear(&mut lines, X, y, default_weights);
+
+    assert!(stat.report.termination.was_successful());
+    assert!(matches!(
+        stat.conditioning,
+        Conditioning::Singular { rank: 2 }
+    ));
+    assert_relative_eq!(lines[0].a + lines[1].a, 0.5, max_relative = 1e-12);
+    assert_relative_eq!(lines[0].b + lines[1].b, 2.0, max_relative = 1e-12);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn dynamic_points() {
+    let x = X.to_vec();
+    let y = Y.to_vec();
+
+    let mut expected = Linear { a: 0.0, b: 0.0 };
+    let _ = fit_linear(&mut expected, X, Y, default_weights);
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_linear(&mut line, x.as_slice(), y.as_slice(), default_weights);
+
+    assert!(stat.report.termination.was_successful());
+    assert_relative_eq!(line.a, expected.a, max_relative = 1e-12);
+    assert_relative_eq!(line.b, expected.b, max_relative = 1e-12);
+}
+
+#[test]
+fn non_finite() {
+    let mut y = Y;
+    y[3] = f64::NAN;
+
+    let mut line = Linear { a: 1.0, b: 2.0 };
+    let stat = fit_linear(&mut line, X, y, default_weights);
+
+    assert!(matches!(
+        stat.report.termination,
+        TerminationReason::Numerical(_)
+    ));
+    assert_eq!(line, Linear { a: 1.0, b: 2.0 });
+}
@@ -0,0 +1,121 @@
+#![allow(missing_docs)]
+
+#[cfg(feature = "nalgebra")]
+use nacfahi::{GenericArray, U, models::MultiInputModel};
+
+/// z = a*x + b*y + c
+#[cfg(feature = "nalgebra")]
+struct Plane {
+    a: f64,
+    b: f64,
+    c: f64,
+}
+
+#[cfg(feature = "nalgebra")]
+impl MultiInputModel for Plane {
+    type Scalar = f64;
+    type Inputs = U<2>;
+    type ParamCount = U<3>;
+
+    fn evaluate(&self, x: &GenericArray<f64, U<2>>) -> f64 {
+        self.a * x[0] + self.b * x[1] + self.c
+    }
+
+    fn jacobian(&self, x: &GenericArray<f64, U<2>>) -> impl Into<GenericArray<f64, U<3>>> {
+        [x[0], x[1], 1.0]
+    }
+
+    fn set_params(&mut self, new_params: GenericArray<f64, U<3>>) {
+        [self.a, self.b, self.c] = new_params.into_array();
+    }
+
+    fn get_params(&self) -> impl Into<GenericArray<f64, U<3>>> {
+        [self.a, self.b, self.c]
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn plane_matrix() {
+    use approx::assert_ulps_eq;
+    use nacfahi::*;
+    use nalgebra::matrix;
+
+    // some data, presumably 2x - 3y + 1
+    let x = matrix![
+        0.0, 0.0;
+        1.0, 0.0;
+        0.0, 1.0;
+        1.0, 1.0;
+        2.0, 3.0
+    ];
+    let z = matrix![1.0; 3.0; -2.0; 0.0; -4.0];
+
+    let mut plane = Plane {
+        a: 0.0,
+        b: 0.0,
+        c: 0.0,
+    };
+
+    let report = fit_multi_input(&mut plane, x, z, LevenbergMarquardt::new(), |_, _| 1.0);
+
+    assert!(
+        report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+    assert_ulps_eq!(plane.a, 2.0);
+    assert_ulps_eq!(plane.b, -3.0);
+    assert_ulps_eq!(plane.c, 1.0);
+}
+
+#[cfg(all(feature = "nalgebra", feature = "alloc"))]
+#[test]
+fn plane_dyn_matrix() {
+    use approx::assert_ulps_eq;
+    use nacfahi::*;
+    use nalgebra::{Dyn, OMatrix, OVector, U2};
+
+    // some data, presumably 0.5x + 4y - 2
+    let x = OMatrix::<f64, Dyn, U2>::from_fn(20, |i, j| {
+        let i = i as f64;
+        if j == 0 { i } else { (i * 0.7).sin() }
+    });
+    let z = OVector::<f64, Dyn>::from_fn(20, |i, _| 0.5 * x[(i, 0)] + 4.0 * x[(i, 1)] - 2.0);
+
+    let mut plane = Plane {
+        a: 0.0,
+        b: 0.0,
+        c: 0.0,
+    };
+
+    let report = fit_multi_input(&mut plane, &x, &z, LevenbergMarquardt::new(), |_, _| 1.0);
+
+    assert!(
+        report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+    assert_ulps_eq!(plane.a, 0.5, epsilon = 1e-12);
+    assert_ulps_eq!(plane.b, 4.0, epsilon = 1e-12);
+    assert_ulps_eq!(plane.c, -2.0, epsilon = 1e-12);
+}
+
+#[test]
+fn scalar_model() {
+    use approx::assert_ulps_eq;
+    use nacfahi::{models::basic::Linear, *};
+
+    // scalar models are single-input models as well
+    let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = [3.0, 5.0, 7.0, 9.0, 11.0];
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+
+    let report = fit_multi_input(&mut line, x, y, LevenbergMarquardt::new(), |_, _| 1.0);
+
+    assert!(
+        report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+    assert_ulps_eq!(line.a, 2.0);
+    assert_ulps_eq!(line.b, 1.0);
+}
@@ -0,0 +1,153 @@
+#![allow(missing_docs)]
+#![cfg(feature = "nalgebra")]
+
+use nacfahi::{GenericArray, U, models::MultiOutputModel};
+
+/// Two channels decaying with the same rate: y_k = a_k * exp(-x / tau)
+struct SharedDecay {
+    tau: f64,
+    a1: f64,
+    a2: f64,
+}
+
+impl MultiOutputModel for SharedDecay {
+    type Scalar = f64;
+    type Outputs = U<2>;
+    type ParamCount = U<3>;
+
+    fn evaluate(&self, x: &f64) -> impl Into<GenericArray<f64, U<2>>> {
+        let e = (-x / self.tau).exp();
+        [self.a1 * e, self.a2 * e]
+    }
+
+    fn jacobian(&self, x: &f64) -> impl Into<GenericArray<GenericArray<f64, U<3>>, U<2>>> {
+        let e = (-x / self.tau).exp();
+        let de_dtau = e * x / (self.tau * self.tau);
+        [
+            GenericArray::from_array([self.a1 * de_dtau, e, 0.0]),
+            GenericArray::from_array([self.a2 * de_dtau, 0.0, e]),
+        ]
+    }
+
+    fn set_params(&mut self, new_params: GenericArray<f64, U<3>>) {
+        [self.tau, self.a1, self.a2] = new_params.into_array();
+    }
+
+    fn get_params(&self) -> impl Into<GenericArray<f64, U<3>>> {
+        [self.tau, self.a1, self.a2]
+    }
+}
+
+fn unit_weights(_x: f64, _y: GenericArray<f64, U<2>>) -> GenericArray<f64, U<2>> {
+    GenericArray::from_array([1.0, 1.0])
+}
+
+#[test]
+fn shared_decay_matrix() {
+    use approx::assert_abs_diff_eq;
+    use nacfahi::*;
+    use nalgebra::{SMatrix, SVector};
+
+    let x = SVector::<f64, 10>::from_fn(|i, _| i as f64 * 0.5);
+    let y = SMatrix::<f64, 10, 2>::from_fn(|i, j| {
+        let amplitude = if j == 0 { 3.0 } else { -1.5 };
+        amplitude * (-x[i] / 2.0).exp()
+    });
+
+    let mut model = SharedDecay {
+        tau: 1.0,
+        a1: 1.0,
+        a2: 1.0,
+    };
+
+    let report = fit_multi_output(&mut model, x, y, LevenbergMarquardt::new(), unit_weights);
+
+    assert!(
+        report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+    assert_abs_diff_eq!(model.tau, 2.0, epsilon = 1e-10);
+    assert_abs_diff_eq!(model.a1, 3.0, epsilon = 1e-10);
+    assert_abs_diff_eq!(model.a2, -1.5, epsilon = 1e-10);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn shared_decay_dyn_matrix() {
+    use approx::assert_abs_diff_eq;
+    use nacfahi::*;
+    use nalgebra::{Dyn, OMatrix, OVector, U2};
+
+    let x = OVector::<f64, Dyn>::from_fn(25, |i, _| i as f64 * 0.2);
+    let y = OMatrix::<f64, Dyn, U2>::from_fn(25, |i, j| {
+        let amplitude = if j == 0 { 0.5 } else { 4.0 };
+        amplitude * (-x[i] / 1.5).exp()
+    });
+
+    let mut model = SharedDecay {
+        tau: 1.0,
+        a1: 1.0,
+        a2: 1.0,
+    };
+
+    let report = fit_multi_output(&mut model, &x, &y, LevenbergMarquardt::new(), unit_weights);
+
+    assert!(
+        report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+    assert_abs_diff_eq!(model.tau, 1.5, epsilon = 1e-10);
+    assert_abs_diff_eq!(model.a1, 0.5, epsilon = 1e-10);
+    assert_abs_diff_eq!(model.a2, 4.0, epsilon = 1e-10);
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn weighted_channels() {
+    use nacfahi::*;
+    use nalgebra::{SMatrix, SVector};
+
+    // channels disagree on the decay rate, so the optimum depends on weights
+    let x = SVector::<f64, 10>::from_fn(|i, _| i as f64 * 0.5);
+    let y = SMatrix::<f64, 10, 2>::from_fn(|i, j| {
+        if j == 0 {
+            3.0 * (-x[i] / 2.0).exp()
+        } else {
+            -1.5 * (-x[i] / 1.0).exp()
+        }
+    });
+    let weights = |_, _: GenericArray<f64, U<2>>| GenericArray::from_array([1.0, 3.0]);
+
+    let mut model = SharedDecay {
+        tau: 1.0,
+        a1: 1.0,
+        a2: 1.0,
+    };
+    let report = fit_multi_output(&mut model, x, y, LevenbergMarquardt::new(), weights);
+    assert!(
+        report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+
+    // weighted sum of squares should be stationary at the result
+    let objective = |model: &SharedDecay| {
+        (0..10)
+            .map(|i| {
+                let [f_1, f_2] = model.evaluate(&x[i]).into().into_array();
+                (f_1 - y[(i, 0)]).powi(2) + (3.0 * (f_2 - y[(i, 1)])).powi(2)
+            })
+            .sum::<f64>()
+    };
+    let h = 1e-6;
+    let step = |tau, a1, a2| SharedDecay { tau, a1, a2 };
+    let SharedDecay { tau, a1, a2 } = model;
+    let gradient = [
+        objective(&step(tau + h, a1, a2)) - objective(&step(tau - h, a1, a2)),
+        objective(&step(tau, a1 + h, a2)) - objective(&step(tau, a1 - h, a2)),
+        objective(&step(tau, a1, a2 + h)) - objective(&step(tau, a1, a2 - h)),
+    ]
+    .map(|d| d / (2.0 * h));
+    for d in gradient {
+        assert!(d.abs() < 1e-6, "{gradient:?}");
+    }
+}
@@ -0,0 +1,116 @@
+#![allow(missing_docs)]
+
+use approx::assert_relative_eq;
+use nacfahi::{
+    GenericArray, U, fit,
+    models::{
+        FitModel, FitModelXDeriv,
+        utility::{FnModel, NumericJacobian, NumericModel},
+    },
+};
+use static_assertions::assert_impl_all;
+
+/// Damped oscillation $a \exp(-g x) \cos(w x)$
+#[derive(Debug, Clone, Copy)]
+pub struct Damped {
+    pub a: f64,
+    pub g: f64,
+    pub w: f64,
+}
+
+impl NumericModel for Damped {
+    type Scalar = f64;
+    type ParamCount = U<3>;
+
+    fn evaluate(&self, params: &GenericArray<f64, U<3>>, x: &f64) -> f64 {
+        let [a, g, w] = (*params).into_array();
+        a * (-g * x).exp() * (w * x).cos()
+    }
+
+    fn set_params(&mut self, new_params: GenericArray<f64, U<3>>) {
+        [self.a, self.g, self.w] = new_params.into_array();
+    }
+
+    fn get_params(&self) -> impl Into<GenericArray<f64, U<3>>> {
+        [self.a, self.g, self.w]
+    }
+}
+
+assert_impl_all!(NumericJacobian<Damped>: FitModel<Scalar = f64>, FitModelXDeriv);
+
+const TRUTH: Damped = Damped {
+    a: 2.0,
+    g: 0.3,
+    w: 1.7,
+};
+
+fn data() -> ([f64; 40], [f64; 40]) {
+    let model = NumericJacobian::new(TRUTH);
+    let x = core::array::from_fn(|i| i as f64 * 0.2);
+    let y = x.map(|x| model.evaluate(&x));
+    (x, y)
+}
+
+fn initial() -> NumericJacobian<Damped> {
+    NumericJacobian::new(Damped {
+        a: 1.5,
+        g: 0.2,
+        w: 1.6,
+    })
+}
+
+#[test]
+fn const_problem() {
+    let (x, y) = data();
+    let mut model = initial();
+    let report = fit!(&mut model, x, y);
+
+    assert!(
+        report.termination.was_successful(),
+        "Fit should be successful {report:?}"
+    );
+    assert_relative_eq!(model.inner.a, TRUTH.a, max_relative = 1e-8);
+    assert_relative_eq!(model.inner.g, TRUTH.g, max_relative = 1e-8);
+    assert_relative_eq!(model.inner.w, TRUTH.w, max_relative = 1e-8);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn dyn_problem() {
+    use nacfahi::models::utility::NumericStep;
+
+    let (x, y) = data();
+    let mut model = initial()
+        .with_step(NumericStep::Absolute(1e-4))
+        .with_richardson();
+    let report = fit!(&mut model, x.as_slice(), y.as_slice());
+
+    assert!(
+        report.termination.was_successful(),
+        "Fit should be successful {report:?}"
+    );
+    assert_relative_eq!(model.inner.a, TRUTH.a, max_relative = 1e-8);
+    assert_relative_eq!(model.inner.g, TRUTH.g, max_relative = 1e-8);
+    assert_relative_eq!(model.inner.w, TRUTH.w, max_relative = 1e-8);
+}
+
+#[test]
+fn closure_model() {
+    let x = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = x.map(|x: f64| 0.5 * x * x - x + 2.0);
+
+    let parabola = FnModel::new([0.0; 3], |[a, b, c]: [f64; 3], x: f64| {
+        a * x * x + b * x + c
+    });
+    let mut model = NumericJacobian::new(parabola);
+    let report = fit!(&mut model, x, y);
+
+    assert!(
+        report.termination.was_successful(),
+        "Fit should be successful {report:?}"
+    );
+    let [a, b, c] = model.inner.params;
+    assert_relative_eq!(a, 0.5, max_relative = 1e-8);
+    assert_relative_eq!(b, -1.0, max_relative = 1e-8);
+    assert_relative_eq!(c, 2.0, max_relative = 1e-8);
+}
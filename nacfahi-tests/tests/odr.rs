@@ -0,0 +1,84 @@
+#![allow(missing_docs)]
+
+use nacfahi::models::{FitModel, FitModelXDeriv, basic::Exponent};
+
+const X: [f64; 10] = [0.0, 0.3, 0.6, 0.9, 1.2, 1.5, 1.8, 2.1, 2.4, 2.7];
+const Y: [f64; 10] = [1.05, 1.28, 1.85, 2.31, 3.42, 4.38, 6.13, 8.04, 11.3, 14.6];
+const X_SIGMA: [f64; 10] = [0.05; 10];
+const Y_SIGMA: [f64; 10] = [0.1, 0.1, 0.1, 0.1, 0.2, 0.2, 0.2, 0.3, 0.3, 0.3];
+
+fn initial() -> Exponent<f64> {
+    Exponent { a: 1.0, b: 1.0 }
+}
+
+#[test]
+fn exact_x_is_plain_sigma_fit() {
+    use approx::assert_relative_eq;
+    use nacfahi::*;
+
+    let mut plain = initial();
+    let report = fit!(&mut plain, X, Y, sigma = Y_SIGMA);
+    assert!(report.termination.was_successful());
+
+    let mut odr_model = initial();
+    let odr = fit!(&mut odr_model, X, Y, sigma = Y_SIGMA, sigma_x = [0.0; 10]);
+    assert!(odr.result.termination.was_successful());
+
+    assert_relative_eq!(odr_model.a, plain.a, max_relative = 1e-10);
+    assert_relative_eq!(odr_model.b, plain.b, max_relative = 1e-10);
+    for (&effective, sigma) in odr.sigma.iter().zip(Y_SIGMA) {
+        assert_relative_eq!(effective, sigma);
+    }
+}
+
+#[test]
+fn effective_sigma_is_consistent() {
+    use approx::assert_relative_eq;
+    use nacfahi::*;
+
+    let mut model = initial();
+    let odr = fit!(&mut model, X, Y, sigma = Y_SIGMA, sigma_x = X_SIGMA);
+    assert!(odr.result.termination.was_successful());
+    assert!(odr.iterations > 1, "Effective errors should be refined");
+
+    // effective errors correspond to the fitted model
+    for i in 0..X.len() {
+        let propagated = model.deriv_x(&X[i]) * X_SIGMA[i];
+        let expected = (Y_SIGMA[i].powi(2) + propagated.powi(2)).sqrt();
+        assert_relative_eq!(odr.sigma[i], expected, max_relative = 1e-6);
+    }
+
+    // and refitting with them does not change anything
+    let mut refit = model;
+    let sigma: [f64; 10] = core::array::from_fn(|i| odr.sigma[i]);
+    let report = fit!(&mut refit, X, Y, sigma = sigma);
+    assert!(report.termination.was_successful());
+    assert_relative_eq!(refit.a, model.a, max_relative = 1e-6);
+    assert_relative_eq!(refit.b, model.b, max_relative = 1e-6);
+    assert_relative_eq!(
+        refit.evaluate(&1.0),
+        model.evaluate(&1.0),
+        max_relative = 1e-6
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn errors_account_for_x() {
+    use nacfahi::*;
+
+    let mut plain = initial();
+    let plain_stat = fit_stat!(&mut plain, X, Y, sigma = Y_SIGMA);
+
+    let mut model = initial();
+    let odr = fit_stat!(&mut model, X, Y, sigma = Y_SIGMA, sigma_x = X_SIGMA);
+    let stat = odr.result;
+
+    assert!(stat.report.termination.was_successful());
+    assert!(stat.errors.a > plain_stat.errors.a);
+    assert!(stat.errors.b > plain_stat.errors.b);
+    assert!(
+        stat.chi2 < plain_stat.chi2,
+        "Larger effective errors should reduce chi2"
+    );
+}
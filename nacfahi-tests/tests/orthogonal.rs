@@ -0,0 +1,150 @@
+#![allow(missing_docs)]
+
+use approx::assert_relative_eq;
+use nacfahi::{
+    models::{
+        FitModel, FitModelXDeriv,
+        basic::{Chebyshev, Legendre, Polynomial},
+    },
+    *,
+};
+
+#[test]
+fn basis_values() {
+    // T_3(cos theta) = cos(3 theta), on the default domain
+    let cubic = Chebyshev {
+        x_min: -1.0,
+        x_max: 1.0,
+        params: [0.0, 0.0, 0.0, 1.0],
+    };
+    // P_2(t) = (3 t^2 - 1) / 2, with t = x - 2 on [1, 3]
+    let quadratic = Legendre {
+        x_min: 1.0,
+        x_max: 3.0,
+        params: [0.0, 0.0, 1.0],
+    };
+    for theta in [0.0f64, 0.3, 1.0, 2.5] {
+        assert_relative_eq!(
+            cubic.evaluate(&theta.cos()),
+            (3.0 * theta).cos(),
+            epsilon = 1e-12
+        );
+        // T_3'(cos theta) = 3 sin(3 theta) / sin(theta)
+        if theta > 0.0 {
+            assert_relative_eq!(
+                cubic.deriv_x(&theta.cos()),
+                3.0 * (3.0 * theta).sin() / theta.sin(),
+                epsilon = 1e-10
+            );
+        }
+        let x = 2.0 + theta.cos();
+        let t = x - 2.0;
+        assert_relative_eq!(
+            quadratic.evaluate(&x),
+            (3.0 * t * t - 1.0) / 2.0,
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(quadratic.deriv_x(&x), 3.0 * t, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn polynomial_round_trip() {
+    let polynomial = Polynomial {
+        params: [1.0, -2.0, 0.5, 0.25, -0.125],
+    };
+    let chebyshev = Chebyshev::from_polynomial(&polynomial, -2.0, 3.0);
+    let legendre = Legendre::from_polynomial(&polynomial, -2.0, 3.0);
+    for x in [-2.0, -0.5, 0.0, 1.0, 2.5, 4.0] {
+        assert_relative_eq!(
+            chebyshev.evaluate(&x),
+            polynomial.evaluate(&x),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            legendre.evaluate(&x),
+            polynomial.evaluate(&x),
+            epsilon = 1e-12
+        );
+    }
+    for (back, expected) in [chebyshev.to_polynomial(), legendre.to_polynomial()]
+        .iter()
+        .flat_map(|back| back.params.iter().zip(&polynomial.params))
+    {
+        assert_relative_eq!(back, expected, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn high_order_far_from_origin() {
+    // powers of x are hopeless here, but the mapped basis does not care
+    let truth = Chebyshev {
+        x_min: 1000.0,
+        x_max: 1010.0,
+        params: [
+            1.0, -0.5, 0.25, 2.0, -1.0, 0.3, 0.7, -0.2, 0.1, -0.05, 0.02, 0.01,
+        ],
+    };
+    let x: [f64; 101] = core::array::from_fn(|i| 1000.0 + 0.1 * i as f64);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut chebyshev = Chebyshev {
+        params: [0.0; 12],
+        ..truth
+    };
+    let stat = fit_linear(&mut chebyshev, x, y, default_weights);
+    assert!(stat.report.termination.was_successful());
+    assert!(stat.conditioning.is_regular());
+    for (param, expected) in chebyshev.params.iter().zip(&truth.params) {
+        assert_relative_eq!(param, expected, epsilon = 1e-10);
+    }
+
+    let mut legendre = Legendre::<12, f64> {
+        x_min: 1000.0,
+        x_max: 1010.0,
+        params: [0.0; 12],
+    };
+    let stat = fit_stat!(&mut legendre, x, y);
+    assert!(stat.report.termination.was_successful());
+    for x in x {
+        assert_relative_eq!(legendre.evaluate(&x), truth.evaluate(&x), epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn guess_series() {
+    let chebyshev_truth = Chebyshev {
+        x_min: 2.0,
+        x_max: 6.0,
+        params: [1.0, -2.0, 0.5, 0.25],
+    };
+    let legendre_truth = Legendre {
+        x_min: 2.0,
+        x_max: 6.0,
+        params: [-0.5, 1.5, 2.0, -0.75],
+    };
+    let x: [f64; 21] = core::array::from_fn(|i| 2.0 + 0.2 * i as f64);
+
+    // domain is kept, only coefficients are guessed
+    let mut chebyshev = Chebyshev {
+        params: [0.0; 4],
+        ..chebyshev_truth
+    };
+    guess_params(&mut chebyshev, &x, &x.map(|x| chebyshev_truth.evaluate(&x)));
+    let mut legendre = Legendre {
+        params: [0.0; 4],
+        ..legendre_truth
+    };
+    guess_params(&mut legendre, &x, &x.map(|x| legendre_truth.evaluate(&x)));
+
+    assert_eq!((chebyshev.x_min, chebyshev.x_max), (2.0, 6.0));
+    assert_eq!((legendre.x_min, legendre.x_max), (2.0, 6.0));
+    for (param, expected) in chebyshev
+        .params
+        .iter()
+        .zip(&chebyshev_truth.params)
+        .chain(legendre.params.iter().zip(&legendre_truth.params))
+    {
+        assert_relative_eq!(param, expected, epsilon = 1e-10);
+    }
+}
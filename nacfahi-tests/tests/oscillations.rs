@@ -0,0 +1,192 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use approx::assert_relative_eq;
+use common::grid;
+use nacfahi::{
+    models::{
+        FitModel, FitModelXDeriv,
+        basic::{DampedSine, FourierSeries, Sine},
+    },
+    *,
+};
+
+#[test]
+fn special_cases() {
+    let sine = Sine {
+        a: 2.0,
+        omega: 1.5,
+        phi: 0.3,
+    };
+    let undamped = DampedSine {
+        a: 2.0,
+        omega: 1.5,
+        phi: 0.3,
+        gamma: 0.0,
+    };
+    // a sin(wx + phi) = a sin(phi) cos(wx) + a cos(phi) sin(wx)
+    let series = FourierSeries {
+        omega: 1.5,
+        a0: 0.0,
+        a: [2.0 * 0.3f64.sin()],
+        b: [2.0 * 0.3f64.cos()],
+    };
+    for x in [-3.0, 0.0, 0.5, 1.0, 2.0, 4.0] {
+        assert_relative_eq!(undamped.evaluate(&x), sine.evaluate(&x));
+        assert_relative_eq!(undamped.deriv_x(&x), sine.deriv_x(&x));
+        assert_relative_eq!(series.evaluate(&x), sine.evaluate(&x), epsilon = 1e-12);
+        assert_relative_eq!(series.deriv_x(&x), sine.deriv_x(&x), epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn fit_damped_sine() {
+    let truth = DampedSine {
+        a: 3.0,
+        omega: 2.0,
+        phi: 0.5,
+        gamma: 0.3,
+    };
+    let x = grid::<101>(0.0, 0.1);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut damped = DampedSine {
+        a: 1.0,
+        omega: 1.9,
+        phi: 0.0,
+        gamma: 0.0,
+    };
+    let stat = fit_stat!(&mut damped, x, y);
+
+    assert!(stat.report.termination.was_successful());
+    assert_relative_eq!(damped.a, truth.a, max_relative = 1e-8);
+    assert_relative_eq!(damped.omega, truth.omega, max_relative = 1e-8);
+    assert_relative_eq!(damped.phi, truth.phi, max_relative = 1e-8);
+    assert_relative_eq!(damped.gamma, truth.gamma, max_relative = 1e-8);
+}
+
+#[test]
+fn fit_square_wave() {
+    // square wave with period 2 pi has only odd sine harmonics, 4 / (k pi)
+    let x = grid::<120>(0.025, 2.0 * core::f64::consts::PI / 120.0);
+    let y = x.map(|x: f64| x.sin().signum());
+
+    let mut series = FourierSeries::<3, f64> {
+        omega: 1.0,
+        a0: 0.0,
+        a: [0.0; 3],
+        b: [0.0; 3],
+    };
+    let stat = fit_linear(&mut series, x, y, default_weights);
+
+    assert!(stat.report.termination.was_successful());
+    let [a_1, a_2, a_3] = series.a;
+    let [b_1, b_2, b_3] = series.b;
+    let pi = core::f64::consts::PI;
+    for coefficient in [series.a0, a_1, a_2, b_2, a_3] {
+        assert!(coefficient.abs() < 0.05, "{coefficient}");
+    }
+    assert_relative_eq!(b_1, 4.0 / pi, max_relative = 0.02);
+    assert_relative_eq!(b_3, 4.0 / (3.0 * pi), max_relative = 0.05);
+}
+
+#[test]
+fn series_parameter_order() {
+    let series = FourierSeries::<2, f64> {
+        omega: 1.0,
+        a0: 1.0,
+        a: [2.0, 4.0],
+        b: [3.0, 5.0],
+    };
+    let params: GenericArray<f64, U<5>> = series.get_params().into();
+    assert_eq!(params.into_array(), [1.0, 2.0, 3.0, 4.0, 5.0]);
+
+    let mut copy = FourierSeries::<2, f64> {
+        omega: 1.0,
+        a0: 0.0,
+        a: [0.0; 2],
+        b: [0.0; 2],
+    };
+    copy.set_params(params);
+    assert_eq!(copy, series);
+}
+
+#[test]
+fn guess_sine() {
+    let truth = Sine {
+        a: -1.5,
+        omega: 2.7,
+        phi: 1.0,
+    };
+    let x = grid::<120>(-1.0, 0.1);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    // initial frequency is far enough to miss without a guess
+    let mut sine = Sine {
+        a: 1.0,
+        omega: 0.5,
+        phi: 0.0,
+    };
+    let report = fit!(&mut sine, x, y, guess = true);
+
+    assert!(report.termination.was_successful());
+    for x in x {
+        assert_relative_eq!(sine.evaluate(&x), truth.evaluate(&x), epsilon = 1e-8);
+    }
+    assert_relative_eq!(sine.omega, truth.omega, max_relative = 1e-8);
+}
+
+#[test]
+fn guess_damped_sine() {
+    let truth = DampedSine {
+        a: 3.0,
+        omega: 4.0,
+        phi: -0.7,
+        gamma: 0.25,
+    };
+    let x = grid::<121>(0.0, 0.06);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut damped = DampedSine {
+        a: 1.0,
+        omega: 7.0,
+        phi: 0.0,
+        gamma: 0.0,
+    };
+    let report = fit!(&mut damped, x, y, guess = true);
+
+    assert!(report.termination.was_successful());
+    assert_relative_eq!(damped.a, truth.a, max_relative = 1e-8);
+    assert_relative_eq!(damped.omega, truth.omega, max_relative = 1e-8);
+    assert_relative_eq!(damped.phi, truth.phi, max_relative = 1e-8);
+    assert_relative_eq!(damped.gamma, truth.gamma, max_relative = 1e-8);
+}
+
+#[test]
+fn guess_series() {
+    let truth = FourierSeries::<2, f64> {
+        omega: 0.8,
+        a0: 0.5,
+        a: [1.0, -0.3],
+        b: [2.0, 0.7],
+    };
+    let x = grid::<40>(-2.0, 0.25);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut series = FourierSeries::<2, f64> {
+        omega: 0.8,
+        a0: 0.0,
+        a: [0.0; 2],
+        b: [0.0; 2],
+    };
+    guess_params(&mut series, &x, &y);
+
+    assert_relative_eq!(series.a0, truth.a0, epsilon = 1e-10);
+    for (guess, truth) in series.a.into_iter().zip(truth.a) {
+        assert_relative_eq!(guess, truth, epsilon = 1e-10);
+    }
+    for (guess, truth) in series.b.into_iter().zip(truth.b) {
+        assert_relative_eq!(guess, truth, epsilon = 1e-10);
+    }
+}
@@ -0,0 +1,190 @@
+#![allow(missing_docs)]
+
+use approx::assert_relative_eq;
+use nacfahi::{
+    models::{
+        FitModel, FitModelXDeriv,
+        basic::{BrokenPowerLaw, PowerLaw, SmoothlyBrokenPowerLaw},
+    },
+    *,
+};
+
+fn log_grid<const N: usize>(from: f64, ratio: f64) -> [f64; N] {
+    core::array::from_fn(|i| from * ratio.powi(i32::try_from(i).unwrap()))
+}
+
+#[test]
+fn non_positive_x() {
+    let power = PowerLaw { a: 2.0, k: 1.5 };
+    let broken = BrokenPowerLaw {
+        a: 2.0,
+        x_b: 1.0,
+        k_1: 1.5,
+        k_2: -1.0,
+    };
+    let smooth = SmoothlyBrokenPowerLaw {
+        a: 2.0,
+        x_b: 1.0,
+        k_1: 1.5,
+        k_2: -1.0,
+        delta: 0.1,
+    };
+    for x in [0.0f64, -1.0] {
+        assert!(power.evaluate(&x).is_nan());
+        assert!(power.deriv_x(&x).is_nan());
+        assert!(power.jacobian(&x).into().iter().all(|d| d.is_nan()));
+        assert!(broken.evaluate(&x).is_nan());
+        assert!(smooth.evaluate(&x).is_nan());
+    }
+
+    let x = [-1.0, 1.0, 2.0, 3.0];
+    let y = [1.0, 2.0, 4.0, 6.0];
+    let mut power = PowerLaw { a: 1.0, k: 1.0 };
+    let report = fit!(&mut power, x, y);
+    assert!(matches!(
+        report.termination,
+        TerminationReason::Numerical(_)
+    ));
+}
+
+#[test]
+fn limits() {
+    let broken = BrokenPowerLaw {
+        a: 3.0,
+        x_b: 2.0,
+        k_1: 1.5,
+        k_2: -0.5,
+    };
+    let smooth = SmoothlyBrokenPowerLaw {
+        a: 3.0,
+        x_b: 2.0,
+        k_1: 1.5,
+        k_2: -0.5,
+        delta: 0.1,
+    };
+    assert_relative_eq!(broken.evaluate(&2.0), 3.0);
+    assert_relative_eq!(smooth.evaluate(&2.0), 3.0);
+    // far from the knee on either side, smoothing leaves a constant factor of 2^((k_1 - k_2) delta)
+    let factor = 2.0f64.powf(2.0 * 0.1);
+    for x in [0.01, 400.0] {
+        assert_relative_eq!(
+            smooth.evaluate(&x),
+            broken.evaluate(&x) * factor,
+            max_relative = 1e-9
+        );
+    }
+    // and does not overflow
+    assert!(smooth.evaluate(&1e300).is_finite());
+    assert!(smooth.jacobian(&1e300).into().iter().all(|d| d.is_finite()));
+
+    let below = PowerLaw {
+        a: 3.0 / 2.0f64.powf(1.5),
+        k: 1.5,
+    };
+    for x in [0.1, 0.5, 1.9] {
+        assert_relative_eq!(
+            broken.evaluate(&x),
+            below.evaluate(&x),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(broken.deriv_x(&x), below.deriv_x(&x), max_relative = 1e-12);
+    }
+}
+
+#[test]
+fn fit_power_law() {
+    let truth = PowerLaw { a: -2.5, k: -1.3 };
+    let x = log_grid::<30>(0.1, 1.25);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut power = PowerLaw { a: 1.0, k: 0.0 };
+    let report = fit!(&mut power, x, y, guess = true);
+
+    assert!(report.termination.was_successful());
+    assert_relative_eq!(power.a, truth.a, max_relative = 1e-10);
+    assert_relative_eq!(power.k, truth.k, max_relative = 1e-10);
+}
+
+#[test]
+fn fit_smoothly_broken() {
+    let truth = SmoothlyBrokenPowerLaw {
+        a: 5.0,
+        x_b: 3.0,
+        k_1: 0.5,
+        k_2: -2.0,
+        delta: 0.4,
+    };
+    let x = log_grid::<50>(0.05, 1.15);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut smooth = SmoothlyBrokenPowerLaw {
+        a: 4.0,
+        x_b: 2.0,
+        k_1: 0.0,
+        k_2: -1.0,
+        delta: 1.0,
+    };
+    let stat = fit_stat!(&mut smooth, x, y);
+
+    assert!(stat.report.termination.was_successful());
+    assert_relative_eq!(smooth.a, truth.a, max_relative = 1e-6);
+    assert_relative_eq!(smooth.x_b, truth.x_b, max_relative = 1e-6);
+    assert_relative_eq!(smooth.k_1, truth.k_1, max_relative = 1e-6);
+    assert_relative_eq!(smooth.k_2, truth.k_2, max_relative = 1e-6);
+    assert_relative_eq!(smooth.delta, truth.delta, max_relative = 1e-6);
+}
+
+#[test]
+fn guess_broken() {
+    // odd number of points on a log grid, so the break is exactly at the median
+    let x = log_grid::<41>(0.1, 1.2);
+    let truth = BrokenPowerLaw {
+        a: -2.0,
+        x_b: x[20],
+        k_1: 1.5,
+        k_2: -0.5,
+    };
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut broken = BrokenPowerLaw {
+        a: 1.0,
+        x_b: 1.0,
+        k_1: 0.0,
+        k_2: 0.0,
+    };
+    guess_params(&mut broken, &x, &y);
+
+    assert_relative_eq!(broken.a, truth.a, max_relative = 1e-10);
+    assert_relative_eq!(broken.x_b, truth.x_b, max_relative = 1e-10);
+    assert_relative_eq!(broken.k_1, truth.k_1, max_relative = 1e-10);
+    assert_relative_eq!(broken.k_2, truth.k_2, max_relative = 1e-10);
+}
+
+#[test]
+fn guess_smoothly_broken() {
+    let truth = SmoothlyBrokenPowerLaw {
+        a: 5.0,
+        x_b: 3.0,
+        k_1: 0.5,
+        k_2: -2.0,
+        delta: 0.4,
+    };
+    let x = log_grid::<50>(0.05, 1.15);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut smooth = SmoothlyBrokenPowerLaw {
+        a: 1.0,
+        x_b: 100.0,
+        k_1: 3.0,
+        k_2: 3.0,
+        delta: 0.5,
+    };
+    let stat = fit_stat!(&mut smooth, x, y, guess = true);
+
+    assert!(stat.report.termination.was_successful());
+    assert_relative_eq!(smooth.a, truth.a, max_relative = 1e-6);
+    assert_relative_eq!(smooth.x_b, truth.x_b, max_relative = 1e-6);
+    assert_relative_eq!(smooth.k_1, truth.k_1, max_relative = 1e-6);
+    assert_relative_eq!(smooth.k_2, truth.k_2, max_relative = 1e-6);
+    assert_relative_eq!(smooth.delta, truth.delta, max_relative = 1e-6);
+}
@@ -0,0 +1,192 @@
+#![allow(missing_docs)]
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use approx::assert_relative_eq;
+use common::{X, Y};
+use nacfahi::{
+    models::{
+        FitModel,
+        basic::{Exponent, Linear},
+    },
+    *,
+};
+
+#[test]
+fn linear_matches_covariance() {
+    // chi2 of a linear model is exactly parabolic, so profile should match covariance errors
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y);
+
+    let mut profiled = Linear { a: 0.0, b: 0.0 };
+    let profile = fit_profile(
+        &mut profiled,
+        X,
+        Y,
+        1.0,
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+    assert!(profile.report.termination.was_successful());
+    assert_relative_eq!(profile.chi2, stat.chi2, max_relative = 1e-8);
+    assert_relative_eq!(profiled.a, line.a, max_relative = 1e-8);
+    assert_relative_eq!(profiled.b, line.b, max_relative = 1e-8);
+
+    let [a_lower, b_lower] = profile.lower.into_array();
+    let [a_upper, b_upper] = profile.upper.into_array();
+    assert_relative_eq!(a_lower, stat.errors.a, max_relative = 1e-4);
+    assert_relative_eq!(a_upper, stat.errors.a, max_relative = 1e-4);
+    assert_relative_eq!(b_lower, stat.errors.b, max_relative = 1e-4);
+    assert_relative_eq!(b_upper, stat.errors.b, max_relative = 1e-4);
+
+    // 4 times the delta is twice the interval
+    let profile = fit_profile(
+        &mut profiled,
+        X,
+        Y,
+        4.0,
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+    assert_relative_eq!(profile.upper[0], 2.0 * stat.errors.a, max_relative = 1e-4);
+}
+
+#[test]
+fn known_sigma() {
+    let sigma = [0.2; 8];
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y, sigma = sigma);
+
+    let mut profiled = Linear { a: 0.0, b: 0.0 };
+    let profile = fit_profile_sigma(
+        &mut profiled,
+        X,
+        Y,
+        sigma,
+        1.0,
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+    assert!(profile.report.termination.was_successful());
+    assert_relative_eq!(profile.lower[0], stat.errors.a, max_relative = 1e-4);
+    assert_relative_eq!(profile.upper[1], stat.errors.b, max_relative = 1e-4);
+}
+
+#[test]
+fn nonlinear_asymmetric() {
+    let x = [0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0];
+    let y = [2.1, 2.4, 3.5, 4.2, 5.6, 7.4, 8.9];
+
+    let mut exponent = Exponent { a: 1.0, b: 1.0 };
+    let profile = fit_profile(
+        &mut exponent,
+        x,
+        y,
+        1.0,
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+    assert!(profile.report.termination.was_successful());
+
+    for i in 0..2 {
+        assert!(profile.lower[i].is_finite() && profile.lower[i] > 0.0);
+        assert!(profile.upper[i].is_finite() && profile.upper[i] > 0.0);
+    }
+    // `a` is a multiplier, and `b` enters exponentially
+    assert!((profile.lower[1] - profile.upper[1]).abs() > 1e-3 * profile.upper[1]);
+}
+
+#[test]
+fn no_degrees_of_freedom() {
+    let x = [0.0f64, 1.0];
+    let y = [1.0, 3.0];
+
+    // line through two points leaves nothing to estimate data variance from
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let profile = fit_profile(
+        &mut line,
+        x,
+        y,
+        1.0,
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+    assert!(profile.report.termination.was_successful());
+    assert_relative_eq!(line.a, 2.0, max_relative = 1e-10);
+    assert!(
+        profile
+            .lower
+            .iter()
+            .chain(&profile.upper)
+            .all(|v| v.is_nan())
+    );
+
+    // known errors don't need that
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let profile = fit_profile_sigma(
+        &mut line,
+        x,
+        y,
+        [0.1; 2],
+        1.0,
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+    assert!(
+        profile
+            .lower
+            .iter()
+            .chain(&profile.upper)
+            .all(|v| v.is_finite())
+    );
+}
+
+/// Line that can't be evaluated with slope above one.
+#[derive(Debug)]
+struct CappedLine(Linear<f64>);
+
+impl FitModel for CappedLine {
+    type Scalar = f64;
+    type ParamCount = U<2>;
+
+    fn evaluate(&self, x: &f64) -> f64 {
+        if self.0.a > 1.0 {
+            f64::NAN
+        } else {
+            self.0.evaluate(x)
+        }
+    }
+
+    fn jacobian(&self, x: &f64) -> impl Into<GenericArray<f64, U<2>>> {
+        self.0.jacobian(x)
+    }
+
+    fn set_params(&mut self, new_params: GenericArray<f64, U<2>>) {
+        self.0.set_params(new_params);
+    }
+
+    fn get_params(&self) -> impl Into<GenericArray<f64, U<2>>> {
+        self.0.get_params()
+    }
+}
+
+#[test]
+fn failed_refit() {
+    let x = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = [0.1, 0.4, 1.1, 1.4, 2.1, 2.4];
+
+    // chi2 never rises that much before refits start failing at slope above one
+    let mut line = CappedLine(Linear { a: 0.0, b: 0.0 });
+    let profile = fit_profile(
+        &mut line,
+        x,
+        y,
+        1e4,
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+    assert!(profile.report.termination.was_successful());
+    assert!(profile.upper[0].is_nan());
+    assert!(profile.lower[0].is_finite());
+}
@@ -0,0 +1,258 @@
+#![allow(missing_docs)]
+#![cfg(feature = "alloc")]
+
+use approx::assert_relative_eq;
+use nacfahi::{
+    models::{
+        basic::Linear,
+        utility::{Bounded, ParamBound, Tie, Tied},
+    },
+    resample::{Bootstrap, ResampleRng, SplitMix64},
+    *,
+};
+use rand::{RngCore, SeedableRng, rngs::StdRng};
+
+fn data() -> ([f64; 30], [f64; 30]) {
+    let x = core::array::from_fn(|i| i as f64 / 3.0);
+    let mut rng = SplitMix64::from_seed(7);
+    let y = x.map(|x| {
+        // uniform noise in [-0.5, 0.5)
+        let noise = (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64 - 0.5;
+        1.5 * x - 2.0 + noise
+    });
+    (x, y)
+}
+
+#[derive(Debug)]
+struct Rand(StdRng);
+
+impl ResampleRng for Rand {
+    fn from_seed(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}
+
+#[test]
+fn jackknife_matches_covariance() {
+    let (x, y) = data();
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, x, y);
+
+    let mut resampled_line = Linear { a: 0.0, b: 0.0 };
+    let jackknife = fit_jackknife(
+        &mut resampled_line,
+        x,
+        y,
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+    assert!(jackknife.report.termination.was_successful());
+    assert_eq!(jackknife.samples.len(), x.len());
+    assert_eq!(jackknife.failed, 0);
+    // model is left at the original fit
+    assert_relative_eq!(resampled_line.a, line.a, max_relative = 1e-8);
+    assert_relative_eq!(resampled_line.b, line.b, max_relative = 1e-8);
+
+    assert_relative_eq!(jackknife.mean.a, line.a, max_relative = 1e-2);
+    assert_relative_eq!(jackknife.std_dev.a, stat.errors.a, max_relative = 0.3);
+    assert_relative_eq!(jackknife.std_dev.b, stat.errors.b, max_relative = 0.3);
+
+    let (lower, upper) = jackknife.intervals(0.95);
+    assert_relative_eq!(
+        (upper.a + lower.a) / 2.0,
+        jackknife.mean.a,
+        max_relative = 1e-10
+    );
+    assert!(lower.b < line.b && line.b < upper.b);
+}
+
+#[test]
+fn jackknife_exact_data() {
+    let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+    let y = x.map(|x| 2.0 * x + 1.0);
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let jackknife = fit_jackknife(&mut line, x, y, LevenbergMarquardt::new(), default_weights);
+    assert_relative_eq!(jackknife.mean.a, 2.0, max_relative = 1e-8);
+    assert!(jackknife.std_dev.a < 1e-8);
+    assert!(jackknife.std_dev.b < 1e-8);
+}
+
+#[test]
+fn bootstrap_residuals() {
+    let (x, y) = data();
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, x, y);
+
+    let bootstrap = |seed| {
+        let mut line = Linear { a: 0.0, b: 0.0 };
+        fit_bootstrap::<_, _, _, SplitMix64>(
+            &mut line,
+            x,
+            y,
+            Bootstrap::Residuals,
+            500,
+            seed,
+            LevenbergMarquardt::new(),
+            default_weights,
+        )
+    };
+    let first = bootstrap(1);
+    assert_eq!(first.samples.len(), 500);
+    assert_relative_eq!(first.mean.a, line.a, max_relative = 1e-2);
+    assert_relative_eq!(first.std_dev.a, stat.errors.a, max_relative = 0.2);
+    assert_relative_eq!(first.std_dev.b, stat.errors.b, max_relative = 0.2);
+
+    // same seed yields same result
+    let second = bootstrap(1);
+    assert_eq!(first.samples, second.samples);
+    let third = bootstrap(2);
+    assert_ne!(first.samples, third.samples);
+
+    let (lower, upper) = first.intervals(0.9);
+    assert!(lower.a < line.a && line.a < upper.a);
+    assert!(lower.b < line.b && line.b < upper.b);
+    // percentile interval should be about as wide as a normal one
+    assert_relative_eq!(
+        (upper.a - lower.a) / 2.0,
+        1.645 * first.std_dev.a,
+        max_relative = 0.2
+    );
+}
+
+#[test]
+fn bootstrap_pairs_custom_rng() {
+    let (x, y) = data();
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, x, y);
+
+    let mut resampled_line = Linear { a: 0.0, b: 0.0 };
+    let bootstrap = fit_bootstrap::<_, _, _, Rand>(
+        &mut resampled_line,
+        &x[..],
+        &y[..],
+        Bootstrap::Pairs,
+        500,
+        1,
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+    assert_eq!(bootstrap.samples.len() + bootstrap.failed, 500);
+    assert_relative_eq!(bootstrap.std_dev.a, stat.errors.a, max_relative = 0.3);
+
+    let (lower, upper) = bootstrap.intervals(0.95);
+    assert!(lower.a < line.a && line.a < upper.a);
+}
+
+#[test]
+fn bootstrap_bounded() {
+    let (x, y) = data();
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, x, y);
+
+    // optimizer sees transformed parameters, which are nowhere near the actual ones
+    let mut bounded = Bounded::new(
+        Linear { a: 1.0, b: 0.0 },
+        [ParamBound::Both(0.0, 10.0), ParamBound::Lower(-10.0)],
+    );
+    let bootstrap = fit_bootstrap::<_, _, _, SplitMix64>(
+        &mut bounded,
+        x,
+        y,
+        Bootstrap::Residuals,
+        500,
+        1,
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+    assert_eq!(bootstrap.samples.len(), 500);
+    assert_relative_eq!(bootstrap.mean.inner().a, line.a, max_relative = 1e-2);
+    assert_relative_eq!(bootstrap.mean.inner().b, line.b, max_relative = 1e-2);
+    assert_relative_eq!(bootstrap.std_dev.a, stat.errors.a, max_relative = 0.2);
+    assert_relative_eq!(bootstrap.std_dev.b, stat.errors.b, max_relative = 0.2);
+
+    let (lower, upper) = bootstrap.intervals(0.9);
+    let (lower, upper) = (lower.into_inner(), upper.into_inner());
+    assert!(lower.a < line.a && line.a < upper.a);
+    assert!(lower.b < line.b && line.b < upper.b);
+    assert_relative_eq!(
+        (upper.a - lower.a) / 2.0,
+        1.645 * stat.errors.a,
+        max_relative = 0.2
+    );
+}
+
+#[test]
+fn bootstrap_bounded_near_limit() {
+    let (x, y) = data();
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, x, y);
+
+    // slope distribution is cut by the bound close to the best fit, so linear error propagation through the bound transform underestimates the spread
+    let max = line.a + stat.errors.a / 10.0;
+    let mut bounded = Bounded::new(
+        Linear { a: 1.0, b: 0.0 },
+        [ParamBound::Upper(max), ParamBound::Free],
+    );
+    let bootstrap = fit_bootstrap::<_, _, _, SplitMix64>(
+        &mut bounded,
+        x,
+        y,
+        Bootstrap::Residuals,
+        500,
+        1,
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+    assert_eq!(bootstrap.samples.len(), 500);
+    assert!(bootstrap.mean.inner().a <= max);
+
+    let (lower, upper) = bootstrap.intervals(0.9);
+    let (lower, upper) = (lower.into_inner(), upper.into_inner());
+    assert!(upper.a <= max + 1e-12);
+    // distribution is not normal, so these only roughly agree
+    assert_relative_eq!(
+        (upper.a - lower.a) / 2.0,
+        1.645 * bootstrap.std_dev.a,
+        max_relative = 0.25
+    );
+}
+
+#[test]
+fn bootstrap_tied() {
+    let (x, y) = data();
+    let bounds = [ParamBound::Both(0.0, 10.0), ParamBound::Lower(-10.0)];
+    let bounded = fit_bootstrap::<_, _, _, SplitMix64>(
+        &mut Bounded::new(Linear { a: 1.0, b: 0.0 }, bounds),
+        x,
+        y,
+        Bootstrap::Residuals,
+        200,
+        1,
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+
+    // tying nothing changes nothing, even though reduced parameters are the transformed ones
+    let tied = fit_bootstrap::<_, _, _, SplitMix64>(
+        &mut Tied::<_, U<2>>::new(
+            Bounded::new(Linear { a: 1.0, b: 0.0 }, bounds),
+            [Tie::Free, Tie::Free],
+        ),
+        x,
+        y,
+        Bootstrap::Residuals,
+        200,
+        1,
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+    let (mean, bounded_mean) = (tied.mean.inner().inner(), bounded.mean.inner());
+    assert_relative_eq!(mean.a, bounded_mean.a, max_relative = 1e-8);
+    assert_relative_eq!(mean.b, bounded_mean.b, max_relative = 1e-8);
+    assert_relative_eq!(tied.std_dev.a, bounded.std_dev.a, max_relative = 1e-8);
+    assert_relative_eq!(tied.std_dev.b, bounded.std_dev.b, max_relative = 1e-8);
+}
@@ -0,0 +1,152 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use approx::assert_relative_eq;
+use common::{X, Y};
+use nacfahi::{
+    models::{FitModel, basic::Linear},
+    *,
+};
+
+#[test]
+fn linear_regression() {
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y);
+    let report = residual_report(&line, X, Y, default_weights);
+
+    let n = X.len() as f64;
+    let x_mean = X.iter().sum::<f64>() / n;
+    let sxx = X.iter().map(|x| (x - x_mean).powi(2)).sum::<f64>();
+    let s = stat.reduced_chi2.sqrt();
+
+    for (i, (x, y)) in X.iter().zip(&Y).enumerate() {
+        let residual = y - (line.a * x + line.b);
+        assert_relative_eq!(report.residuals[i], residual, max_relative = 1e-8);
+        assert_relative_eq!(report.weighted[i], residual, max_relative = 1e-8);
+
+        let h = 1.0 / n + (x - x_mean).powi(2) / sxx;
+        assert_relative_eq!(report.leverage[i], h, max_relative = 1e-10);
+        let r = residual / (s * (1.0 - h).sqrt());
+        assert_relative_eq!(report.standardized[i], r, max_relative = 1e-8);
+        assert_relative_eq!(
+            report.cooks_distance[i],
+            r * r / 2.0 * h / (1.0 - h),
+            max_relative = 1e-8
+        );
+    }
+    assert_relative_eq!(report.leverage.sum(), 2.0, max_relative = 1e-10);
+
+    let e = &report.residuals;
+    let dw = (1..e.len()).map(|i| (e[i] - e[i - 1]).powi(2)).sum::<f64>() / e.norm_squared();
+    assert_relative_eq!(report.durbin_watson, dw, max_relative = 1e-10);
+    assert_eq!(report.runs.positive + report.runs.negative, 8);
+}
+
+#[test]
+fn known_sigma() {
+    let sigma = [0.1, 0.2, 0.1, 0.3, 0.2, 0.1, 0.2, 0.3];
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y, sigma = sigma);
+    let report = residual_report_sigma(&line, X, Y, sigma, default_weights);
+
+    for (i, sigma) in sigma.iter().enumerate() {
+        assert_relative_eq!(
+            report.weighted[i],
+            report.residuals[i] / sigma,
+            max_relative = 1e-12
+        );
+        // no variance estimate here
+        assert_relative_eq!(
+            report.standardized[i],
+            report.weighted[i] / (1.0 - report.leverage[i]).sqrt(),
+            max_relative = 1e-12
+        );
+    }
+    assert_relative_eq!(
+        report.weighted.norm_squared(),
+        stat.chi2,
+        max_relative = 1e-10
+    );
+}
+
+#[test]
+fn runs() {
+    // + + + - - + + - - -: 4 runs out of 5 positive and 5 negative
+    let x: [f64; 10] = core::array::from_fn(|i| i as f64);
+    let offsets = [1.0, 1.0, 1.0, -1.0, -1.0, 1.0, 1.0, -1.0, -1.0, -1.0];
+    let y: [f64; 10] = core::array::from_fn(|i| offsets[i]);
+    let flat = Linear { a: 0.0, b: 0.0 };
+    let report = residual_report(&flat, x, y, default_weights);
+
+    let runs = report.runs;
+    assert_eq!((runs.runs, runs.positive, runs.negative), (4, 5, 5));
+    // mean is 6, variance is 5 * 4 / 9
+    let z = (4.0 - 6.0) / (20.0f64 / 9.0).sqrt();
+    assert_relative_eq!(runs.z, z, max_relative = 1e-12);
+    assert_relative_eq!(
+        runs.p_value,
+        2.0 * stats::normal_cdf(z),
+        max_relative = 1e-12
+    );
+
+    // all residuals of the same sign
+    let report = residual_report(&flat, x, x.map(|x| x + 1.0), default_weights);
+    assert_eq!(report.runs.runs, 1);
+    assert!(report.runs.z.is_nan());
+}
+
+/// Line without parameter errors support.
+#[derive(Debug)]
+struct BareLine(Linear<f64>);
+
+impl FitModel for BareLine {
+    type Scalar = f64;
+    type ParamCount = U<2>;
+
+    fn evaluate(&self, x: &f64) -> f64 {
+        self.0.evaluate(x)
+    }
+
+    fn jacobian(&self, x: &f64) -> impl Into<GenericArray<f64, U<2>>> {
+        self.0.jacobian(x)
+    }
+
+    fn set_params(&mut self, new_params: GenericArray<f64, U<2>>) {
+        self.0.set_params(new_params);
+    }
+
+    fn get_params(&self) -> impl Into<GenericArray<f64, U<2>>> {
+        self.0.get_params()
+    }
+}
+
+#[test]
+fn model_without_errors() {
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let _ = fit!(&mut line, X, Y);
+    let expected = residual_report(&line, X, Y, default_weights);
+
+    // residual diagnostics don't need parameter errors
+    let mut bare = BareLine(Linear { a: 0.0, b: 0.0 });
+    let _ = fit!(&mut bare, X, Y);
+    let report = residual_report(&bare, X, Y, default_weights);
+    let sigma_report = residual_report_sigma(&bare, X, Y, [0.1; 8], default_weights);
+    for i in 0..X.len() {
+        assert_relative_eq!(
+            report.residuals[i],
+            expected.residuals[i],
+            max_relative = 1e-8
+        );
+        assert_relative_eq!(
+            report.leverage[i],
+            expected.leverage[i],
+            max_relative = 1e-8
+        );
+        assert_relative_eq!(
+            sigma_report.weighted[i],
+            10.0 * report.residuals[i],
+            max_relative = 1e-8
+        );
+    }
+}
@@ -0,0 +1,120 @@
+#![allow(missing_docs)]
+
+use nacfahi::models::{
+    FitModel,
+    basic::{Constant, Gaussian},
+};
+
+const SPIKES: [usize; 3] = [5, 22, 31];
+
+#[derive(nacfahi::models::FitModelSum)]
+#[scalar_type(f64)]
+struct Peak {
+    peak: Gaussian<f64>,
+    background: Constant<f64>,
+}
+
+fn truth() -> Peak {
+    Peak {
+        peak: Gaussian {
+            a: 3.0,
+            x_c: 0.3,
+            sigma: 0.8,
+        },
+        background: Constant { c: 0.5 },
+    }
+}
+
+fn initial() -> Peak {
+    Peak {
+        peak: Gaussian {
+            a: 2.0,
+            x_c: 0.0,
+            sigma: 1.0,
+        },
+        background: Constant { c: 0.0 },
+    }
+}
+
+/// Peak with small noise and a couple of "cosmic ray" spikes
+fn spectrum() -> ([f64; 40], [f64; 40]) {
+    let truth = truth();
+    let x: [f64; 40] = core::array::from_fn(|i| -4.0 + i as f64 * 0.2);
+    let mut y = x.map(|x| truth.evaluate(&x) + 0.02 * (x * 12.9898).sin());
+    for spike in SPIKES {
+        y[spike] += 25.0;
+    }
+    (x, y)
+}
+
+#[test]
+fn spikes_are_ignored() {
+    use approx::assert_abs_diff_eq;
+    use nacfahi::{loss::Huber, *};
+
+    let (x, y) = spectrum();
+    let truth = truth();
+
+    // plain least squares is ruined
+    let mut plain = initial();
+    let _ = fit!(&mut plain, x, y);
+    assert!((plain.background.c - truth.background.c).abs() > 0.5);
+
+    let mut robust = initial();
+    let result = fit!(&mut robust, x, y, loss = Huber(1.345));
+    assert!(
+        result.result.termination.was_successful(),
+        "Approximation should be successful"
+    );
+    assert!(result.iterations > 1);
+    for (i, &weight) in result.weights.iter().enumerate() {
+        if SPIKES.contains(&i) {
+            assert!(weight < 0.01, "Spike at {i} should be suppressed");
+        } else {
+            assert!(weight > 0.5, "Point at {i} should not be suppressed");
+        }
+    }
+    assert_abs_diff_eq!(robust.peak.a, truth.peak.a, epsilon = 0.05);
+    assert_abs_diff_eq!(robust.peak.x_c, truth.peak.x_c, epsilon = 0.05);
+    assert_abs_diff_eq!(robust.peak.sigma, truth.peak.sigma, epsilon = 0.05);
+    assert_abs_diff_eq!(robust.background.c, truth.background.c, epsilon = 0.05);
+    // scale reflects the noise, not the spikes
+    assert!(result.scale < 0.05);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn robust_stat_with_sigma() {
+    use approx::assert_abs_diff_eq;
+    use nacfahi::{loss::Cauchy, *};
+
+    // background-free spectrum
+    let (x, y) = spectrum();
+    let y = y.map(|y| y - truth().background.c);
+    let sigma = [0.02; 40];
+    let truth = truth().peak;
+
+    let mut model = initial().peak;
+    let result = fit_stat!(
+        &mut model,
+        x.as_slice(),
+        y.as_slice(),
+        sigma = sigma.as_slice(),
+        loss = Cauchy(2.385)
+    );
+    let stat = result.result;
+    assert!(
+        stat.report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+    for spike in SPIKES {
+        assert!(result.weights[spike] < 1e-3, "Spike should be suppressed");
+    }
+    // scale is measured in units of sigma
+    assert!(result.scale < 2.0);
+    // spikes do not blow up chi2
+    assert!(stat.reduced_chi2 < 2.0);
+    assert_abs_diff_eq!(model.a, truth.a, epsilon = 3.0 * stat.errors.a_err);
+    assert_abs_diff_eq!(model.x_c, truth.x_c, epsilon = 3.0 * stat.errors.x_c_err);
+    assert_abs_diff_eq!(model.sigma, truth.sigma, epsilon = 3.0 * stat.errors.s_err);
+}
@@ -0,0 +1,182 @@
+#![allow(missing_docs)]
+
+mod common;
+
+use approx::assert_relative_eq;
+use common::grid;
+use nacfahi::{
+    models::{
+        FitModel, FitModelXDeriv,
+        basic::{ErfStep, Hill, Logistic, Richards},
+    },
+    *,
+};
+
+#[test]
+fn special_cases() {
+    let logistic = Logistic {
+        a: 3.0,
+        x_c: 1.0,
+        k: 2.0,
+    };
+    let richards = Richards {
+        a: 3.0,
+        x_c: 1.0,
+        k: 2.0,
+        nu: 1.0,
+    };
+    // Hill is a logistic in ln x
+    let hill = Hill {
+        bottom: 0.0,
+        top: 3.0,
+        ec50: 1.0f64.exp(),
+        n: 2.0,
+    };
+    for x in [-3.0, 0.0, 0.5, 1.0, 2.0, 4.0] {
+        assert_relative_eq!(
+            richards.evaluate(&x),
+            logistic.evaluate(&x),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            hill.evaluate(&f64::exp(x)),
+            logistic.evaluate(&x),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            hill.deriv_x(&f64::exp(x)) * f64::exp(x),
+            logistic.deriv_x(&x),
+            max_relative = 1e-12
+        );
+    }
+}
+
+#[test]
+fn saturation() {
+    let logistic = Logistic {
+        a: 2.0,
+        x_c: 0.0,
+        k: 1.0,
+    };
+    let richards = Richards {
+        a: 2.0,
+        x_c: 0.0,
+        k: 1.0,
+        nu: 0.5,
+    };
+    for x in [-1e4, 1e4] {
+        assert!(logistic.evaluate(&x).is_finite());
+        assert!(richards.evaluate(&x).is_finite());
+        assert!(logistic.jacobian(&x).into().iter().all(|d| d.is_finite()));
+        assert!(richards.jacobian(&x).into().iter().all(|d| d.is_finite()));
+    }
+
+    let hill = Hill {
+        bottom: 1.0,
+        top: 4.0,
+        ec50: 2.0,
+        n: 1.5,
+    };
+    assert_eq!(hill.evaluate(&0.0), 1.0);
+    assert!(hill.jacobian(&0.0).into().iter().all(|d| d.is_finite()));
+    assert_eq!(hill.deriv_x(&0.0), 0.0);
+}
+
+#[test]
+fn fit_logistic() {
+    let truth = Logistic {
+        a: -4.0,
+        x_c: 1.5,
+        k: 1.2,
+    };
+    let x = grid::<61>(-5.0, 0.2);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut logistic = Logistic {
+        a: 1.0,
+        x_c: 0.0,
+        k: 1.0,
+    };
+    let report = fit!(&mut logistic, x, y, guess = true);
+
+    assert!(report.termination.was_successful());
+    assert_relative_eq!(logistic.a, truth.a, max_relative = 1e-8);
+    assert_relative_eq!(logistic.x_c, truth.x_c, max_relative = 1e-8);
+    assert_relative_eq!(logistic.k, truth.k, max_relative = 1e-8);
+}
+
+#[test]
+fn fit_richards() {
+    let truth = Richards {
+        a: 5.0,
+        x_c: 0.5,
+        k: 1.5,
+        nu: 0.3,
+    };
+    let x = grid::<81>(-6.0, 0.15);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut richards = Richards {
+        a: 1.0,
+        x_c: 0.0,
+        k: 1.0,
+        nu: 1.0,
+    };
+    let stat = fit_stat!(&mut richards, x, y, guess = true);
+
+    assert!(stat.report.termination.was_successful());
+    assert_relative_eq!(richards.a, truth.a, max_relative = 1e-6);
+    assert_relative_eq!(richards.x_c, truth.x_c, max_relative = 1e-6);
+    assert_relative_eq!(richards.k, truth.k, max_relative = 1e-6);
+    assert_relative_eq!(richards.nu, truth.nu, max_relative = 1e-6);
+}
+
+#[test]
+fn fit_hill() {
+    let truth = Hill {
+        bottom: 0.5,
+        top: 10.0,
+        ec50: 3.0,
+        n: 2.2,
+    };
+    let x = grid::<60>(0.25, 0.25);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut hill = Hill {
+        bottom: 0.0,
+        top: 1.0,
+        ec50: 1.0,
+        n: 1.0,
+    };
+    let report = fit!(&mut hill, x, y, guess = true);
+
+    assert!(report.termination.was_successful());
+    assert_relative_eq!(hill.bottom, truth.bottom, max_relative = 1e-8);
+    assert_relative_eq!(hill.top, truth.top, max_relative = 1e-8);
+    assert_relative_eq!(hill.ec50, truth.ec50, max_relative = 1e-8);
+    assert_relative_eq!(hill.n, truth.n, max_relative = 1e-8);
+}
+
+#[test]
+fn fit_erf_step() {
+    // descending edge
+    let truth = ErfStep {
+        a: -3.0,
+        x_c: 2.0,
+        sigma: -0.8,
+    };
+    let x = grid::<81>(-2.0, 0.1);
+    let y = x.map(|x| truth.evaluate(&x));
+
+    let mut edge = ErfStep {
+        a: 1.0,
+        x_c: 0.0,
+        sigma: 1.0,
+    };
+    let report = fit!(&mut edge, x, y, guess = true);
+
+    assert!(report.termination.was_successful());
+    assert_relative_eq!(edge.a, truth.a, max_relative = 1e-8);
+    assert_relative_eq!(edge.x_c, truth.x_c, max_relative = 1e-8);
+    assert_relative_eq!(edge.sigma, truth.sigma, max_relative = 1e-8);
+}
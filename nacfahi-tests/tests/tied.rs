@@ -0,0 +1,120 @@
+#![allow(missing_docs)]
+
+use nacfahi::models::{
+    FitModel,
+    basic::Gaussian,
+    utility::{Tie, Tied},
+};
+use static_assertions::assert_impl_all;
+
+assert_impl_all!(Tied<[Gaussian<f64>; 2], nacfahi::U<4>>: FitModel<Scalar = f64>);
+
+fn doublet_ties() -> [Tie<f64>; 6] {
+    [
+        Tie::Free,
+        Tie::Free,
+        Tie::Free,
+        // second peak is half as high, and 3.2 to the right
+        Tie::Linear {
+            source: 0,
+            scale: 0.5,
+            offset: 0.0,
+        },
+        Tie::Linear {
+            source: 1,
+            scale: 1.0,
+            offset: 3.2,
+        },
+        Tie::Free,
+    ]
+}
+
+fn doublet_data() -> ([f64; 40], [f64; 40]) {
+    let truth = [
+        Gaussian::<f64> {
+            a: 2.0,
+            x_c: -0.7,
+            sigma: 0.6,
+        },
+        Gaussian::<f64> {
+            a: 1.0,
+            x_c: 2.5,
+            sigma: 0.9,
+        },
+    ];
+    let x: [f64; 40] = core::array::from_fn(|i| -4.0 + i as f64 * 0.25);
+    let y = x.map(|x| {
+        // deterministic "noise"
+        let noise = 0.01 * (x * 12.9898).sin();
+        truth.iter().map(|g| g.evaluate(&x)).sum::<f64>() + noise
+    });
+    (x, y)
+}
+
+#[test]
+fn doublet() {
+    use approx::assert_abs_diff_eq;
+    use nacfahi::*;
+
+    let (x, y) = doublet_data();
+    let peaks = [
+        Gaussian::<f64> {
+            a: 1.0,
+            x_c: 0.0,
+            sigma: 1.0,
+        },
+        Gaussian::<f64> {
+            a: 1.0,
+            x_c: 3.0,
+            sigma: 1.0,
+        },
+    ];
+    let mut tied = Tied::<_, U<4>>::new(peaks, doublet_ties());
+
+    let report = fit!(&mut tied, x, y);
+    assert!(
+        report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+
+    let [first, second] = tied.into_inner();
+    assert_abs_diff_eq!(second.x_c - first.x_c, 3.2, epsilon = 1e-12);
+    assert_abs_diff_eq!(second.a / first.a, 0.5, epsilon = 1e-12);
+    assert_abs_diff_eq!(first.x_c, -0.7, epsilon = 1e-2);
+    assert_abs_diff_eq!(second.sigma, 0.9, epsilon = 1e-2);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn dependent_errors() {
+    use approx::assert_relative_eq;
+    use nacfahi::*;
+
+    let (x, y) = doublet_data();
+    let peaks = [
+        Gaussian::<f64> {
+            a: 1.0,
+            x_c: 0.0,
+            sigma: 1.0,
+        },
+        Gaussian::<f64> {
+            a: 1.0,
+            x_c: 3.0,
+            sigma: 1.0,
+        },
+    ];
+    let mut tied = Tied::<_, U<4>>::new(peaks, doublet_ties());
+
+    let stat = fit_stat!(&mut tied, x, y);
+    let [first, second] = stat.errors;
+    let covariance = stat.covariance_matrix;
+
+    // errors of free parameters come straight from the covariance
+    assert_relative_eq!(first.a_err, covariance[(0, 0)].sqrt());
+    assert_relative_eq!(first.x_c_err, covariance[(1, 1)].sqrt());
+    assert_relative_eq!(first.s_err, covariance[(2, 2)].sqrt());
+    assert_relative_eq!(second.s_err, covariance[(3, 3)].sqrt());
+    // dependent ones are propagated
+    assert_relative_eq!(second.a_err, 0.5 * first.a_err);
+    assert_relative_eq!(second.x_c_err, first.x_c_err);
+}
@@ -0,0 +1,145 @@
+#![allow(missing_docs)]
+
+use nacfahi::models::basic::Linear;
+
+const X: [f64; 8] = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+const Y: [f64; 8] = [1.2, 2.7, 5.3, 6.9, 9.2, 10.8, 13.1, 25.0];
+const SIGMA: [f64; 8] = [0.1, 0.2, 0.1, 0.3, 0.2, 0.1, 0.2, 5.0];
+
+/// Analytic weighted least squares for a line: returns `(a, b)`, their covariance matrix (for unit variance), and chi2.
+fn weighted_line(w: [f64; 8]) -> ((f64, f64), [[f64; 2]; 2], f64) {
+    let s = |f: &dyn Fn(f64, f64) -> f64| -> f64 {
+        X.iter()
+            .zip(Y)
+            .zip(w)
+            .map(|((&x, y), w)| w * w * f(x, y))
+            .sum()
+    };
+    let s1 = s(&|_, _| 1.0);
+    let sx = s(&|x, _| x);
+    let sxx = s(&|x, _| x * x);
+    let sy = s(&|_, y| y);
+    let sxy = s(&|x, y| x * y);
+    let det = s1 * sxx - sx * sx;
+    let a = (s1 * sxy - sx * sy) / det;
+    let b = (sxx * sy - sx * sxy) / det;
+    let covariance = [[s1 / det, -sx / det], [-sx / det, sxx / det]];
+    let chi2 = s(&|x, y| (a * x + b - y).powi(2));
+    ((a, b), covariance, chi2)
+}
+
+#[test]
+fn weights_are_respected() {
+    use approx::assert_relative_eq;
+    use nacfahi::*;
+
+    // outlier at the end is heavily suppressed
+    let weight = |x: f64, _y: f64| if x > 6.5 { 0.01 } else { 1.0 + x };
+    let ((a, b), _, _) = weighted_line(X.map(|x| weight(x, 0.0)));
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let report = fit!(&mut line, X, Y, weights = weight);
+
+    assert!(
+        report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+    assert_relative_eq!(line.a, a, max_relative = 1e-10);
+    assert_relative_eq!(line.b, b, max_relative = 1e-10);
+}
+
+#[test]
+fn sigma_is_inverse_weight() {
+    use approx::assert_relative_eq;
+    use nacfahi::*;
+
+    let ((a, b), _, _) = weighted_line(SIGMA.map(|s| 1.0 / s));
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let report = fit!(&mut line, X, Y, sigma = SIGMA);
+    assert!(
+        report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+    assert_relative_eq!(line.a, a, max_relative = 1e-10);
+    assert_relative_eq!(line.b, b, max_relative = 1e-10);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn sigma_slices() {
+    use approx::assert_relative_eq;
+    use nacfahi::*;
+
+    let ((a, b), _, _) = weighted_line(SIGMA.map(|s| 1.0 / s));
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let report = fit_sigma(
+        &mut line,
+        X.as_slice(),
+        Y.as_slice(),
+        SIGMA.as_slice(),
+        LevenbergMarquardt::new(),
+        default_weights,
+    );
+    assert!(
+        report.termination.was_successful(),
+        "Approximation should be successful"
+    );
+    assert_relative_eq!(line.a, a, max_relative = 1e-10);
+    assert_relative_eq!(line.b, b, max_relative = 1e-10);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn absolute_sigma_stat() {
+    use approx::assert_relative_eq;
+    use nacfahi::*;
+
+    let (_, covariance, chi2) = weighted_line(SIGMA.map(|s| 1.0 / s));
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y, sigma = SIGMA);
+
+    assert_relative_eq!(stat.chi2, chi2, max_relative = 1e-8);
+    assert_relative_eq!(stat.reduced_chi2, chi2 / 6.0, max_relative = 1e-8);
+    // covariance is not rescaled with known errors
+    for (i, row) in covariance.iter().enumerate() {
+        for (j, &expected) in row.iter().enumerate() {
+            assert_relative_eq!(
+                stat.covariance_matrix[(i, j)],
+                expected,
+                max_relative = 1e-8
+            );
+        }
+    }
+    assert_relative_eq!(stat.errors.a, covariance[0][0].sqrt(), max_relative = 1e-8);
+    assert_relative_eq!(stat.errors.b, covariance[1][1].sqrt(), max_relative = 1e-8);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn relative_weights_stat() {
+    use approx::assert_relative_eq;
+    use nacfahi::*;
+
+    let weight = |x: f64, _y: f64| 1.0 / (1.0 + x);
+    let (_, covariance, chi2) = weighted_line(X.map(|x| weight(x, 0.0)));
+
+    let mut line = Linear { a: 0.0, b: 0.0 };
+    let stat = fit_stat!(&mut line, X, Y, weights = weight);
+
+    assert_relative_eq!(stat.chi2, chi2, max_relative = 1e-8);
+    // data variance is estimated from the residuals
+    let reduced_chi2 = chi2 / 6.0;
+    assert_relative_eq!(stat.reduced_chi2, reduced_chi2, max_relative = 1e-8);
+    for (i, row) in covariance.iter().enumerate() {
+        for (j, &expected) in row.iter().enumerate() {
+            assert_relative_eq!(
+                stat.covariance_matrix[(i, j)],
+                expected * reduced_chi2,
+                max_relative = 1e-8
+            );
+        }
+    }
+}
@@ -1,18 +1,46 @@
+use core::ops::Mul;
+
 use generic_array::{GenericArray, functional::FunctionalSequence};
 use generic_array_storage::{Conv, GenericArrayStorage, GenericMatrix, GenericMatrixFromExt};
 use levenberg_marquardt::LeastSquaresProblem;
 use nalgebra::{ComplexField, DefaultAllocator, OMatrix, allocator::Allocator};
 
-use crate::models::FitModel;
+use crate::input_row;
+use crate::models::{MultiInputModel, MultiOutputModel};
+use typenum::Prod;
 
-pub(crate) struct ConstOptimizationProblem<'data, Points: Conv, Model: FitModel, Weights> {
-    pub model: Model,
-    pub x: nalgebra::VectorView<'data, Model::Scalar, Points::Nalg>,
+pub(crate) struct ConstOptimizationProblem<'data, Points: Conv, Model: MultiInputModel, Weights> {
+    pub model: &'data mut Model,
+    pub x: nalgebra::MatrixView<'data, Model::Scalar, Points::Nalg, <Model::Inputs as Conv>::Nalg>,
     pub y: nalgebra::VectorView<'data, Model::Scalar, Points::Nalg>,
+    pub sigma: Option<nalgebra::VectorView<'data, Model::Scalar, Points::Nalg>>,
     pub weights: Weights,
 }
 
-impl<Points: Conv, Model: FitModel, Weights>
+impl<Points: Conv, Model: MultiInputModel, Weights>
+    ConstOptimizationProblem<'_, Points, Model, Weights>
+where
+    Model::Scalar: ComplexField + Copy,
+    Weights: Fn(
+        GenericArray<Model::Scalar, <Model::Inputs as Conv>::TNum>,
+        Model::Scalar,
+    ) -> Model::Scalar,
+{
+    /// Weight of a data point, applied to both residual and jacobian row.
+    fn weight(
+        &self,
+        i_x: usize,
+        x: GenericArray<Model::Scalar, <Model::Inputs as Conv>::TNum>,
+        y: Model::Scalar,
+    ) -> Model::Scalar {
+        let weight = (self.weights)(x, y);
+        self.sigma
+            .as_ref()
+            .map_or(weight, |sigma| weight / sigma[i_x])
+    }
+}
+
+impl<Points: Conv, Model: MultiInputModel, Weights>
     LeastSquaresProblem<Model::Scalar, Points::Nalg, <Model::ParamCount as Conv>::Nalg>
     for ConstOptimizationProblem<'_, Points, Model, Weights>
 where
@@ -20,8 +48,10 @@ where
     DefaultAllocator: Allocator<<Model::ParamCount as Conv>::Nalg, nalgebra::U1>,
     DefaultAllocator: Allocator<Points::Nalg, <Model::ParamCount as Conv>::Nalg>,
     Model::Scalar: nalgebra::Scalar + ComplexField + Copy,
-    Model: FitModel,
-    Weights: Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+    Weights: Fn(
+        GenericArray<Model::Scalar, <Model::Inputs as Conv>::TNum>,
+        Model::Scalar,
+    ) -> Model::Scalar,
 {
     type ResidualStorage = GenericArrayStorage<Model::Scalar, Points, typenum::U1>;
 
@@ -44,11 +74,17 @@ where
     }
 
     fn residuals(&self) -> Option<GenericMatrix<Model::Scalar, Points, typenum::U1>> {
-        let mat: GenericMatrix<Model::Scalar, Points, typenum::U1> = self
-            .x
-            .zip_map(&self.y, |x, y| {
-                (self.weights)(x, y) * (self.model.evaluate(&x) - y)
-            })
+        let mat: GenericMatrix<Model::Scalar, Points, typenum::U1> =
+            OMatrix::<Model::Scalar, Points::Nalg, nalgebra::U1>::from_fn_generic(
+                Points::new_nalg(),
+                nalgebra::U1,
+                |i_x, _| {
+                    let x = input_row::<_, _, Model::Inputs>(&self.x, i_x);
+                    let y = self.y[i_x];
+                    let f_x = self.model.evaluate(&x);
+                    self.weight(i_x, x, y) * (f_x - y)
+                },
+            )
             .into_generic_matrix();
         Some(mat)
     }
@@ -60,10 +96,12 @@ where
                 Model::ParamCount::new_nalg(),
             );
 
-        for i_x in 0..self.x.len() {
+        for i_x in 0..self.x.nrows() {
+            let x = input_row::<_, _, Model::Inputs>(&self.x, i_x);
             let jacobian_x: GenericArray<_, <Model::ParamCount as Conv>::TNum> =
-                self.model.jacobian(&self.x[i_x]).into();
-            let arr = jacobian_x.map(|v| GenericArray::<_, typenum::U1>::from_array([v]));
+                self.model.jacobian(&x).into();
+            let weight = self.weight(i_x, x, self.y[i_x]);
+            let arr = jacobian_x.map(|v| GenericArray::<_, typenum::U1>::from_array([weight * v]));
             let mat = GenericMatrix::<Model::Scalar, nalgebra::U1, Model::ParamCount>::from_data(
                 GenericArrayStorage(arr),
             );
@@ -72,3 +110,111 @@ where
         Some(res.into_generic_matrix())
     }
 }
+
+/// [`typenum`]-faced count of residuals for multi-output problem.
+pub(crate) type Stacked<Points, Outputs> = Prod<<Points as Conv>::TNum, <Outputs as Conv>::TNum>;
+
+pub(crate) struct ConstMultiOutputProblem<'data, Points: Conv, Model: MultiOutputModel, Weights> {
+    pub model: &'data mut Model,
+    pub x: nalgebra::VectorView<'data, Model::Scalar, Points::Nalg>,
+    pub y: nalgebra::MatrixView<'data, Model::Scalar, Points::Nalg, <Model::Outputs as Conv>::Nalg>,
+    pub weights: Weights,
+}
+
+impl<Points: Conv, Model: MultiOutputModel, Weights>
+    LeastSquaresProblem<
+        Model::Scalar,
+        <Stacked<Points, Model::Outputs> as Conv>::Nalg,
+        <Model::ParamCount as Conv>::Nalg,
+    > for ConstMultiOutputProblem<'_, Points, Model, Weights>
+where
+    Points::TNum: Mul<<Model::Outputs as Conv>::TNum>,
+    Stacked<Points, Model::Outputs>: Conv,
+    DefaultAllocator: Allocator<<Stacked<Points, Model::Outputs> as Conv>::Nalg, nalgebra::U1>,
+    DefaultAllocator: Allocator<<Model::ParamCount as Conv>::Nalg, nalgebra::U1>,
+    DefaultAllocator: Allocator<
+            <Stacked<Points, Model::Outputs> as Conv>::Nalg,
+            <Model::ParamCount as Conv>::Nalg,
+        >,
+    Model::Scalar: nalgebra::Scalar + ComplexField + Copy,
+    Weights: Fn(
+        Model::Scalar,
+        GenericArray<Model::Scalar, <Model::Outputs as Conv>::TNum>,
+    ) -> GenericArray<Model::Scalar, <Model::Outputs as Conv>::TNum>,
+{
+    type ResidualStorage =
+        GenericArrayStorage<Model::Scalar, Stacked<Points, Model::Outputs>, typenum::U1>;
+
+    type ParameterStorage = GenericArrayStorage<Model::Scalar, Model::ParamCount, typenum::U1>;
+
+    type JacobianStorage =
+        GenericArrayStorage<Model::Scalar, Stacked<Points, Model::Outputs>, Model::ParamCount>;
+
+    fn set_params(&mut self, x: &GenericMatrix<Model::Scalar, Model::ParamCount, typenum::U1>) {
+        let slice: &[Model::Scalar] = x.data.as_ref();
+        let arr =
+            GenericArray::<Model::Scalar, <Model::ParamCount as Conv>::TNum>::from_slice(slice)
+                .clone();
+        self.model.set_params(arr);
+    }
+
+    fn params(&self) -> GenericMatrix<Model::Scalar, Model::ParamCount, typenum::U1> {
+        let pars: GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum> =
+            self.model.get_params().into();
+        GenericMatrix::from_data(GenericArrayStorage(GenericArray::from_array([pars])))
+    }
+
+    fn residuals(
+        &self,
+    ) -> Option<GenericMatrix<Model::Scalar, Stacked<Points, Model::Outputs>, typenum::U1>> {
+        let outputs = self.y.ncols();
+        let mut res = OMatrix::<
+            Model::Scalar,
+            <Stacked<Points, Model::Outputs> as Conv>::Nalg,
+            nalgebra::U1,
+        >::zeros_generic(
+            Stacked::<Points, Model::Outputs>::new_nalg(), nalgebra::U1
+        );
+
+        for i_x in 0..self.x.len() {
+            let x = self.x[i_x];
+            let y = input_row::<_, _, Model::Outputs>(&self.y, i_x);
+            let f_x: GenericArray<_, <Model::Outputs as Conv>::TNum> =
+                self.model.evaluate(&x).into();
+            let w = (self.weights)(x, y.clone());
+            for i_out in 0..outputs {
+                res[i_x * outputs + i_out] = w[i_out] * (f_x[i_out] - y[i_out]);
+            }
+        }
+        Some(res.into_generic_matrix())
+    }
+
+    fn jacobian(
+        &self,
+    ) -> Option<GenericMatrix<Model::Scalar, Stacked<Points, Model::Outputs>, Model::ParamCount>>
+    {
+        let outputs = self.y.ncols();
+        let mut res = OMatrix::<
+            Model::Scalar,
+            <Stacked<Points, Model::Outputs> as Conv>::Nalg,
+            <Model::ParamCount as Conv>::Nalg,
+        >::zeros_generic(
+            Stacked::<Points, Model::Outputs>::new_nalg(),
+            Model::ParamCount::new_nalg(),
+        );
+
+        for i_x in 0..self.x.len() {
+            let x = self.x[i_x];
+            let y = input_row::<_, _, Model::Outputs>(&self.y, i_x);
+            let w = (self.weights)(x, y);
+            let jacobian_x: GenericArray<_, <Model::Outputs as Conv>::TNum> =
+                self.model.jacobian(&x).into();
+            for (i_out, (row, w)) in jacobian_x.into_iter().zip(w).enumerate() {
+                for (i_param, v) in row.into_iter().enumerate() {
+                    res[(i_x * outputs + i_out, i_param)] = w * v;
+                }
+            }
+        }
+        Some(res.into_generic_matrix())
+    }
+}
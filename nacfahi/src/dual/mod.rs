@@ -0,0 +1,566 @@
+use core::cmp::Ordering;
+use core::num::FpCategory;
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+use generic_array::{
+    ArrayLength, GenericArray, functional::FunctionalSequence, sequence::GenericSequence,
+};
+use num_traits::{Float, FloatConst, Num, NumCast, One, ToPrimitive, Zero};
+
+/// Dual number for forward-mode automatic differentiation: a value alongside its derivatives over `N` variables.
+///
+/// Arithmetic operations and [`Float`] functions propagate derivatives via the chain rule, so evaluating any generic function with dual numbers yields its gradient alongside the value. Everything is stored inline, so this is `no_std`- and stack-friendly.
+///
+/// Comparisons only account for the [`Dual::value`].
+///
+/// ```rust
+/// # use approx::assert_ulps_eq;
+/// # use nacfahi::{dual::Dual, U};
+/// # use num_traits::Float;
+/// // f(a, b) = a * sin(b)
+/// fn f<T: Float>(a: T, b: T) -> T {
+///     a * b.sin()
+/// }
+///
+/// let a = Dual::<f64, U<2>>::variable(2.0, 0);
+/// let b = Dual::<f64, U<2>>::variable(0.5, 1);
+/// let res = f(a, b);
+///
+/// assert_ulps_eq!(res.value, 2.0 * 0.5.sin());
+/// assert_ulps_eq!(res.grad[0], 0.5.sin());
+/// assert_ulps_eq!(res.grad[1], 2.0 * 0.5.cos());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Dual<Scalar, N: ArrayLength> {
+    /// Value of the number
+    pub value: Scalar,
+    /// Derivatives over each of the variables
+    pub grad: GenericArray<Scalar, N>,
+}
+
+impl<Scalar: Copy, N: ArrayLength> Copy for Dual<Scalar, N> where GenericArray<Scalar, N>: Copy {}
+
+impl<Scalar: Zero, N: ArrayLength> Dual<Scalar, N> {
+    /// Creates a number not depending on any of the variables.
+    #[inline]
+    pub fn constant(value: Scalar) -> Self {
+        Self {
+            value,
+            grad: GenericArray::generate(|_| Scalar::zero()),
+        }
+    }
+}
+
+impl<Scalar: Zero + One, N: ArrayLength> Dual<Scalar, N> {
+    /// Creates `index`-th variable.
+    ///
+    /// ### Panics
+    ///
+    /// - If `index` is not less than `N`
+    #[inline]
+    pub fn variable(value: Scalar, index: usize) -> Self {
+        assert!(index < N::USIZE, "Variable index out of bounds");
+        Self {
+            value,
+            grad: GenericArray::generate(|i| {
+                if i == index {
+                    Scalar::one()
+                } else {
+                    Scalar::zero()
+                }
+            }),
+        }
+    }
+}
+
+impl<Scalar: Float, N: ArrayLength> Dual<Scalar, N> {
+    /// Applies a function with known `value` and `derivative` at the current point.
+    #[inline]
+    fn chain(self, value: Scalar, derivative: Scalar) -> Self {
+        Self {
+            value,
+            grad: self.grad.map(|g| g * derivative),
+        }
+    }
+}
+
+impl<Scalar: Zero, N: ArrayLength> From<Scalar> for Dual<Scalar, N> {
+    #[inline]
+    fn from(value: Scalar) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl<Scalar: PartialEq, N: ArrayLength> PartialEq for Dual<Scalar, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<Scalar: PartialOrd, N: ArrayLength> PartialOrd for Dual<Scalar, N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<Scalar: Float, N: ArrayLength> Neg for Dual<Scalar, N> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self {
+            value: -self.value,
+            grad: self.grad.map(Neg::neg),
+        }
+    }
+}
+
+impl<Scalar: Float, N: ArrayLength> Add for Dual<Scalar, N> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            value: self.value + rhs.value,
+            grad: self.grad.zip(rhs.grad, Add::add),
+        }
+    }
+}
+
+impl<Scalar: Float, N: ArrayLength> Sub for Dual<Scalar, N> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            value: self.value - rhs.value,
+            grad: self.grad.zip(rhs.grad, Sub::sub),
+        }
+    }
+}
+
+#[allow(
+    clippy::suspicious_arithmetic_impl,
+    reason = "Derivatives follow the product rule"
+)]
+impl<Scalar: Float, N: ArrayLength> Mul for Dual<Scalar, N> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (a, b) = (self.value, rhs.value);
+        Self {
+            value: a * b,
+            grad: self.grad.zip(rhs.grad, |da, db| da * b + a * db),
+        }
+    }
+}
+
+#[allow(
+    clippy::suspicious_arithmetic_impl,
+    reason = "Derivatives follow the quotient rule"
+)]
+impl<Scalar: Float, N: ArrayLength> Div for Dual<Scalar, N> {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self::Output {
+        let (a, b) = (self.value, rhs.value);
+        let value = a / b;
+        Self {
+            value,
+            grad: self.grad.zip(rhs.grad, |da, db| (da - value * db) / b),
+        }
+    }
+}
+
+impl<Scalar: Float, N: ArrayLength> Rem for Dual<Scalar, N> {
+    type Output = Self;
+
+    #[inline]
+    fn rem(self, rhs: Self) -> Self::Output {
+        // a % b = a - b * trunc(a / b)
+        let (a, b) = (self.value, rhs.value);
+        let quotient = (a / b).trunc();
+        Self {
+            value: a % b,
+            grad: self.grad.zip(rhs.grad, |da, db| da - quotient * db),
+        }
+    }
+}
+
+macro_rules! assign_ops {
+    ($($trait:ident :: $method:ident => $op:ident :: $op_method:ident),+ $(,)?) => {$(
+        impl<Scalar: Float, N: ArrayLength> $trait for Dual<Scalar, N> {
+            #[inline]
+            fn $method(&mut self, rhs: Self) {
+                *self = $op::$op_method(self.clone(), rhs);
+            }
+        }
+    )+};
+}
+
+assign_ops! {
+    AddAssign::add_assign => Add::add,
+    SubAssign::sub_assign => Sub::sub,
+    MulAssign::mul_assign => Mul::mul,
+    DivAssign::div_assign => Div::div,
+    RemAssign::rem_assign => Rem::rem,
+}
+
+impl<Scalar: Float, N: ArrayLength> Zero for Dual<Scalar, N> {
+    #[inline]
+    fn zero() -> Self {
+        Self::constant(Scalar::zero())
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+}
+
+impl<Scalar: Float, N: ArrayLength> One for Dual<Scalar, N> {
+    #[inline]
+    fn one() -> Self {
+        Self::constant(Scalar::one())
+    }
+}
+
+impl<Scalar: Float, N: ArrayLength> Num for Dual<Scalar, N> {
+    type FromStrRadixErr = Scalar::FromStrRadixErr;
+
+    #[inline]
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        Scalar::from_str_radix(str, radix).map(Self::constant)
+    }
+}
+
+impl<Scalar: Float, N: ArrayLength> ToPrimitive for Dual<Scalar, N> {
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        self.value.to_i64()
+    }
+
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        self.value.to_u64()
+    }
+
+    #[inline]
+    fn to_f64(&self) -> Option<f64> {
+        self.value.to_f64()
+    }
+}
+
+impl<Scalar: Float, N: ArrayLength> NumCast for Dual<Scalar, N> {
+    #[inline]
+    fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+        <Scalar as NumCast>::from(n).map(Self::constant)
+    }
+}
+
+/// Forwards functions not depending on derivatives to the value.
+macro_rules! value_fns {
+    ($($name:ident -> $ret:ty),+ $(,)?) => {$(
+        #[inline]
+        fn $name(self) -> $ret {
+            self.value.$name()
+        }
+    )+};
+}
+
+/// Creates constants from the value type.
+macro_rules! constant_fns {
+    ($trait:ident: $($name:ident),+ $(,)?) => {$(
+        #[inline]
+        fn $name() -> Self {
+            Self::constant(<Scalar as $trait>::$name())
+        }
+    )+};
+}
+
+/// Functions with zero derivative (almost everywhere).
+macro_rules! step_fns {
+    ($($name:ident),+ $(,)?) => {$(
+        #[inline]
+        fn $name(self) -> Self {
+            Self::constant(self.value.$name())
+        }
+    )+};
+}
+
+impl<Scalar: Float, N: ArrayLength> Float for Dual<Scalar, N>
+where
+    GenericArray<Scalar, N>: Copy,
+{
+    constant_fns!(Float: nan, infinity, neg_infinity, neg_zero, min_value, min_positive_value, max_value, epsilon);
+
+    value_fns!(
+        is_nan -> bool,
+        is_infinite -> bool,
+        is_finite -> bool,
+        is_normal -> bool,
+        classify -> FpCategory,
+        is_sign_positive -> bool,
+        is_sign_negative -> bool,
+        integer_decode -> (u64, i16, i8),
+    );
+
+    step_fns!(floor, ceil, round, trunc, signum);
+
+    #[inline]
+    fn fract(self) -> Self {
+        self.chain(self.value.fract(), Scalar::one())
+    }
+
+    #[inline]
+    fn abs(self) -> Self {
+        self.chain(self.value.abs(), self.value.signum())
+    }
+
+    #[inline]
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+
+    #[inline]
+    fn recip(self) -> Self {
+        let value = self.value.recip();
+        self.chain(value, -value * value)
+    }
+
+    #[inline]
+    fn powi(self, n: i32) -> Self {
+        if n == 0 {
+            // derivative below would be `0 * 0^-1` at zero
+            return Self::constant(Scalar::one());
+        }
+        let n_scalar: Scalar = NumCast::from(n).expect("Should be able to represent exponent");
+        self.chain(self.value.powi(n), n_scalar * self.value.powi(n - 1))
+    }
+
+    #[inline]
+    fn powf(self, n: Self) -> Self {
+        let value = self.value.powf(n.value);
+        let d_self = n.value * self.value.powf(n.value - Scalar::one());
+        if n.grad.iter().all(Zero::is_zero) {
+            // logarithm below is not defined for negative base
+            self.chain(value, d_self)
+        } else {
+            let d_n = value * self.value.ln();
+            Self {
+                value,
+                grad: self.grad.zip(n.grad, |ds, dn| ds * d_self + dn * d_n),
+            }
+        }
+    }
+
+    #[inline]
+    fn sqrt(self) -> Self {
+        let value = self.value.sqrt();
+        self.chain(value, (value + value).recip())
+    }
+
+    #[inline]
+    fn exp(self) -> Self {
+        let value = self.value.exp();
+        self.chain(value, value)
+    }
+
+    #[inline]
+    fn exp2(self) -> Self {
+        let value = self.value.exp2();
+        let ln_2 = (Scalar::one() + Scalar::one()).ln();
+        self.chain(value, value * ln_2)
+    }
+
+    #[inline]
+    fn ln(self) -> Self {
+        self.chain(self.value.ln(), self.value.recip())
+    }
+
+    #[inline]
+    fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+
+    #[inline]
+    fn log2(self) -> Self {
+        let ln_2 = (Scalar::one() + Scalar::one()).ln();
+        self.chain(self.value.log2(), (self.value * ln_2).recip())
+    }
+
+    #[inline]
+    fn log10(self) -> Self {
+        let ten: Scalar = NumCast::from(10).expect("Should be able to represent 10");
+        let ln_10 = ten.ln();
+        self.chain(self.value.log10(), (self.value * ln_10).recip())
+    }
+
+    #[inline]
+    fn max(self, other: Self) -> Self {
+        if other.value > self.value || self.value.is_nan() {
+            other
+        } else {
+            self
+        }
+    }
+
+    #[inline]
+    fn min(self, other: Self) -> Self {
+        if other.value < self.value || self.value.is_nan() {
+            other
+        } else {
+            self
+        }
+    }
+
+    #[inline]
+    fn abs_sub(self, other: Self) -> Self {
+        if self.value > other.value {
+            self - other
+        } else {
+            Self::zero()
+        }
+    }
+
+    #[inline]
+    fn cbrt(self) -> Self {
+        let value = self.value.cbrt();
+        self.chain(
+            value,
+            (value * value * (Scalar::one() + Scalar::one() + Scalar::one())).recip(),
+        )
+    }
+
+    #[inline]
+    fn hypot(self, other: Self) -> Self {
+        (self * self + other * other).sqrt()
+    }
+
+    #[inline]
+    fn sin(self) -> Self {
+        let (sin, cos) = self.value.sin_cos();
+        self.chain(sin, cos)
+    }
+
+    #[inline]
+    fn cos(self) -> Self {
+        let (sin, cos) = self.value.sin_cos();
+        self.chain(cos, -sin)
+    }
+
+    #[inline]
+    fn tan(self) -> Self {
+        let value = self.value.tan();
+        self.chain(value, Scalar::one() + value * value)
+    }
+
+    #[inline]
+    fn asin(self) -> Self {
+        let derivative = (Scalar::one() - self.value * self.value).sqrt().recip();
+        self.chain(self.value.asin(), derivative)
+    }
+
+    #[inline]
+    fn acos(self) -> Self {
+        let derivative = -(Scalar::one() - self.value * self.value).sqrt().recip();
+        self.chain(self.value.acos(), derivative)
+    }
+
+    #[inline]
+    fn atan(self) -> Self {
+        let derivative = (Scalar::one() + self.value * self.value).recip();
+        self.chain(self.value.atan(), derivative)
+    }
+
+    #[inline]
+    fn atan2(self, other: Self) -> Self {
+        let (y, x) = (self.value, other.value);
+        let norm = x * x + y * y;
+        Self {
+            value: y.atan2(x),
+            grad: self.grad.zip(other.grad, |dy, dx| (x * dy - y * dx) / norm),
+        }
+    }
+
+    #[inline]
+    fn sin_cos(self) -> (Self, Self) {
+        let (sin, cos) = self.value.sin_cos();
+        (self.chain(sin, cos), self.chain(cos, -sin))
+    }
+
+    #[inline]
+    fn exp_m1(self) -> Self {
+        self.chain(self.value.exp_m1(), self.value.exp())
+    }
+
+    #[inline]
+    fn ln_1p(self) -> Self {
+        self.chain(self.value.ln_1p(), (Scalar::one() + self.value).recip())
+    }
+
+    #[inline]
+    fn sinh(self) -> Self {
+        self.chain(self.value.sinh(), self.value.cosh())
+    }
+
+    #[inline]
+    fn cosh(self) -> Self {
+        self.chain(self.value.cosh(), self.value.sinh())
+    }
+
+    #[inline]
+    fn tanh(self) -> Self {
+        let value = self.value.tanh();
+        self.chain(value, Scalar::one() - value * value)
+    }
+
+    #[inline]
+    fn asinh(self) -> Self {
+        let derivative = (self.value * self.value + Scalar::one()).sqrt().recip();
+        self.chain(self.value.asinh(), derivative)
+    }
+
+    #[inline]
+    fn acosh(self) -> Self {
+        let derivative = (self.value * self.value - Scalar::one()).sqrt().recip();
+        self.chain(self.value.acosh(), derivative)
+    }
+
+    #[inline]
+    fn atanh(self) -> Self {
+        let derivative = (Scalar::one() - self.value * self.value).recip();
+        self.chain(self.value.atanh(), derivative)
+    }
+}
+
+#[allow(non_snake_case, reason = "Constant names are defined by the trait")]
+impl<Scalar: Float + FloatConst, N: ArrayLength> FloatConst for Dual<Scalar, N> {
+    constant_fns!(
+        FloatConst: E,
+        FRAC_1_PI,
+        FRAC_1_SQRT_2,
+        FRAC_2_PI,
+        FRAC_2_SQRT_PI,
+        FRAC_PI_2,
+        FRAC_PI_3,
+        FRAC_PI_4,
+        FRAC_PI_6,
+        FRAC_PI_8,
+        LN_10,
+        LN_2,
+        LOG10_E,
+        LOG2_E,
+        PI,
+        SQRT_2,
+    );
+}
+
+#[cfg(test)]
+mod tests;
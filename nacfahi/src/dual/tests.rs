@@ -0,0 +1,67 @@
+use num_traits::{Float, FloatConst, One};
+use typenum::{U1, U2};
+
+use super::Dual;
+
+type D1 = Dual<f64, U1>;
+
+/// Checks derivative of `f` against a central finite difference.
+fn check(f: impl Fn(D1) -> D1, x: f64) {
+    let h = 1e-6;
+    let res = f(D1::variable(x, 0));
+    let numeric = (f(D1::constant(x + h)).value - f(D1::constant(x - h)).value) / (2.0 * h);
+    approx::assert_relative_eq!(res.grad[0], numeric, max_relative = 1e-6, epsilon = 1e-8);
+}
+
+#[test]
+fn elementary_functions() {
+    let functions: [fn(D1) -> D1; 22] = [
+        |x| x * x * x - x / (x + D1::one()),
+        |x| x % D1::constant(0.3),
+        Float::recip,
+        |x| x.powi(3),
+        |x| x.powf(D1::constant(2.5)),
+        |x| D1::constant(1.7).powf(x),
+        |x| x.powf(x),
+        Float::sqrt,
+        Float::cbrt,
+        Float::exp,
+        Float::exp2,
+        Float::ln,
+        Float::log10,
+        |x| x.log(D1::constant(3.0)),
+        |x| x.hypot(D1::constant(2.0) * x + D1::one()),
+        |x| x.sin() * x.cos() + x.tan(),
+        |x| x.asin() + x.acos() * x.atan(),
+        |x| x.atan2(D1::one() - x),
+        |x| x.sinh() + x.cosh() * x.tanh(),
+        |x| x.asinh() + x.atanh() * x.exp_m1() + x.ln_1p(),
+        |x| (x + D1::constant(1.5)).acosh(),
+        |x| x.abs() * D1::PI(),
+    ];
+    for f in functions {
+        for x in [0.15, 0.4, 0.65] {
+            check(f, x);
+        }
+    }
+}
+
+#[test]
+fn gradient() {
+    let a = Dual::<f64, U2>::variable(1.5, 0);
+    let b = Dual::<f64, U2>::variable(-0.5, 1);
+    let res = (a * b).exp() + a / b;
+
+    approx::assert_ulps_eq!(res.value, (1.5f64 * -0.5).exp() + 1.5 / -0.5);
+    approx::assert_ulps_eq!(res.grad[0], -0.5 * (1.5f64 * -0.5).exp() + 1.0 / -0.5);
+    approx::assert_ulps_eq!(res.grad[1], 1.5 * (1.5f64 * -0.5).exp() - 1.5 / 0.25);
+}
+
+#[test]
+fn powi_zero() {
+    for x in [0.0, 1.5] {
+        let res = D1::variable(x, 0).powi(0);
+        approx::assert_ulps_eq!(res.value, 1.0);
+        approx::assert_ulps_eq!(res.grad[0], 0.0);
+    }
+}
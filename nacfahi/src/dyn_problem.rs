@@ -1,18 +1,43 @@
 use generic_array::{GenericArray, functional::FunctionalSequence};
 use generic_array_storage::{Conv, GenericArrayStorage, GenericMatrix};
 use levenberg_marquardt::LeastSquaresProblem;
-use nalgebra::{ComplexField, DefaultAllocator, Dyn, OMatrix, allocator::Allocator};
+use nalgebra::{ComplexField, DefaultAllocator, Dim, Dyn, OMatrix, allocator::Allocator};
 
-use crate::models::FitModel;
+use crate::input_row;
+use crate::models::{FitModel, MultiInputModel, MultiOutputModel, utility::GlobalFit};
+use alloc::vec::Vec;
 
-pub(crate) struct DynOptimizationProblem<'data, Model: FitModel, Weights> {
-    pub model: Model,
-    pub x: nalgebra::VectorView<'data, Model::Scalar, Dyn>,
+pub(crate) struct DynOptimizationProblem<'data, Model: MultiInputModel, Weights> {
+    pub model: &'data mut Model,
+    pub x: nalgebra::MatrixView<'data, Model::Scalar, Dyn, <Model::Inputs as Conv>::Nalg>,
     pub y: nalgebra::VectorView<'data, Model::Scalar, Dyn>,
+    pub sigma: Option<nalgebra::VectorView<'data, Model::Scalar, Dyn>>,
     pub weights: Weights,
 }
 
-impl<Model: FitModel, Weights>
+impl<Model: MultiInputModel, Weights> DynOptimizationProblem<'_, Model, Weights>
+where
+    Model::Scalar: ComplexField + Copy,
+    Weights: Fn(
+        GenericArray<Model::Scalar, <Model::Inputs as Conv>::TNum>,
+        Model::Scalar,
+    ) -> Model::Scalar,
+{
+    /// Weight of a data point, applied to both residual and jacobian row.
+    fn weight(
+        &self,
+        i_x: usize,
+        x: GenericArray<Model::Scalar, <Model::Inputs as Conv>::TNum>,
+        y: Model::Scalar,
+    ) -> Model::Scalar {
+        let weight = (self.weights)(x, y);
+        self.sigma
+            .as_ref()
+            .map_or(weight, |sigma| weight / sigma[i_x])
+    }
+}
+
+impl<Model: MultiInputModel, Weights>
     LeastSquaresProblem<Model::Scalar, Dyn, <Model::ParamCount as Conv>::Nalg>
     for DynOptimizationProblem<'_, Model, Weights>
 where
@@ -20,7 +45,10 @@ where
         + Allocator<Dyn>
         + Allocator<Dyn, <Model::ParamCount as Conv>::Nalg>,
     Model::Scalar: nalgebra::Scalar + ComplexField + Copy,
-    Weights: Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+    Weights: Fn(
+        GenericArray<Model::Scalar, <Model::Inputs as Conv>::TNum>,
+        Model::Scalar,
+    ) -> Model::Scalar,
 {
     type ResidualStorage =
         <DefaultAllocator as Allocator<Dyn, nalgebra::U1>>::Buffer<Model::Scalar>;
@@ -47,23 +75,32 @@ where
     }
 
     fn residuals(&self) -> Option<OMatrix<Model::Scalar, Dyn, nalgebra::U1>> {
-        let mat: OMatrix<Model::Scalar, Dyn, nalgebra::U1> = self.x.zip_map(&self.y, |x, y| {
-            (self.weights)(x, y) * (self.model.evaluate(&x) - y)
-        });
+        let mat = OMatrix::<Model::Scalar, Dyn, nalgebra::U1>::from_fn_generic(
+            Dyn(self.x.nrows()),
+            nalgebra::U1,
+            |i_x, _| {
+                let x = input_row::<_, _, Model::Inputs>(&self.x, i_x);
+                let y = self.y[i_x];
+                let f_x = self.model.evaluate(&x);
+                self.weight(i_x, x, y) * (f_x - y)
+            },
+        );
         Some(mat)
     }
 
     fn jacobian(&self) -> Option<OMatrix<Model::Scalar, Dyn, <Model::ParamCount as Conv>::Nalg>> {
         let mut res =
             OMatrix::<Model::Scalar, Dyn, <Model::ParamCount as Conv>::Nalg>::zeros_generic(
-                Dyn(self.x.len()),
+                Dyn(self.x.nrows()),
                 Model::ParamCount::new_nalg(),
             );
 
-        for i_x in 0..self.x.len() {
+        for i_x in 0..self.x.nrows() {
+            let x = input_row::<_, _, Model::Inputs>(&self.x, i_x);
             let jacobian_x: GenericArray<_, <Model::ParamCount as Conv>::TNum> =
-                self.model.jacobian(&self.x[i_x]).into();
-            let arr = jacobian_x.map(|v| GenericArray::<_, typenum::U1>::from_array([v]));
+                self.model.jacobian(&x).into();
+            let weight = self.weight(i_x, x, self.y[i_x]);
+            let arr = jacobian_x.map(|v| GenericArray::<_, typenum::U1>::from_array([weight * v]));
             let mat = GenericMatrix::<Model::Scalar, nalgebra::U1, Model::ParamCount>::from_data(
                 GenericArrayStorage(arr),
             );
@@ -72,3 +109,272 @@ where
         Some(res)
     }
 }
+
+pub(crate) struct DynMultiOutputProblem<'data, Model: MultiOutputModel, Weights> {
+    pub model: &'data mut Model,
+    pub x: nalgebra::VectorView<'data, Model::Scalar, Dyn>,
+    pub y: nalgebra::MatrixView<'data, Model::Scalar, Dyn, <Model::Outputs as Conv>::Nalg>,
+    pub weights: Weights,
+}
+
+impl<Model: MultiOutputModel, Weights>
+    LeastSquaresProblem<Model::Scalar, Dyn, <Model::ParamCount as Conv>::Nalg>
+    for DynMultiOutputProblem<'_, Model, Weights>
+where
+    DefaultAllocator: Allocator<<Model::ParamCount as Conv>::Nalg, nalgebra::U1>
+        + Allocator<Dyn>
+        + Allocator<Dyn, <Model::ParamCount as Conv>::Nalg>,
+    Model::Scalar: nalgebra::Scalar + ComplexField + Copy,
+    Weights: Fn(
+        Model::Scalar,
+        GenericArray<Model::Scalar, <Model::Outputs as Conv>::TNum>,
+    ) -> GenericArray<Model::Scalar, <Model::Outputs as Conv>::TNum>,
+{
+    type ResidualStorage =
+        <DefaultAllocator as Allocator<Dyn, nalgebra::U1>>::Buffer<Model::Scalar>;
+
+    type ParameterStorage = GenericArrayStorage<Model::Scalar, Model::ParamCount, typenum::U1>;
+
+    type JacobianStorage = <DefaultAllocator as Allocator<
+        Dyn,
+        <Model::ParamCount as Conv>::Nalg,
+    >>::Buffer<Model::Scalar>;
+
+    fn set_params(&mut self, x: &GenericMatrix<Model::Scalar, Model::ParamCount, typenum::U1>) {
+        let slice: &[Model::Scalar] = x.data.as_ref();
+        let arr =
+            GenericArray::<Model::Scalar, <Model::ParamCount as Conv>::TNum>::from_slice(slice)
+                .clone();
+        self.model.set_params(arr);
+    }
+
+    fn params(&self) -> GenericMatrix<Model::Scalar, Model::ParamCount, typenum::U1> {
+        let pars: GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum> =
+            self.model.get_params().into();
+        GenericMatrix::from_data(GenericArrayStorage(GenericArray::from_array([pars])))
+    }
+
+    fn residuals(&self) -> Option<OMatrix<Model::Scalar, Dyn, nalgebra::U1>> {
+        let outputs = self.y.ncols();
+        let mut res = OMatrix::<Model::Scalar, Dyn, nalgebra::U1>::zeros_generic(
+            Dyn(self.x.len() * outputs),
+            nalgebra::U1,
+        );
+
+        for i_x in 0..self.x.len() {
+            let x = self.x[i_x];
+            let y = input_row::<_, _, Model::Outputs>(&self.y, i_x);
+            let f_x: GenericArray<_, <Model::Outputs as Conv>::TNum> =
+                self.model.evaluate(&x).into();
+            let w = (self.weights)(x, y.clone());
+            for i_out in 0..outputs {
+                res[i_x * outputs + i_out] = w[i_out] * (f_x[i_out] - y[i_out]);
+            }
+        }
+        Some(res)
+    }
+
+    fn jacobian(&self) -> Option<OMatrix<Model::Scalar, Dyn, <Model::ParamCount as Conv>::Nalg>> {
+        let outputs = self.y.ncols();
+        let mut res =
+            OMatrix::<Model::Scalar, Dyn, <Model::ParamCount as Conv>::Nalg>::zeros_generic(
+                Dyn(self.x.len() * outputs),
+                Model::ParamCount::new_nalg(),
+            );
+
+        for i_x in 0..self.x.len() {
+            let x = self.x[i_x];
+            let y = input_row::<_, _, Model::Outputs>(&self.y, i_x);
+            let w = (self.weights)(x, y);
+            let jacobian_x: GenericArray<_, <Model::Outputs as Conv>::TNum> =
+                self.model.jacobian(&x).into();
+            for (i_out, (row, w)) in jacobian_x.into_iter().zip(w).enumerate() {
+                for (i_param, v) in row.into_iter().enumerate() {
+                    res[(i_x * outputs + i_out, i_param)] = w * v;
+                }
+            }
+        }
+        Some(res)
+    }
+}
+
+type DataView<'data, Model, Points> =
+    nalgebra::VectorView<'data, <Model as FitModel>::Scalar, Points>;
+
+pub(crate) struct DynGlobalProblem<'data, Model: FitModel, Points: Dim> {
+    pub global: &'data mut GlobalFit<Model>,
+    pub data: Vec<(
+        DataView<'data, Model, Points>,
+        DataView<'data, Model, Points>,
+    )>,
+}
+
+impl<Model: FitModel, Points: Dim> DynGlobalProblem<'_, Model, Points> {
+    fn residual_count(&self) -> usize {
+        self.data.iter().map(|(x, _)| x.len()).sum()
+    }
+}
+
+impl<Model: FitModel, Points: Dim> LeastSquaresProblem<Model::Scalar, Dyn, Dyn>
+    for DynGlobalProblem<'_, Model, Points>
+where
+    DefaultAllocator: Allocator<Dyn> + Allocator<Dyn, Dyn>,
+    Model::Scalar: nalgebra::Scalar + ComplexField + Copy,
+{
+    type ResidualStorage =
+        <DefaultAllocator as Allocator<Dyn, nalgebra::U1>>::Buffer<Model::Scalar>;
+
+    type ParameterStorage =
+        <DefaultAllocator as Allocator<Dyn, nalgebra::U1>>::Buffer<Model::Scalar>;
+
+    type JacobianStorage = <DefaultAllocator as Allocator<Dyn, Dyn>>::Buffer<Model::Scalar>;
+
+    fn set_params(&mut self, x: &OMatrix<Model::Scalar, Dyn, nalgebra::U1>) {
+        self.global.set_params(x.as_slice());
+    }
+
+    fn params(&self) -> OMatrix<Model::Scalar, Dyn, nalgebra::U1> {
+        OMatrix::<Model::Scalar, Dyn, nalgebra::U1>::from_vec(self.global.get_params())
+    }
+
+    fn residuals(&self) -> Option<OMatrix<Model::Scalar, Dyn, nalgebra::U1>> {
+        let residuals = self
+            .global
+            .models()
+            .iter()
+            .zip(&self.data)
+            .flat_map(|(model, (x, y))| {
+                x.iter().zip(y.iter()).map(|(x, y)| model.evaluate(x) - *y)
+            });
+        Some(OMatrix::<Model::Scalar, Dyn, nalgebra::U1>::from_iterator(
+            self.residual_count(),
+            residuals,
+        ))
+    }
+
+    fn jacobian(&self) -> Option<OMatrix<Model::Scalar, Dyn, Dyn>> {
+        let mut res = OMatrix::<Model::Scalar, Dyn, Dyn>::zeros_generic(
+            Dyn(self.residual_count()),
+            Dyn(self.global.param_count()),
+        );
+
+        let mut i_res = 0;
+        for (dataset, (model, (x, _))) in self.global.models().iter().zip(&self.data).enumerate() {
+            for x in x.iter() {
+                let jacobian_x: GenericArray<_, <Model::ParamCount as Conv>::TNum> =
+                    model.jacobian(x).into();
+                for (param, v) in jacobian_x.into_iter().enumerate() {
+                    res[(i_res, self.global.param_index(dataset, param))] = v;
+                }
+                i_res += 1;
+            }
+        }
+        Some(res)
+    }
+}
+
+pub(crate) struct DynFrozenProblem<'data, Model: FitModel, Points: Dim, Weights> {
+    pub model: &'data mut Model,
+    pub frozen: GenericArray<bool, <Model::ParamCount as Conv>::TNum>,
+    pub x: DataView<'data, Model, Points>,
+    pub y: DataView<'data, Model, Points>,
+    pub sigma: Option<DataView<'data, Model, Points>>,
+    pub weights: Weights,
+}
+
+impl<Model: FitModel, Points: Dim, Weights> DynFrozenProblem<'_, Model, Points, Weights>
+where
+    Model::Scalar: ComplexField + Copy,
+    Weights: Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+{
+    fn free_count(&self) -> usize {
+        self.frozen.iter().filter(|frozen| !**frozen).count()
+    }
+
+    /// Weight of a data point, applied to both residual and jacobian row.
+    fn weight(&self, i_x: usize, x: Model::Scalar, y: Model::Scalar) -> Model::Scalar {
+        let weight = (self.weights)(x, y);
+        self.sigma
+            .as_ref()
+            .map_or(weight, |sigma| weight / sigma[i_x])
+    }
+}
+
+impl<Model: FitModel, Points: Dim, Weights> LeastSquaresProblem<Model::Scalar, Dyn, Dyn>
+    for DynFrozenProblem<'_, Model, Points, Weights>
+where
+    DefaultAllocator: Allocator<Dyn> + Allocator<Dyn, Dyn>,
+    Model::Scalar: nalgebra::Scalar + ComplexField + Copy,
+    Weights: Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+{
+    type ResidualStorage =
+        <DefaultAllocator as Allocator<Dyn, nalgebra::U1>>::Buffer<Model::Scalar>;
+
+    type ParameterStorage =
+        <DefaultAllocator as Allocator<Dyn, nalgebra::U1>>::Buffer<Model::Scalar>;
+
+    type JacobianStorage = <DefaultAllocator as Allocator<Dyn, Dyn>>::Buffer<Model::Scalar>;
+
+    fn set_params(&mut self, x: &OMatrix<Model::Scalar, Dyn, nalgebra::U1>) {
+        let mut free = x.iter().copied();
+        let params: GenericArray<_, <Model::ParamCount as Conv>::TNum> =
+            FitModel::get_params(self.model).into();
+        let params = params.zip(&self.frozen, |param, frozen| {
+            if *frozen {
+                param
+            } else {
+                free.next()
+                    .expect("Should have a value for each free parameter")
+            }
+        });
+        FitModel::set_params(self.model, params);
+    }
+
+    fn params(&self) -> OMatrix<Model::Scalar, Dyn, nalgebra::U1> {
+        let params: GenericArray<_, <Model::ParamCount as Conv>::TNum> =
+            FitModel::get_params(self.model).into();
+        OMatrix::<Model::Scalar, Dyn, nalgebra::U1>::from_iterator(
+            self.free_count(),
+            params
+                .into_iter()
+                .zip(&self.frozen)
+                .filter_map(|(param, frozen)| (!frozen).then_some(param)),
+        )
+    }
+
+    fn residuals(&self) -> Option<OMatrix<Model::Scalar, Dyn, nalgebra::U1>> {
+        let residuals = self
+            .x
+            .iter()
+            .zip(self.y.iter())
+            .enumerate()
+            .map(|(i_x, (&x, &y))| {
+                self.weight(i_x, x, y) * (FitModel::evaluate(self.model, &x) - y)
+            });
+        Some(OMatrix::<Model::Scalar, Dyn, nalgebra::U1>::from_iterator(
+            self.x.len(),
+            residuals,
+        ))
+    }
+
+    fn jacobian(&self) -> Option<OMatrix<Model::Scalar, Dyn, Dyn>> {
+        let mut res = OMatrix::<Model::Scalar, Dyn, Dyn>::zeros_generic(
+            Dyn(self.x.len()),
+            Dyn(self.free_count()),
+        );
+
+        for (i_x, (&x, &y)) in self.x.iter().zip(self.y.iter()).enumerate() {
+            let weight = self.weight(i_x, x, y);
+            let jacobian_x: GenericArray<_, <Model::ParamCount as Conv>::TNum> =
+                FitModel::jacobian(self.model, &x).into();
+            let free = jacobian_x
+                .into_iter()
+                .zip(&self.frozen)
+                .filter_map(|(v, frozen)| (!frozen).then_some(v));
+            for (i_param, v) in free.enumerate() {
+                res[(i_x, i_param)] = weight * v;
+            }
+        }
+        Some(res)
+    }
+}
@@ -1,15 +1,23 @@
 #![doc = include_str!("../README.md")]
 #![no_std] // <-- see that attr? No shit!
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::borrow::{Borrow, BorrowMut};
-use core::ops::Sub;
+use core::ops::{Mul, RangeInclusive, Sub};
 
 #[cfg(feature = "alloc")]
-use dyn_problem::DynOptimizationProblem;
-use generic_array::ArrayLength;
-use models::{FitModel, FitModelErrors, LevMarModel};
+use dyn_problem::{
+    DynFrozenProblem, DynGlobalProblem, DynMultiOutputProblem, DynOptimizationProblem,
+};
+use generic_array::{ArrayLength, sequence::GenericSequence};
+use models::{
+    FitModel, FitModelErrors, GuessParams, LevMarModel, LinearInParams, MultiInputModel,
+    MultiOutputModel,
+};
 
-use const_problem::ConstOptimizationProblem;
+use const_problem::{ConstMultiOutputProblem, ConstOptimizationProblem, Stacked};
 use generic_array_storage::{GenericArrayStorage, GenericMatrix, GenericMatrixFromExt};
 use levenberg_marquardt::LeastSquaresProblem;
 #[cfg(feature = "alloc")]
@@ -21,7 +29,7 @@ pub use simba::scalar::{ComplexField, RealField};
 use nalgebra::{Matrix, OMatrix};
 use typenum::Unsigned;
 
-use num_traits::{Float, NumCast};
+use num_traits::{Float, NumCast, One, Zero};
 
 /// Re-export from `typenum`
 pub type U<const N: usize> = typenum::generic_const_mappings::U<N>;
@@ -47,6 +55,19 @@ pub use levenberg_marquardt::TerminationReason;
 /// Fitting models
 pub mod models;
 
+/// Loss functions for robust fitting
+pub mod loss;
+
+/// Dual numbers for automatic differentiation
+pub mod dual;
+
+/// Special functions and distributions used by fit statistics
+pub mod stats;
+
+/// Random resampling for empirical parameter errors
+#[cfg(feature = "alloc")]
+pub mod resample;
+
 /// These are items required for macro to function
 #[doc(hidden)]
 pub mod __procmacro {
@@ -67,26 +88,56 @@ mod const_problem;
 #[doc(hidden)]
 mod dyn_problem;
 
+#[doc(hidden)]
+mod linear;
+pub use linear::{fit_linear, fit_linear_sigma};
+
+#[doc(hidden)]
+mod residuals;
+pub use residuals::{
+    FitResidualBound, ResidualReport, RunsTest, residual_report, residual_report_sigma,
+};
+
+#[doc(hidden)]
+mod robust;
+pub use robust::{RobustFit, fit_robust, fit_robust_sigma, fit_stat_robust, fit_stat_robust_sigma};
+
+#[doc(hidden)]
+mod odr;
+pub use odr::{OdrFit, fit_odr, fit_stat_odr};
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+mod profile;
+#[cfg(feature = "alloc")]
+pub use profile::{Profile, fit_profile, fit_profile_sigma};
+
+#[cfg(feature = "alloc")]
+pub use resample::fit::{Resampled, fit_bootstrap, fit_jackknife};
+
 #[doc = include_str!("../doc/as_matrix_view.md")]
 pub trait AsMatrixView {
     /// Type of the elements
     type Scalar;
     /// Type representing data points count.
     type Points: CreateProblem;
+    /// Type representing data columns count. This is [`nalgebra::U1`](type@nalgebra::U1) for anything but multi-column matrices.
+    type Columns: DimName;
 
     /// Creates data view from type.
     fn convert(
         &self,
-    ) -> MatrixView<'_, Self::Scalar, <Self::Points as CreateProblem>::Nalg, nalgebra::U1>;
+    ) -> MatrixView<'_, Self::Scalar, <Self::Points as CreateProblem>::Nalg, Self::Columns>;
 }
 
 impl<T: AsMatrixView + ?Sized> AsMatrixView for &T {
     type Scalar = T::Scalar;
     type Points = T::Points;
+    type Columns = T::Columns;
 
     fn convert(
         &self,
-    ) -> MatrixView<'_, Self::Scalar, <Self::Points as CreateProblem>::Nalg, nalgebra::U1> {
+    ) -> MatrixView<'_, Self::Scalar, <Self::Points as CreateProblem>::Nalg, Self::Columns> {
         <T as AsMatrixView>::convert(self)
     }
 }
@@ -94,10 +145,11 @@ impl<T: AsMatrixView + ?Sized> AsMatrixView for &T {
 impl<T: AsMatrixView + ?Sized> AsMatrixView for &mut T {
     type Scalar = T::Scalar;
     type Points = T::Points;
+    type Columns = T::Columns;
 
     fn convert(
         &self,
-    ) -> MatrixView<'_, Self::Scalar, <Self::Points as CreateProblem>::Nalg, nalgebra::U1> {
+    ) -> MatrixView<'_, Self::Scalar, <Self::Points as CreateProblem>::Nalg, Self::Columns> {
         <T as AsMatrixView>::convert(self)
     }
 }
@@ -108,6 +160,7 @@ where
 {
     type Scalar = Scalar;
     type Points = nalgebra::Const<N>;
+    type Columns = nalgebra::U1;
 
     fn convert(
         &self,
@@ -120,21 +173,29 @@ where
 impl<Scalar: nalgebra::Scalar> AsMatrixView for [Scalar] {
     type Scalar = Scalar;
     type Points = Dyn;
+    type Columns = nalgebra::U1;
 
     fn convert(&self) -> MatrixView<'_, Self::Scalar, Self::Points, nalgebra::U1> {
         MatrixView::<'_, Self::Scalar, Self::Points, nalgebra::U1>::from_slice(self, self.len())
     }
 }
 
-impl<Scalar, Points: Dim, S> AsMatrixView for Matrix<Scalar, Points, nalgebra::U1, S>
+impl<Scalar, Points: Dim, Columns: DimName, S> AsMatrixView for Matrix<Scalar, Points, Columns, S>
 where
     Points: CreateProblem<Nalg = Points>,
-    S: nalgebra::storage::RawStorage<Scalar, Points, RStride = nalgebra::U1, CStride = Points>,
+    S: nalgebra::storage::RawStorage<
+            Scalar,
+            Points,
+            Columns,
+            RStride = nalgebra::U1,
+            CStride = Points,
+        >,
 {
     type Scalar = Scalar;
     type Points = Points;
+    type Columns = Columns;
 
-    fn convert(&self) -> MatrixView<'_, Self::Scalar, Self::Points, nalgebra::U1> {
+    fn convert(&self) -> MatrixView<'_, Self::Scalar, Self::Points, Self::Columns> {
         self.as_view()
     }
 }
@@ -147,6 +208,7 @@ where
 {
     type Scalar = Scalar;
     type Points = <Points as Conv>::Nalg;
+    type Columns = nalgebra::U1;
 
     fn convert(
         &self,
@@ -163,11 +225,16 @@ pub trait CreateProblem {
     type Nalg: Dim;
 
     /// Creates a problem from data views and arbitrary model.
-    fn create<'d, Model: FitModel + 'd>(
-        x: MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>,
+    fn create<'d, Model: MultiInputModel + 'd>(
+        x: MatrixView<'d, Model::Scalar, Self::Nalg, <Model::Inputs as Conv>::Nalg>,
         y: MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>,
-        model: Model,
-        weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar + 'd,
+        sigma: Option<MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>>,
+        model: &'d mut Model,
+        weights: impl Fn(
+            GenericArray<Model::Scalar, <Model::Inputs as Conv>::TNum>,
+            Model::Scalar,
+        ) -> Model::Scalar
+        + 'd,
     ) -> impl LeastSquaresProblem<Model::Scalar, Self::Nalg, <Model::ParamCount as Conv>::Nalg> + 'd
     where
         Model::Scalar: ComplexField + Copy,
@@ -182,11 +249,16 @@ where
 {
     type Nalg = nalgebra::Const<POINTS>;
 
-    fn create<'d, Model: FitModel + 'd>(
-        x: MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>,
+    fn create<'d, Model: MultiInputModel + 'd>(
+        x: MatrixView<'d, Model::Scalar, Self::Nalg, <Model::Inputs as Conv>::Nalg>,
         y: MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>,
-        model: Model,
-        weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar + 'd,
+        sigma: Option<MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>>,
+        model: &'d mut Model,
+        weights: impl Fn(
+            GenericArray<Model::Scalar, <Model::Inputs as Conv>::TNum>,
+            Model::Scalar,
+        ) -> Model::Scalar
+        + 'd,
     ) -> impl LeastSquaresProblem<Model::Scalar, Self::Nalg, <Model::ParamCount as Conv>::Nalg> + 'd
     where
         Model::Scalar: ComplexField + Copy,
@@ -198,6 +270,7 @@ where
             model,
             x,
             y,
+            sigma,
             weights,
         }
     }
@@ -209,11 +282,16 @@ where
 {
     type Nalg = Self;
 
-    fn create<'d, Model: FitModel + 'd>(
-        x: MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>,
+    fn create<'d, Model: MultiInputModel + 'd>(
+        x: MatrixView<'d, Model::Scalar, Self::Nalg, <Model::Inputs as Conv>::Nalg>,
         y: MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>,
-        model: Model,
-        weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar + 'd,
+        sigma: Option<MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>>,
+        model: &'d mut Model,
+        weights: impl Fn(
+            GenericArray<Model::Scalar, <Model::Inputs as Conv>::TNum>,
+            Model::Scalar,
+        ) -> Model::Scalar
+        + 'd,
     ) -> impl LeastSquaresProblem<Model::Scalar, Self::Nalg, <Model::ParamCount as Conv>::Nalg> + 'd
     where
         Model::Scalar: ComplexField + Copy,
@@ -225,6 +303,7 @@ where
             model,
             x,
             y,
+            sigma,
             weights,
         }
     }
@@ -234,11 +313,16 @@ where
 impl CreateProblem for Dyn {
     type Nalg = Self;
 
-    fn create<'d, Model: FitModel + 'd>(
-        x: MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>,
+    fn create<'d, Model: MultiInputModel + 'd>(
+        x: MatrixView<'d, Model::Scalar, Self::Nalg, <Model::Inputs as Conv>::Nalg>,
         y: MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>,
-        model: Model,
-        weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar + 'd,
+        sigma: Option<MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>>,
+        model: &'d mut Model,
+        weights: impl Fn(
+            GenericArray<Model::Scalar, <Model::Inputs as Conv>::TNum>,
+            Model::Scalar,
+        ) -> Model::Scalar
+        + 'd,
     ) -> impl LeastSquaresProblem<Model::Scalar, Self::Nalg, <Model::ParamCount as Conv>::Nalg> + 'd
     where
         Model::Scalar: ComplexField + Copy,
@@ -247,6 +331,127 @@ impl CreateProblem for Dyn {
         DefaultAllocator: Allocator<Self::Nalg, <Model::ParamCount as Conv>::Nalg>,
     {
         DynOptimizationProblem::<'d, Model, _> {
+            model,
+            x,
+            y,
+            sigma,
+            weights,
+        }
+    }
+}
+
+/// Same as [`CreateProblem`], but for [`MultiOutputModel`]s: residuals for all of the outputs are stacked into a single vector, so there are `Outputs` times more of them than data points.
+pub trait CreateMultiOutputProblem<Outputs: Conv>: CreateProblem {
+    /// [`nalgebra`]-facing type, for stacked residuals count
+    type Stacked: Dim;
+
+    /// Creates a problem from data views and arbitrary multi-output model.
+    fn create_multi_output<'d, Model: MultiOutputModel<Outputs = Outputs> + 'd>(
+        x: MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>,
+        y: MatrixView<'d, Model::Scalar, Self::Nalg, Outputs::Nalg>,
+        model: &'d mut Model,
+        weights: impl Fn(
+            Model::Scalar,
+            GenericArray<Model::Scalar, Outputs::TNum>,
+        ) -> GenericArray<Model::Scalar, Outputs::TNum>
+        + 'd,
+    ) -> impl LeastSquaresProblem<Model::Scalar, Self::Stacked, <Model::ParamCount as Conv>::Nalg> + 'd
+    where
+        Model::Scalar: ComplexField + Copy,
+        DefaultAllocator: Allocator<<Model::ParamCount as Conv>::Nalg, nalgebra::U1>,
+        DefaultAllocator: Allocator<Self::Stacked, nalgebra::U1>,
+        DefaultAllocator: Allocator<Self::Stacked, <Model::ParamCount as Conv>::Nalg>;
+}
+
+impl<const POINTS: usize, Outputs: Conv> CreateMultiOutputProblem<Outputs>
+    for typenum::Const<POINTS>
+where
+    Self: Conv<Nalg = nalgebra::Const<POINTS>>,
+    <Self as Conv>::TNum: Mul<Outputs::TNum>,
+    Stacked<Self, Outputs>: Conv,
+{
+    type Stacked = <Stacked<Self, Outputs> as Conv>::Nalg;
+
+    fn create_multi_output<'d, Model: MultiOutputModel<Outputs = Outputs> + 'd>(
+        x: MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>,
+        y: MatrixView<'d, Model::Scalar, Self::Nalg, Outputs::Nalg>,
+        model: &'d mut Model,
+        weights: impl Fn(
+            Model::Scalar,
+            GenericArray<Model::Scalar, Outputs::TNum>,
+        ) -> GenericArray<Model::Scalar, Outputs::TNum>
+        + 'd,
+    ) -> impl LeastSquaresProblem<Model::Scalar, Self::Stacked, <Model::ParamCount as Conv>::Nalg> + 'd
+    where
+        Model::Scalar: ComplexField + Copy,
+        DefaultAllocator: Allocator<<Model::ParamCount as Conv>::Nalg, nalgebra::U1>,
+        DefaultAllocator: Allocator<Self::Stacked, nalgebra::U1>,
+        DefaultAllocator: Allocator<Self::Stacked, <Model::ParamCount as Conv>::Nalg>,
+    {
+        ConstMultiOutputProblem::<'d, Self, Model, _> {
+            model,
+            x,
+            y,
+            weights,
+        }
+    }
+}
+
+impl<const POINTS: usize, Outputs: Conv> CreateMultiOutputProblem<Outputs>
+    for nalgebra::Const<POINTS>
+where
+    Self: Conv<Nalg = Self>,
+    <Self as Conv>::TNum: Mul<Outputs::TNum>,
+    Stacked<Self, Outputs>: Conv,
+{
+    type Stacked = <Stacked<Self, Outputs> as Conv>::Nalg;
+
+    fn create_multi_output<'d, Model: MultiOutputModel<Outputs = Outputs> + 'd>(
+        x: MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>,
+        y: MatrixView<'d, Model::Scalar, Self::Nalg, Outputs::Nalg>,
+        model: &'d mut Model,
+        weights: impl Fn(
+            Model::Scalar,
+            GenericArray<Model::Scalar, Outputs::TNum>,
+        ) -> GenericArray<Model::Scalar, Outputs::TNum>
+        + 'd,
+    ) -> impl LeastSquaresProblem<Model::Scalar, Self::Stacked, <Model::ParamCount as Conv>::Nalg> + 'd
+    where
+        Model::Scalar: ComplexField + Copy,
+        DefaultAllocator: Allocator<<Model::ParamCount as Conv>::Nalg, nalgebra::U1>,
+        DefaultAllocator: Allocator<Self::Stacked, nalgebra::U1>,
+        DefaultAllocator: Allocator<Self::Stacked, <Model::ParamCount as Conv>::Nalg>,
+    {
+        ConstMultiOutputProblem::<'d, Self, Model, _> {
+            model,
+            x,
+            y,
+            weights,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Outputs: Conv> CreateMultiOutputProblem<Outputs> for Dyn {
+    type Stacked = Self;
+
+    fn create_multi_output<'d, Model: MultiOutputModel<Outputs = Outputs> + 'd>(
+        x: MatrixView<'d, Model::Scalar, Self::Nalg, nalgebra::U1>,
+        y: MatrixView<'d, Model::Scalar, Self::Nalg, Outputs::Nalg>,
+        model: &'d mut Model,
+        weights: impl Fn(
+            Model::Scalar,
+            GenericArray<Model::Scalar, Outputs::TNum>,
+        ) -> GenericArray<Model::Scalar, Outputs::TNum>
+        + 'd,
+    ) -> impl LeastSquaresProblem<Model::Scalar, Self::Stacked, <Model::ParamCount as Conv>::Nalg> + 'd
+    where
+        Model::Scalar: ComplexField + Copy,
+        DefaultAllocator: Allocator<<Model::ParamCount as Conv>::Nalg, nalgebra::U1>,
+        DefaultAllocator: Allocator<Self::Stacked, nalgebra::U1>,
+        DefaultAllocator: Allocator<Self::Stacked, <Model::ParamCount as Conv>::Nalg>,
+    {
+        DynMultiOutputProblem::<'d, Model, _> {
             model,
             x,
             y,
@@ -261,10 +466,21 @@ pub struct FitterUnit(());
 
 type DataPoints<Data> = <<Data as AsMatrixView>::Points as CreateProblem>::Nalg;
 
+/// Copies `i`-th row of the `x` data into an array suitable for [`MultiInputModel`] calls.
+pub(crate) fn input_row<Scalar: nalgebra::Scalar, Points: Dim, Inputs: Conv>(
+    x: &MatrixView<'_, Scalar, Points, Inputs::Nalg>,
+    i: usize,
+) -> GenericArray<Scalar, Inputs::TNum> {
+    GenericArray::generate(|j| x[(i, j)].clone())
+}
+
 /// A helper trait to simplify type bounds for a user. You probably should no see this.
 ///
 /// In case you do get a "type does not implement" type or error with this trait... I'm sorry.
-pub trait FitBound<Model: LevMarModel, X, Y = X> {
+pub trait FitBound<Model: MultiInputModel, X, Y = X>
+where
+    Model::Scalar: RealField,
+{
     #[doc(hidden)]
     type Points: Dim;
     #[doc(hidden)]
@@ -273,22 +489,26 @@ pub trait FitBound<Model: LevMarModel, X, Y = X> {
         model: &mut Model,
         x: impl Borrow<X>,
         y: impl Borrow<Y>,
-        weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+        sigma: Option<MatrixView<'_, Model::Scalar, Self::Points, nalgebra::U1>>,
+        weights: impl Fn(
+            GenericArray<Model::Scalar, <Model::Inputs as Conv>::TNum>,
+            Model::Scalar,
+        ) -> Model::Scalar,
     ) -> MinimizationReport<Model::Scalar>;
 }
 
 impl<Model, X, Y> FitBound<Model, X, Y> for FitterUnit
 where
-    Model: FitModel,
+    Model: MultiInputModel,
     Model::Scalar: RealField + Float,
     Model::ParamCount: Conv,
     <Model::ParamCount as Conv>::TNum: Sub<typenum::U1>,
     DefaultAllocator: Allocator<<Model::ParamCount as Conv>::Nalg>,
     DefaultAllocator: Allocator<DataPoints<X>>,
     DefaultAllocator: Allocator<DataPoints<X>, <Model::ParamCount as Conv>::Nalg>,
-    X: AsMatrixView<Scalar = Model::Scalar>,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = <Model::Inputs as Conv>::Nalg>,
     X::Points: CreateProblem,
-    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
     DataPoints<X>:
         DimMax<<Model::ParamCount as Conv>::Nalg> + DimMin<<Model::ParamCount as Conv>::Nalg>,
     <Model::ParamCount as Conv>::Nalg:
@@ -313,12 +533,16 @@ where
         model: &mut Model,
         x: impl Borrow<X>,
         y: impl Borrow<Y>,
-        weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+        sigma: Option<MatrixView<'_, Model::Scalar, Self::Points, nalgebra::U1>>,
+        weights: impl Fn(
+            GenericArray<Model::Scalar, <Model::Inputs as Conv>::TNum>,
+            Model::Scalar,
+        ) -> Model::Scalar,
     ) -> MinimizationReport<Model::Scalar> {
         let x = x.borrow().convert();
         let y = y.borrow().convert();
 
-        let problem = X::Points::create::<'_, &mut Model>(x, y, model.borrow_mut(), weights);
+        let problem = X::Points::create::<'_, Model>(x, y, sigma, model, weights);
         let (_, report) = LevenbergMarquardt::minimize::<
             <Model::ParamCount as Conv>::Nalg,
             DataPoints<X>,
@@ -328,6 +552,85 @@ where
     }
 }
 
+/// A helper trait to simplify type bounds for a user. You probably should no see this.
+///
+/// In case you do get a "type does not implement" type or error with this trait... I'm sorry.
+pub trait FitMultiOutputBound<Model: MultiOutputModel, X, Y = X>
+where
+    Model::Scalar: RealField,
+{
+    #[doc(hidden)]
+    fn fit_multi_output(
+        minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+        model: &mut Model,
+        x: impl Borrow<X>,
+        y: impl Borrow<Y>,
+        weights: impl Fn(
+            Model::Scalar,
+            GenericArray<Model::Scalar, <Model::Outputs as Conv>::TNum>,
+        ) -> GenericArray<Model::Scalar, <Model::Outputs as Conv>::TNum>,
+    ) -> MinimizationReport<Model::Scalar>;
+}
+
+type StackedPoints<Data, Outputs> =
+    <<Data as AsMatrixView>::Points as CreateMultiOutputProblem<Outputs>>::Stacked;
+
+impl<Model, X, Y> FitMultiOutputBound<Model, X, Y> for FitterUnit
+where
+    Model: MultiOutputModel,
+    Model::Scalar: RealField + Float,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    X::Points: CreateMultiOutputProblem<Model::Outputs>,
+    Y: AsMatrixView<
+            Scalar = Model::Scalar,
+            Points = X::Points,
+            Columns = <Model::Outputs as Conv>::Nalg,
+        >,
+    DefaultAllocator: Allocator<<Model::ParamCount as Conv>::Nalg>,
+    DefaultAllocator: Allocator<StackedPoints<X, Model::Outputs>>,
+    DefaultAllocator:
+        Allocator<StackedPoints<X, Model::Outputs>, <Model::ParamCount as Conv>::Nalg>,
+    StackedPoints<X, Model::Outputs>:
+        DimMax<<Model::ParamCount as Conv>::Nalg> + DimMin<<Model::ParamCount as Conv>::Nalg>,
+    DefaultAllocator: Reallocator<
+            Model::Scalar,
+            StackedPoints<X, Model::Outputs>,
+            <Model::ParamCount as Conv>::Nalg,
+            DimMaximum<StackedPoints<X, Model::Outputs>, <Model::ParamCount as Conv>::Nalg>,
+            <Model::ParamCount as Conv>::Nalg,
+        >,
+{
+    #[allow(
+        clippy::inline_always,
+        reason = "This function is used in a single place, and, in fact, wound not exist unless I wanted to extract the type bounds to a separate trait."
+    )]
+    #[inline(always)]
+    fn fit_multi_output(
+        minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+        model: &mut Model,
+        x: impl Borrow<X>,
+        y: impl Borrow<Y>,
+        weights: impl Fn(
+            Model::Scalar,
+            GenericArray<Model::Scalar, <Model::Outputs as Conv>::TNum>,
+        ) -> GenericArray<Model::Scalar, <Model::Outputs as Conv>::TNum>,
+    ) -> MinimizationReport<Model::Scalar> {
+        let x = x.borrow().convert();
+        let y = y.borrow().convert();
+
+        let problem = <X::Points as CreateMultiOutputProblem<Model::Outputs>>::create_multi_output::<
+            '_,
+            Model,
+        >(x, y, model, weights);
+        let (_, report) = LevenbergMarquardt::minimize::<
+            <Model::ParamCount as Conv>::Nalg,
+            StackedPoints<X, Model::Outputs>,
+            _,
+        >(minimizer.borrow(), problem);
+        report
+    }
+}
+
 /// A helper trait to simplify type bounds for a user. You probably should no see this.
 ///
 /// In case you do get a "type does not implement" type or error with this trait... I'm sorry.
@@ -335,24 +638,43 @@ pub trait FitErrBound<Model: FitModelErrors + LevMarModel, X, Y = X>:
     FitBound<Model, X, Y>
 {
     #[doc(hidden)]
+    #[allow(
+        clippy::too_many_arguments,
+        reason = "This is an internal function; arguments are just all the things fit statistics depend on."
+    )]
     fn produce_stat(
         model: impl Borrow<Model>,
         report: MinimizationReport<Model::Scalar>,
         x: X,
         y: Y,
+        sigma: Option<MatrixView<'_, Model::Scalar, Self::Points, nalgebra::U1>>,
+        absolute_sigma: bool,
+        weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+        frozen: &GenericArray<bool, <Model::ParamCount as Conv>::TNum>,
     ) -> FitStat<Model>;
+
+    #[doc(hidden)]
+    fn fit_linear(
+        model: &mut Model,
+        x: &X,
+        y: &Y,
+        sigma: Option<MatrixView<'_, Model::Scalar, Self::Points, nalgebra::U1>>,
+        weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+    ) -> MinimizationReport<Model::Scalar>
+    where
+        Model: LinearInParams;
 }
 
 impl<Model, X, Y> FitErrBound<Model, X, Y> for FitterUnit
 where
-    Self: FitBound<Model, X, Y>,
+    Self: FitBound<Model, X, Y, Points = DataPoints<X>>,
     Model: FitModelErrors,
     Model::Scalar: RealField + Float,
     Model::ParamCount: Conv,
     <Model::ParamCount as Conv>::TNum: Sub<typenum::U1>,
-    X: AsMatrixView<Scalar = Model::Scalar>,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
     X::Points: CreateProblem,
-    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
     DefaultAllocator: Allocator<<Model::ParamCount as Conv>::Nalg>
         + Allocator<DataPoints<X>>
         + Allocator<<Model::ParamCount as Conv>::Nalg, DataPoints<X>>
@@ -369,6 +691,10 @@ where
         report: MinimizationReport<Model::Scalar>,
         x: X,
         y: Y,
+        sigma: Option<MatrixView<'_, Model::Scalar, Self::Points, nalgebra::U1>>,
+        absolute_sigma: bool,
+        weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+        frozen: &GenericArray<bool, <Model::ParamCount as Conv>::TNum>,
     ) -> FitStat<Model>
     where
         Model: FitModelErrors,
@@ -376,55 +702,274 @@ where
         let model = model.borrow();
         let x = x.convert();
         let y = y.convert();
+        // same weight the point had during the fit
+        let weight = |i: usize, x: Model::Scalar, y: Model::Scalar| {
+            let weight = weights(x, y);
+            sigma.as_ref().map_or(weight, |sigma| weight / sigma[i])
+        };
 
         let points =
             <Model::Scalar as NumCast>::from::<usize>(x.len()).expect("Too many data points");
         let u_params = <<Model::ParamCount as Conv>::TNum as Unsigned>::USIZE;
+        let free_params = frozen.iter().filter(|frozen| !**frozen).count();
         let parameters =
-            <Model::Scalar as NumCast>::from::<usize>(u_params).expect("Too many parameters");
+            <Model::Scalar as NumCast>::from::<usize>(free_params).expect("Too many parameters");
         // source: https://scholarsarchive.byu.edu/cgi/viewcontent.cgi?article=3213&context=facpub
         // ch. 2 Estimating Uncertainties
-        let s_y_2 = if points > parameters {
-            x.zip_map(&y, |xi, yi| {
-                let f_x = model.evaluate(&xi);
-                let dev = yi - f_x;
-                dev * dev
-            })
-            .sum()
-                / (points - parameters)
+        let (chi2, rss, log_weights) = x.iter().zip(y.iter()).enumerate().fold(
+            (
+                Model::Scalar::zero(),
+                Model::Scalar::zero(),
+                Model::Scalar::zero(),
+            ),
+            |(chi2, rss, log_weights), (i, (&xi, &yi))| {
+                let weight = weight(i, xi, yi);
+                let residual = model.evaluate(&xi) - yi;
+                let dev = weight * residual;
+                (
+                    chi2 + dev * dev,
+                    rss + residual * residual,
+                    log_weights + Float::ln(Float::abs(weight)),
+                )
+            },
+        );
+        // gaussian log-likelihood; point `i` has standard deviation of `1 / weight` in units of data variance
+        let two_pi = Model::Scalar::two_pi();
+        let half = Model::Scalar::one() / (Model::Scalar::one() + Model::Scalar::one());
+        let log_likelihood = if absolute_sigma {
+            log_weights - half * (chi2 + points * Float::ln(two_pi))
+        } else {
+            // data variance is a maximum likelihood estimate here
+            log_weights - half * points * (Float::ln(two_pi * chi2 / points) + Model::Scalar::one())
+        };
+        let reduced_chi2 = if points > parameters {
+            chi2 / (points - parameters)
         } else {
             // WARN: add trace event here, or something
             Model::Scalar::nan()
         };
-        let s_y = Float::sqrt(s_y_2);
-        // thing below is (J * J^T)^-1
-        let jacobian = OMatrix::<Model::Scalar, <Model::ParamCount as Conv>::Nalg, DataPoints<X>>::from_iterator_generic(
-            Model::ParamCount::new_nalg(),
-            DataPoints::<X>::from_usize(x.len()),
-            x.iter().flat_map(|x| model.jacobian(x).into()),
-        );
-        let jj_t = jacobian.clone() * jacobian.transpose();
-        let covariance_matrix = jj_t.try_inverse().map(|jj_x| jj_x * s_y).map_or_else(
-            || {
-                // WARN: add trace here too, I guess
-                let col = core::iter::repeat_n(Model::Scalar::nan(), u_params).collect();
-                let arr = core::iter::repeat_n(col, u_params).collect();
-                GenericMatrix::from_data(GenericArrayStorage(arr))
-            },
-            Matrix::into_generic_matrix,
-        );
+        // thing below is (J * J^T)^-1, with J rows weighted the same way as residuals
+        let jacobian = weighted_jacobian(model, &x, |i| weight(i, x[i], y[i]));
+        let mut jj_t = jacobian.clone() * jacobian.transpose();
+        // frozen parameters are excluded from inversion, and have zero covariance
+        for (i, _) in frozen.iter().enumerate().filter(|(_, frozen)| **frozen) {
+            jj_t.row_mut(i).fill(Model::Scalar::zero());
+            jj_t.column_mut(i).fill(Model::Scalar::zero());
+            jj_t[(i, i)] = Model::Scalar::one();
+        }
+        let conditioning = Conditioning::of_normal_matrix(jj_t.clone(), frozen);
+        // with known absolute errors, there's no need to estimate data variance from residuals
+        let variance = if absolute_sigma {
+            Model::Scalar::one()
+        } else {
+            reduced_chi2
+        };
+        let covariance_matrix = jj_t
+            .try_inverse()
+            .map(|mut jj_x| {
+                for (i, _) in frozen.iter().enumerate().filter(|(_, frozen)| **frozen) {
+                    jj_x[(i, i)] = Model::Scalar::zero();
+                }
+                jj_x * variance
+            })
+            .map_or_else(
+                || {
+                    // singular normal matrix is reported by `conditioning`
+                    let col = core::iter::repeat_n(Model::Scalar::nan(), u_params).collect();
+                    let arr = core::iter::repeat_n(col, u_params).collect();
+                    GenericMatrix::from_data(GenericArrayStorage(arr))
+                },
+                Matrix::into_generic_matrix,
+            );
 
-        let param_errors = (0usize..u_params)
-            .map(|i| Float::sqrt(covariance_matrix[(i, i)]))
-            .collect();
-        let errors = Model::with_errors(param_errors);
+        let errors = model.with_covariance(&covariance_matrix);
 
         FitStat {
             report,
-            reduced_chi2: s_y_2,
+            chi2,
+            reduced_chi2,
+            dof: x.len().saturating_sub(free_params),
+            points: x.len(),
+            free_params,
+            rss,
+            log_likelihood,
+            absolute_sigma,
             errors,
             covariance_matrix,
+            conditioning,
+        }
+    }
+
+    #[allow(
+        clippy::inline_always,
+        reason = "Same as `produce_stat`, this function only exists to extract the type bounds."
+    )]
+    #[inline(always)]
+    fn fit_linear(
+        model: &mut Model,
+        x: &X,
+        y: &Y,
+        sigma: Option<MatrixView<'_, Model::Scalar, Self::Points, nalgebra::U1>>,
+        weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+    ) -> MinimizationReport<Model::Scalar>
+    where
+        Model: LinearInParams,
+    {
+        linear::solve(model, x.convert(), y.convert(), sigma, weights)
+    }
+}
+
+/// Model jacobian at data points `x`, with `i`-th column multiplied by `weight(i)`. Rows correspond to model parameters.
+fn weighted_jacobian<Model: LevMarModel, Points: Dim>(
+    model: &Model,
+    x: &MatrixView<'_, Model::Scalar, Points, nalgebra::U1>,
+    weight: impl Fn(usize) -> Model::Scalar,
+) -> OMatrix<Model::Scalar, <Model::ParamCount as Conv>::Nalg, Points>
+where
+    DefaultAllocator: Allocator<<Model::ParamCount as Conv>::Nalg, Points>,
+{
+    OMatrix::from_iterator_generic(
+        Model::ParamCount::new_nalg(),
+        x.shape_generic().0,
+        x.iter().enumerate().flat_map(|(i, xi)| {
+            let weight = weight(i);
+            let jacobian: GenericArray<_, <Model::ParamCount as Conv>::TNum> =
+                model.jacobian(xi).into();
+            jacobian.into_iter().map(move |v| weight * v)
+        }),
+    )
+}
+
+/// Maximal number of Jacobi rotation sweeps in [`symmetric_eigenvalues`].
+const JACOBI_SWEEPS: usize = 50;
+
+/// Diagonalizes symmetric `matrix` in place with cyclic Jacobi rotations, leaving eigenvalues on its diagonal.
+///
+/// Matrices here are parameter-sized, so simplicity is preferred over speed.
+fn symmetric_eigenvalues<Scalar, D, S>(matrix: &mut Matrix<Scalar, D, D, S>)
+where
+    Scalar: nalgebra::Scalar + Float,
+    D: Dim,
+    S: nalgebra::StorageMut<Scalar, D, D>,
+{
+    let n = matrix.nrows();
+    let two = Scalar::one() + Scalar::one();
+    for _ in 0..JACOBI_SWEEPS {
+        let (off, total) = (0..n).flat_map(|i| (0..n).map(move |j| (i, j))).fold(
+            (Scalar::zero(), Scalar::zero()),
+            |(off, total), (i, j)| {
+                let square = matrix[(i, j)] * matrix[(i, j)];
+                (if i == j { off } else { off + square }, total + square)
+            },
+        );
+        if off <= Scalar::epsilon() * Scalar::epsilon() * total {
+            break;
         }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let apq = matrix[(p, q)];
+                if apq.is_zero() {
+                    continue;
+                }
+                // rotation angle zeroing out (p, q) element; smaller root is taken for stability
+                let theta = (matrix[(q, q)] - matrix[(p, p)]) / (two * apq);
+                let t = theta.signum() / (theta.abs() + theta.hypot(Scalar::one()));
+                let c = Scalar::one() / t.hypot(Scalar::one());
+                let s = t * c;
+                for k in 0..n {
+                    let (akp, akq) = (matrix[(k, p)], matrix[(k, q)]);
+                    matrix[(k, p)] = c * akp - s * akq;
+                    matrix[(k, q)] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (matrix[(p, k)], matrix[(q, k)]);
+                    matrix[(p, k)] = c * apk - s * aqk;
+                    matrix[(q, k)] = s * apk + c * aqk;
+                }
+            }
+        }
+    }
+}
+
+/// Identifiability of fitted parameters, judged by the normal matrix $J^{T} J$ (see [`FitStat::conditioning`]).
+///
+/// Condition number here is the one of weighted jacobian $J$ over free parameters: ratio of its largest and smallest singular values. It depends on parameter scales, so poorly scaled model may appear ill-conditioned while still being perfectly fine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Conditioning<Scalar> {
+    /// Parameters are well-determined by the data.
+    Regular {
+        /// Jacobian condition number
+        condition_number: Scalar,
+    },
+    /// Condition number exceeds $\varepsilon^{-1/4}$, so inverting the normal matrix loses at least half of significant digits. Covariance matrix can't be trusted, and some parameters are likely nearly degenerate (see [`FitStat::degenerate_pairs`]).
+    IllConditioned {
+        /// Jacobian condition number
+        condition_number: Scalar,
+    },
+    /// Normal matrix is singular: some combination of parameters does not affect the model at all. Covariance matrix is meaningless (likely NaN).
+    Singular {
+        /// Numerical rank of jacobian, i.e. number of parameter combinations data actually determine
+        rank: usize,
+    },
+}
+
+impl<Scalar: Float> Conditioning<Scalar> {
+    /// Analyses normal matrix `jj_t` of the fit, ignoring `frozen` parameters.
+    fn of_normal_matrix<D, S, N>(
+        mut jj_t: Matrix<Scalar, D, D, S>,
+        frozen: &GenericArray<bool, N>,
+    ) -> Self
+    where
+        Scalar: nalgebra::Scalar,
+        D: Dim,
+        S: nalgebra::StorageMut<Scalar, D, D>,
+        N: ArrayLength,
+    {
+        symmetric_eigenvalues(&mut jj_t);
+        // frozen parameters are decoupled from the rest, so they occupy their own diagonal elements
+        let eigenvalues = || {
+            frozen
+                .iter()
+                .enumerate()
+                .filter(|(_, frozen)| !**frozen)
+                .map(|(i, _)| jj_t[(i, i)])
+        };
+        let free = eigenvalues().count();
+        let max = eigenvalues().fold(Scalar::zero(), Scalar::max);
+        let min = eigenvalues().fold(Scalar::infinity(), Scalar::min);
+        // same tolerance LAPACK uses for numerical rank
+        let tolerance = max
+            * Scalar::epsilon()
+            * <Scalar as NumCast>::from(free.max(1)).expect("Too many parameters");
+        let rank = eigenvalues().filter(|v| *v > tolerance).count();
+        if rank < free {
+            return Self::Singular { rank };
+        }
+        let condition_number = if free == 0 {
+            Scalar::one()
+        } else {
+            (max / min).sqrt()
+        };
+        if condition_number > Scalar::epsilon().sqrt().sqrt().recip() {
+            Self::IllConditioned { condition_number }
+        } else {
+            Self::Regular { condition_number }
+        }
+    }
+
+    /// Jacobian condition number. Infinite for [`Conditioning::Singular`].
+    pub fn condition_number(&self) -> Scalar {
+        match *self {
+            Self::Regular { condition_number } | Self::IllConditioned { condition_number } => {
+                condition_number
+            }
+            Self::Singular { .. } => Scalar::infinity(),
+        }
+    }
+
+    /// Checks if parameters are well-determined by the data.
+    pub fn is_regular(&self) -> bool {
+        matches!(self, Self::Regular { .. })
     }
 }
 
@@ -444,6 +989,10 @@ macro_rules! fit {
         $y:expr
         $(, weights = $wights:expr)?
         $(, minimizer = $minimizer:expr)?
+        $(, sigma = $sigma:expr)?
+        $(, sigma_x = $sigma_x:expr)?
+        $(, loss = $loss:expr)?
+        $(, guess = $guess:literal)?
     ) => { ... };
 }
 
@@ -451,31 +1000,108 @@ macro_rules! fit {
 #[cfg(not(doc))]
 #[doc = include_str!("../doc/fit_macro.md")]
 macro_rules! fit {
-    ($model:expr_2021, $x:expr_2021, $y:expr_2021 $(, $par_name:ident = $par_value:expr_2021) *) => {{
+    ($model:expr_2021, $x:expr_2021, $y:expr_2021 $(, $($params:tt)*)?) => {{
         use ::nacfahi::default_weights;
         #[allow(unused_mut)]
         let mut minimizer = &::nacfahi::LevenbergMarquardt::new();
         let model = $model;
         let x = $x;
         let y = $y;
-        ::nacfahi::fit!(@ $($par_name = $par_value),*; model = model, x = x, y = y, minimizer = minimizer, weights = default_weights)
+        ::nacfahi::fit!(@ model = model, x = x, y = y, sigma = [], sigma_x = [], loss = [], minimizer = minimizer, weights = default_weights; $($($params)*)?)
     }};
 
-    (@ minimizer = $new_minimizer:expr_2021 $(, $par_name:ident = $par_value:expr_2021) *; model = $model:ident, x = $x:ident, y = $y:ident, minimizer = $minimizer:ident, weights = $weights:ident) => {{
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; minimizer = $new_minimizer:expr_2021 $(, $($rest:tt)*)?) => {{
         use ::core::borrow::Borrow;
         let tmp = $new_minimizer;
         $minimizer = tmp.borrow();
-        ::nacfahi::fit!(@ $($par_name = $par_value),*; model = $model, x = $x, y = $y, minimizer = $minimizer, weights = $weights)
+        ::nacfahi::fit!(@ model = $model, x = $x, y = $y, sigma = [$($sigma)?], sigma_x = [$($sigma_x)?], loss = [$($loss)?], minimizer = $minimizer, weights = $weights; $($($rest)*)?)
+    }};
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; guess = true $(, $($rest:tt)*)?) => {{
+        ::nacfahi::guess_params(&mut *$model, &$x, &$y);
+        ::nacfahi::fit!(@ model = $model, x = $x, y = $y, sigma = [$($sigma)?], sigma_x = [$($sigma_x)?], loss = [$($loss)?], minimizer = $minimizer, weights = $weights; $($($rest)*)?)
     }};
 
-    (@ weights = $new_weights:expr_2021 $(, $par_name:ident = $par_value:expr_2021) *; model = $model:ident, x = $x:ident, y = $y:ident, minimizer = $minimizer:ident, weights = $weights:ident) => {{
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; guess = false $(, $($rest:tt)*)?) => {
+        ::nacfahi::fit!(@ model = $model, x = $x, y = $y, sigma = [$($sigma)?], sigma_x = [$($sigma_x)?], loss = [$($loss)?], minimizer = $minimizer, weights = $weights; $($($rest)*)?)
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; guess = $guess:expr_2021 $(, $($rest:tt)*)?) => {
+        ::core::compile_error!("`guess` only accepts literal `true` or `false`: model would have to implement `GuessParams` for any other value")
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; weights = $new_weights:expr_2021 $(, $($rest:tt)*)?) => {{
         let weights = $new_weights;
-        ::nacfahi::fit!(@ $($par_name = $par_value),*; model = $model, x = $x, y = $y, minimizer = $minimizer, weights = weights)
+        ::nacfahi::fit!(@ model = $model, x = $x, y = $y, sigma = [$($sigma)?], sigma_x = [$($sigma_x)?], loss = [$($loss)?], minimizer = $minimizer, weights = weights; $($($rest)*)?)
+    }};
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; sigma = $new_sigma:expr_2021 $(, $($rest:tt)*)?) => {{
+        let sigma = $new_sigma;
+        ::nacfahi::fit!(@ model = $model, x = $x, y = $y, sigma = [sigma], sigma_x = [$($sigma_x)?], loss = [$($loss)?], minimizer = $minimizer, weights = $weights; $($($rest)*)?)
+    }};
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; loss = $new_loss:expr_2021 $(, $($rest:tt)*)?) => {{
+        let loss = $new_loss;
+        ::nacfahi::fit!(@ model = $model, x = $x, y = $y, sigma = [$($sigma)?], sigma_x = [$($sigma_x)?], loss = [loss], minimizer = $minimizer, weights = $weights; $($($rest)*)?)
+    }};
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; sigma_x = $new_sigma_x:expr_2021 $(, $($rest:tt)*)?) => {{
+        let sigma_x = $new_sigma_x;
+        ::nacfahi::fit!(@ model = $model, x = $x, y = $y, sigma = [$($sigma)?], sigma_x = [sigma_x], loss = [$($loss)?], minimizer = $minimizer, weights = $weights; $($($rest)*)?)
     }};
 
-    (@; model = $model:ident, x = $x:ident, y = $y:ident, minimizer = $minimizer:ident, weights = $weights:ident) => {
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [], sigma_x = [], loss = [], minimizer = $minimizer:ident, weights = $weights:ident;) => {
         ::nacfahi::fit($model, $x, $y, $minimizer, $weights)
     };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$sigma:ident], sigma_x = [], loss = [], minimizer = $minimizer:ident, weights = $weights:ident;) => {
+        ::nacfahi::fit_sigma($model, $x, $y, $sigma, $minimizer, $weights)
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [], sigma_x = [], loss = [$loss:ident], minimizer = $minimizer:ident, weights = $weights:ident;) => {
+        ::nacfahi::fit_robust($model, $x, $y, $loss, $minimizer, $weights)
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$sigma:ident], sigma_x = [], loss = [$loss:ident], minimizer = $minimizer:ident, weights = $weights:ident;) => {
+        ::nacfahi::fit_robust_sigma($model, $x, $y, $sigma, $loss, $minimizer, $weights)
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$sigma:ident], sigma_x = [$sigma_x:ident], loss = [], minimizer = $minimizer:ident, weights = $weights:ident;) => {
+        ::nacfahi::fit_odr($model, $x, $y, $sigma_x, $sigma, $minimizer, $weights)
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [], sigma_x = [$sigma_x:ident], loss = [], minimizer = $minimizer:ident, weights = $weights:ident;) => {
+        ::core::compile_error!("`sigma_x` requires `sigma`: orthogonal distance regression needs errors of both `x` and `y`")
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$sigma_x:ident], loss = [$loss:ident], minimizer = $minimizer:ident, weights = $weights:ident;) => {
+        ::core::compile_error!("`sigma_x` can't be combined with `loss`: orthogonal distance regression does not support robust fitting")
+    };
+}
+
+/// Sets model parameters to rough estimates from data (see [`GuessParams`]). This is what `guess = true` option of [`macro@fit!`] does before fitting.
+///
+/// ```rust
+/// # use approx::assert_relative_eq;
+/// # use nacfahi::{models::basic::Linear, *};
+/// let x = [1.0, 2.0, 3.0, 4.0];
+/// let y = [3.0, 5.0, 7.0, 9.0];
+/// let mut line = Linear { a: 0.0, b: 0.0 };
+/// guess_params(&mut line, &x, &y);
+///
+/// assert_relative_eq!(line.a, 2.0);
+/// assert_relative_eq!(line.b, 1.0);
+/// ```
+pub fn guess_params<Model, X, Y>(model: &mut Model, x: &X, y: &Y)
+where
+    Model: GuessParams,
+    Model::Scalar: nalgebra::Scalar,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1> + ?Sized,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1> + ?Sized,
+{
+    let x = x.convert();
+    let y = y.convert();
+    model.guess(x.iter().cloned().zip(y.iter().cloned()));
 }
 
 /// Main interface point. For more convenient use (mostly - to omit some of the fields), you might want to look into [`macro@fit!`] macro.
@@ -493,7 +1119,230 @@ where
     Model: FitModel + LevMarModel,
     FitterUnit: FitBound<Model, X, Y>,
 {
-    FitterUnit::fit(minimizer, model, x, y, weights)
+    FitterUnit::fit(minimizer, model, x, y, None, scalar_weights(weights))
+}
+
+/// Same as [`function@fit`], but for models of several independent variables (see [`MultiInputModel`]).
+///
+/// Here, `x` is expected to be a matrix containing a column per model input, and `weights` receive a whole row of `x` data.
+///
+/// ```rust
+/// # use approx::assert_ulps_eq;
+/// # use nacfahi::{models::MultiInputModel, *};
+/// # use nalgebra::matrix;
+/// // z = a*x + b*y
+/// struct Plane {
+///     a: f64,
+///     b: f64,
+/// }
+///
+/// impl MultiInputModel for Plane {
+///     type Scalar = f64;
+///     type Inputs = U<2>;
+///     type ParamCount = U<2>;
+///
+///     fn evaluate(&self, x: &GenericArray<f64, U<2>>) -> f64 {
+///         self.a * x[0] + self.b * x[1]
+///     }
+///
+///     fn jacobian(&self, x: &GenericArray<f64, U<2>>) -> impl Into<GenericArray<f64, U<2>>> {
+///         [x[0], x[1]]
+///     }
+///
+///     fn set_params(&mut self, new_params: GenericArray<f64, U<2>>) {
+///         [self.a, self.b] = new_params.into_array();
+///     }
+///
+///     fn get_params(&self) -> impl Into<GenericArray<f64, U<2>>> {
+///         [self.a, self.b]
+///     }
+/// }
+///
+/// let x = matrix![
+///     1.0, 0.0;
+///     0.0, 1.0;
+///     1.0, 1.0;
+///     2.0, 1.0
+/// ];
+/// let z = matrix![3.0; -1.0; 2.0; 5.0];
+///
+/// let mut plane = Plane { a: 0.0, b: 0.0 };
+/// let report = fit_multi_input(&mut plane, x, z, LevenbergMarquardt::new(), |_, _| 1.0);
+///
+/// assert!(report.termination.was_successful());
+/// assert_ulps_eq!(plane.a, 3.0);
+/// assert_ulps_eq!(plane.b, -1.0);
+/// ```
+#[must_use = "Minimization report is really important to check if approximation happened at all"]
+pub fn fit_multi_input<Model, X, Y>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(
+        GenericArray<Model::Scalar, <Model::Inputs as Conv>::TNum>,
+        Model::Scalar,
+    ) -> Model::Scalar,
+) -> MinimizationReport<Model::Scalar>
+where
+    Model: MultiInputModel,
+    Model::Scalar: RealField,
+    FitterUnit: FitBound<Model, X, Y>,
+{
+    FitterUnit::fit(minimizer, model, x, y, None, weights)
+}
+
+/// Same as [`function@fit`], but for models with several outputs (see [`MultiOutputModel`]).
+///
+/// Here, `y` is expected to be a matrix containing a column per model output. Residuals of all the outputs are minimized simultaneously, so parameters are shared between them. `weights` receive `x` value and a whole row of `y` data, and should return a weight for each of the outputs.
+///
+/// ```rust
+/// # use approx::assert_ulps_eq;
+/// # use nacfahi::{models::MultiOutputModel, *};
+/// # use nalgebra::matrix;
+/// // two channels with shared slope: y1 = k*x + b1, y2 = k*x + b2
+/// struct Lines {
+///     k: f64,
+///     b1: f64,
+///     b2: f64,
+/// }
+///
+/// impl MultiOutputModel for Lines {
+///     type Scalar = f64;
+///     type Outputs = U<2>;
+///     type ParamCount = U<3>;
+///
+///     fn evaluate(&self, x: &f64) -> impl Into<GenericArray<f64, U<2>>> {
+///         [self.k * x + self.b1, self.k * x + self.b2]
+///     }
+///
+///     fn jacobian(&self, x: &f64) -> impl Into<GenericArray<GenericArray<f64, U<3>>, U<2>>> {
+///         [
+///             GenericArray::from_array([*x, 1.0, 0.0]),
+///             GenericArray::from_array([*x, 0.0, 1.0]),
+///         ]
+///     }
+///
+///     fn set_params(&mut self, new_params: GenericArray<f64, U<3>>) {
+///         [self.k, self.b1, self.b2] = new_params.into_array();
+///     }
+///
+///     fn get_params(&self) -> impl Into<GenericArray<f64, U<3>>> {
+///         [self.k, self.b1, self.b2]
+///     }
+/// }
+///
+/// let x = matrix![0.0; 1.0; 2.0; 3.0];
+/// let y = matrix![
+///     1.0, -1.0;
+///     3.0, 1.0;
+///     5.0, 3.0;
+///     7.0, 5.0
+/// ];
+///
+/// let mut lines = Lines { k: 0.0, b1: 0.0, b2: 0.0 };
+/// let report = fit_multi_output(&mut lines, x, y, LevenbergMarquardt::new(), |_, _| {
+///     GenericArray::from_array([1.0, 1.0])
+/// });
+///
+/// assert!(report.termination.was_successful());
+/// assert_ulps_eq!(lines.k, 2.0);
+/// assert_ulps_eq!(lines.b1, 1.0);
+/// assert_ulps_eq!(lines.b2, -1.0);
+/// ```
+#[must_use = "Minimization report is really important to check if approximation happened at all"]
+pub fn fit_multi_output<Model, X, Y>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(
+        Model::Scalar,
+        GenericArray<Model::Scalar, <Model::Outputs as Conv>::TNum>,
+    ) -> GenericArray<Model::Scalar, <Model::Outputs as Conv>::TNum>,
+) -> MinimizationReport<Model::Scalar>
+where
+    Model: MultiOutputModel,
+    Model::Scalar: RealField,
+    FitterUnit: FitMultiOutputBound<Model, X, Y>,
+{
+    FitterUnit::fit_multi_output(minimizer, model, x, y, weights)
+}
+
+/// Result of [`function@fit_global`].
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct GlobalFitResult<Model: LevMarModel> {
+    /// Report resulted from the fit
+    pub report: MinimizationReport<Model::Scalar>,
+    /// Fitted per-dataset models, in the order of datasets. Shared parameters are the same for all of them.
+    pub models: alloc::vec::Vec<Model>,
+}
+
+/// Fits several datasets simultaneously, each with its own instance of the same model. Parameters marked in `shared` are shared between all of the models, while the rest are fitted for each dataset independently.
+///
+/// Internally, this builds a single problem with residuals of all datasets concatenated (see [`GlobalFit`](models::utility::GlobalFit)). Initial values of shared parameters are taken from the first model.
+///
+/// ```rust
+/// # use approx::assert_ulps_eq;
+/// # use nacfahi::{models::basic::Linear, *};
+/// // lines with the same slope, but different offsets
+/// let x1 = [0.0, 1.0, 2.0];
+/// let y1 = [1.0, 3.0, 5.0];
+/// let x2 = [1.0, 2.0, 3.0];
+/// let y2 = [-1.0, 1.0, 3.0];
+///
+/// let line = Linear { a: 0.0, b: 0.0 };
+/// let result = fit_global(
+///     [(line, x1, y1), (line, x2, y2)],
+///     [true, false], // `a` is shared, `b` is not
+///     LevenbergMarquardt::new(),
+/// );
+///
+/// assert!(result.report.termination.was_successful());
+/// assert_ulps_eq!(result.models[0].a, 2.0);
+/// assert_ulps_eq!(result.models[1].a, 2.0);
+/// assert_ulps_eq!(result.models[0].b, 1.0);
+/// assert_ulps_eq!(result.models[1].b, -3.0);
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use = "Minimization report is really important to check if approximation happened at all"]
+pub fn fit_global<Model, X, Y>(
+    datasets: impl IntoIterator<Item = (Model, X, Y)>,
+    shared: impl Into<GenericArray<bool, <Model::ParamCount as Conv>::TNum>>,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+) -> GlobalFitResult<Model>
+where
+    Model: LevMarModel,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+{
+    let (models, data): (alloc::vec::Vec<_>, alloc::vec::Vec<_>) = datasets
+        .into_iter()
+        .map(|(model, x, y)| (model, (x, y)))
+        .unzip();
+    let mut global = models::utility::GlobalFit::new(models, shared);
+
+    let problem = DynGlobalProblem {
+        global: &mut global,
+        data: data
+            .iter()
+            .map(|(x, y)| (x.convert(), y.convert()))
+            .collect(),
+    };
+    let (_, report) = LevenbergMarquardt::minimize::<Dyn, Dyn, _>(minimizer.borrow(), problem);
+
+    GlobalFitResult {
+        report,
+        models: global.into_models(),
+    }
+}
+
+/// Adapts scalar weights function to a single-input [`MultiInputModel`] one.
+fn scalar_weights<Scalar: Copy>(
+    weights: impl Fn(Scalar, Scalar) -> Scalar,
+) -> impl Fn(GenericArray<Scalar, typenum::U1>, Scalar) -> Scalar {
+    move |x, y| weights(x[0], y)
 }
 
 /// Result of [`function@fit_stat`].
@@ -501,14 +1350,370 @@ where
 pub struct FitStat<Model: FitModelErrors + LevMarModel> {
     /// Report resulted from the fit
     pub report: MinimizationReport<Model::Scalar>,
-    /// $\chi^{2}/\test{dof}$ criteria. Should be about 1 for correct fit.
+    /// $\chi^{2}$ of the fit: sum of squared residuals, weighted the same way they were during the fit.
+    pub chi2: Model::Scalar,
+    /// $\chi^{2}/\text{dof}$ criteria. Should be about 1 for correct fit with correctly estimated data errors.
     pub reduced_chi2: Model::Scalar,
+    /// Degrees of freedom of the fit: number of data points minus number of fitted parameters.
+    pub dof: usize,
+    /// Number of data points
+    pub points: usize,
+    /// Number of fitted (not frozen) parameters
+    pub free_params: usize,
+    /// Residual sum of squares: same as `chi2`, but without any weights (or data errors).
+    pub rss: Model::Scalar,
+    /// Logarithm of gaussian likelihood of the fit.
+    ///
+    /// Without known data errors, data variance is estimated as $\chi^{2}/n$ (maximum likelihood estimate), and `weights` are treated as relative inverse errors.
+    pub log_likelihood: Model::Scalar,
+    /// If set, covariance matrix was computed from known data errors, instead of being scaled by $\chi^{2}/\text{dof}$.
+    pub absolute_sigma: bool,
     /// Type defined by model, containing parameter errors.
     ///
     /// This will usually be the model type itself, but there may be exceptions.
     pub errors: Model::OwnedModel,
     /// A parameter covariance matrix. If you don't know what this is, you can safely ignore it.
     pub covariance_matrix: GenericMatrix<Model::Scalar, Model::ParamCount, Model::ParamCount>,
+    /// Conditioning of the fit. In case parameters are not identifiable from the data, covariance matrix (and errors) should not be trusted.
+    pub conditioning: Conditioning<Model::Scalar>,
+}
+
+/// Fitted curve value and its uncertainty at a single `x` point (see [`FitStat::band`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Band<Scalar> {
+    /// Point the band is computed at
+    pub x: Scalar,
+    /// Fitted model value
+    pub value: Scalar,
+    /// Standard error of the fitted value (mean prediction), propagated from parameter covariance.
+    pub std_error: Scalar,
+    /// Standard error of a single new observation: fitted value error combined with data variance.
+    pub prediction_std_error: Scalar,
+    /// Confidence interval for the fitted value
+    pub confidence: RangeInclusive<Scalar>,
+    /// Prediction interval for a single new observation
+    pub prediction: RangeInclusive<Scalar>,
+}
+
+impl<Model: FitModelErrors + LevMarModel> FitStat<Model> {
+    /// Quantile of the parameter distribution, corresponding to two-sided `confidence` level.
+    ///
+    /// With known data errors that's a normal distribution, and Student's t-distribution with [`FitStat::dof`] degrees of freedom otherwise.
+    ///
+    /// ### Outputs NaN
+    ///
+    /// - If data errors are unknown, and there are no degrees of freedom
+    pub fn interval_quantile(&self, confidence: Model::Scalar) -> Model::Scalar {
+        let p = (Model::Scalar::one() + confidence) / (Model::Scalar::one() + Model::Scalar::one());
+        if self.absolute_sigma {
+            stats::normal_quantile(p)
+        } else if self.dof == 0 {
+            Model::Scalar::nan()
+        } else {
+            let dof = <Model::Scalar as NumCast>::from(self.dof).expect("Too many data points");
+            stats::student_t_quantile(p, dof)
+        }
+    }
+
+    /// Computes band at `x` with known `quantile` and data variance.
+    fn band_impl<M>(
+        &self,
+        model: &M,
+        x: Model::Scalar,
+        quantile: Model::Scalar,
+        variance: Model::Scalar,
+    ) -> Band<Model::Scalar>
+    where
+        M: FitModel<Scalar = Model::Scalar, ParamCount = Model::ParamCount>,
+    {
+        let value = FitModel::evaluate(model, &x);
+        let jacobian: GenericArray<_, <Model::ParamCount as Conv>::TNum> =
+            FitModel::jacobian(model, &x).into();
+        // J * C * J^T
+        let variance_mean =
+            jacobian
+                .iter()
+                .enumerate()
+                .fold(Model::Scalar::zero(), |acc, (i, &j_i)| {
+                    jacobian.iter().enumerate().fold(acc, |acc, (j, &j_j)| {
+                        acc + j_i * self.covariance_matrix[(i, j)] * j_j
+                    })
+                });
+        let std_error = Float::sqrt(variance_mean);
+        let prediction_std_error = Float::sqrt(variance_mean + variance);
+        Band {
+            x,
+            value,
+            std_error,
+            prediction_std_error,
+            confidence: (value - quantile * std_error)..=(value + quantile * std_error),
+            prediction: (value - quantile * prediction_std_error)
+                ..=(value + quantile * prediction_std_error),
+        }
+    }
+
+    /// Computes fitted value and its uncertainty at `x`, with intervals of two-sided `confidence` level (like `0.95`).
+    ///
+    /// Supply the fitted model itself (after the fit), since [`FitStat`] does not store it.
+    ///
+    /// Prediction interval is computed for a new observation with unit weight: its variance is estimated with $\chi^{2}/\text{dof}$. With known data errors, such an observation is assumed to have unit error; use [`FitStat::band_sigma`] to specify it.
+    ///
+    /// ```rust
+    /// # use nacfahi::{models::basic::Linear, *};
+    /// let x = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    /// let y = [1.1, 2.9, 5.2, 6.8, 9.1, 11.0];
+    ///
+    /// let mut line = Linear { a: 0.0, b: 0.0 };
+    /// let stat = fit_stat!(&mut line, x, y);
+    ///
+    /// let band = stat.band(&line, 2.5, 0.95);
+    /// assert!(band.confidence.contains(&band.value));
+    /// assert!(band.prediction.start() < band.confidence.start());
+    /// ```
+    pub fn band<M>(
+        &self,
+        model: &M,
+        x: Model::Scalar,
+        confidence: Model::Scalar,
+    ) -> Band<Model::Scalar>
+    where
+        M: FitModel<Scalar = Model::Scalar, ParamCount = Model::ParamCount>,
+    {
+        let variance = if self.absolute_sigma {
+            Model::Scalar::one()
+        } else {
+            self.reduced_chi2
+        };
+        self.band_impl(model, x, self.interval_quantile(confidence), variance)
+    }
+
+    /// Same as [`FitStat::band`], but a new observation has known error `sigma`, which is used for the prediction interval as-is.
+    pub fn band_sigma<M>(
+        &self,
+        model: &M,
+        x: Model::Scalar,
+        sigma: Model::Scalar,
+        confidence: Model::Scalar,
+    ) -> Band<Model::Scalar>
+    where
+        M: FitModel<Scalar = Model::Scalar, ParamCount = Model::ParamCount>,
+    {
+        self.band_impl(model, x, self.interval_quantile(confidence), sigma * sigma)
+    }
+
+    /// Same as [`FitStat::band`], but for a number of `x` points.
+    pub fn bands<'s, M>(
+        &'s self,
+        model: &'s M,
+        x: impl IntoIterator<Item = Model::Scalar> + 's,
+        confidence: Model::Scalar,
+    ) -> impl Iterator<Item = Band<Model::Scalar>> + 's
+    where
+        M: FitModel<Scalar = Model::Scalar, ParamCount = Model::ParamCount>,
+    {
+        let variance = if self.absolute_sigma {
+            Model::Scalar::one()
+        } else {
+            self.reduced_chi2
+        };
+        let quantile = self.interval_quantile(confidence);
+        x.into_iter()
+            .map(move |x| self.band_impl(model, x, quantile, variance))
+    }
+
+    /// Goodness-of-fit p-value: probability to get $\chi^{2}$ at least as big as this one, if the model is correct.
+    ///
+    /// This only makes sense with known data errors (see [`FitStat::absolute_sigma`]); otherwise $\chi^{2}$ is in units of unknown data variance. Very small p-values indicate that model does not describe the data (or that the errors are underestimated), and values close to one indicate overestimated errors.
+    ///
+    /// ### Outputs NaN
+    ///
+    /// - If there are no degrees of freedom
+    pub fn p_value(&self) -> Model::Scalar {
+        if self.dof == 0 {
+            return Model::Scalar::nan();
+        }
+        let dof = <Model::Scalar as NumCast>::from(self.dof).expect("Too many data points");
+        stats::chi2_sf(self.chi2, dof)
+    }
+
+    /// Confidence intervals of fitted parameters of two-sided `confidence` level (like `0.68`, `0.95` or `0.99`), in order of [`FitModel::get_params`].
+    ///
+    /// Supply the fitted model itself (after the fit), since [`FitStat`] does not store it. Intervals are symmetric, with half-widths being parameter standard errors multiplied by [`FitStat::interval_quantile`].
+    ///
+    /// Intervals are given for actual parameter values (see [`FitModelErrors::get_external_params`]), with standard errors propagated into them the same way [`FitModelErrors::with_covariance`] does (see [`FitModelErrors::external_derivatives`]). For a [`Bounded`](models::utility::Bounded) model, these are intervals of `inner` model parameters.
+    ///
+    /// ```rust
+    /// # use nacfahi::{models::basic::Linear, *};
+    /// let x = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    /// let y = [1.1, 2.9, 5.2, 6.8, 9.1, 11.0];
+    ///
+    /// let mut line = Linear { a: 0.0, b: 0.0 };
+    /// let stat = fit_stat!(&mut line, x, y);
+    ///
+    /// let [a_68, _] = stat.parameter_intervals(&line, 0.68).into_array();
+    /// let [a_99, _] = stat.parameter_intervals(&line, 0.99).into_array();
+    /// assert!(a_68.contains(&line.a));
+    /// assert!(a_99.start() < a_68.start() && a_68.end() < a_99.end());
+    /// ```
+    pub fn parameter_intervals<M>(
+        &self,
+        model: &M,
+        confidence: Model::Scalar,
+    ) -> GenericArray<RangeInclusive<Model::Scalar>, <Model::ParamCount as Conv>::TNum>
+    where
+        M: FitModelErrors<Scalar = Model::Scalar, ParamCount = Model::ParamCount>,
+    {
+        let quantile = self.interval_quantile(confidence);
+        let params = model.get_external_params();
+        let derivatives = model.external_derivatives();
+        GenericArray::generate(|i| {
+            let half_width =
+                quantile * Float::sqrt(self.covariance_matrix[(i, i)]) * Float::abs(derivatives[i]);
+            (params[i] - half_width)..=(params[i] + half_width)
+        })
+    }
+
+    /// Same as [`FitStat::errors`], but errors are half-widths of confidence intervals of two-sided `confidence` level, instead of standard errors.
+    ///
+    /// Supply the fitted model itself (after the fit), since [`FitStat`] does not store it.
+    pub fn confidence_errors<M>(&self, model: &M, confidence: Model::Scalar) -> M::OwnedModel
+    where
+        M: FitModelErrors<Scalar = Model::Scalar, ParamCount = Model::ParamCount>,
+    {
+        let quantile = self.interval_quantile(confidence);
+        let mut covariance = self.covariance_matrix.clone();
+        covariance.apply(|value| *value = *value * quantile * quantile);
+        model.with_covariance(&covariance)
+    }
+
+    /// Parameter correlation matrix: covariance normalized by parameter variances, so that elements lie within $[-1, 1]$.
+    ///
+    /// Rows and columns of frozen parameters (or any parameters with zero variance) are zero.
+    pub fn correlation_matrix(
+        &self,
+    ) -> GenericMatrix<Model::Scalar, Model::ParamCount, Model::ParamCount> {
+        let covariance = &self.covariance_matrix;
+        let mut correlation = covariance.clone();
+        let count = <<Model::ParamCount as Conv>::TNum as Unsigned>::USIZE;
+        for i in 0..count {
+            for j in 0..count {
+                let norm = Float::sqrt(covariance[(i, i)] * covariance[(j, j)]);
+                correlation[(i, j)] = if norm.is_zero() {
+                    Model::Scalar::zero()
+                } else {
+                    covariance[(i, j)] / norm
+                };
+            }
+        }
+        correlation
+    }
+
+    /// Pairs of parameters `(i, j)` (`i < j`, in order of [`FitModel::get_params`]) whose absolute correlation is at least `threshold`, along with the correlation itself.
+    ///
+    /// Such pairs are nearly degenerate: data can only determine some combination of them. Threshold of `0.99` or so is a reasonable choice.
+    pub fn degenerate_pairs(
+        &self,
+        threshold: Model::Scalar,
+    ) -> impl Iterator<Item = (usize, usize, Model::Scalar)> {
+        let count = <<Model::ParamCount as Conv>::TNum as Unsigned>::USIZE;
+        let correlation = self.correlation_matrix();
+        (0..count)
+            .flat_map(move |i| ((i + 1)..count).map(move |j| (i, j)))
+            .map(move |(i, j)| (i, j, correlation[(i, j)]))
+            .filter(move |(_, _, value)| Float::abs(*value) >= threshold)
+    }
+
+    /// Number of parameters likelihood was maximized over: fitted parameters, and data variance (unless data errors are known).
+    fn likelihood_params(&self) -> Model::Scalar {
+        let params = self.free_params + <usize as From<bool>>::from(!self.absolute_sigma);
+        <Model::Scalar as NumCast>::from(params).expect("Too many parameters")
+    }
+
+    /// [Akaike information criterion](https://en.wikipedia.org/wiki/Akaike_information_criterion) $2k - 2 \ln L$.
+    ///
+    /// When comparing fits of the same data, one with lower value is preferred. Here, $k$ counts fitted parameters, and data variance in case it was estimated from the fit (see [`FitStat::log_likelihood`]).
+    pub fn aic(&self) -> Model::Scalar {
+        let two = Model::Scalar::one() + Model::Scalar::one();
+        two * (self.likelihood_params() - self.log_likelihood)
+    }
+
+    /// [`FitStat::aic`] with correction for small number of data points: $\text{AIC} + \frac{2k(k+1)}{n-k-1}$.
+    ///
+    /// This should be preferred over plain AIC, unless there are lots of data points. Infinite if $n \le k + 1$.
+    pub fn aicc(&self) -> Model::Scalar {
+        let one = Model::Scalar::one();
+        let two = one + one;
+        let k = self.likelihood_params();
+        let n = <Model::Scalar as NumCast>::from(self.points).expect("Too many data points");
+        if n - k - one <= Model::Scalar::zero() {
+            return Model::Scalar::infinity();
+        }
+        self.aic() + two * k * (k + one) / (n - k - one)
+    }
+
+    /// [Bayesian information criterion](https://en.wikipedia.org/wiki/Bayesian_information_criterion) $k \ln n - 2 \ln L$.
+    ///
+    /// Penalizes extra parameters stronger than [`FitStat::aic`] does. Lower is better.
+    pub fn bic(&self) -> Model::Scalar {
+        let two = Model::Scalar::one() + Model::Scalar::one();
+        let n = <Model::Scalar as NumCast>::from(self.points).expect("Too many data points");
+        self.likelihood_params() * Float::ln(n) - two * self.log_likelihood
+    }
+
+    /// Performs [F-test](https://en.wikipedia.org/wiki/F-test#Regression_problems) of this fit against a fit of `complex` model to the same data.
+    ///
+    /// Models should be nested, i.e. this model should be a special case of the `complex` one (like a single gaussian is a special case of two gaussians). Small [`FTest::p_value`] means that extra parameters improve the fit significantly.
+    ///
+    /// ### Outputs NaN
+    ///
+    /// - If `complex` does not have fewer degrees of freedom, or has none
+    ///
+    /// ```rust
+    /// # use nacfahi::{models::basic::{Constant, Linear}, *};
+    /// let x = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    /// let y = [1.1, 1.4, 2.1, 2.4, 3.2, 3.4];
+    ///
+    /// let mut constant = Constant { c: 0.0 };
+    /// let simple = fit_stat!(&mut constant, x, y);
+    /// let mut line = Linear { a: 0.0, b: 0.0 };
+    /// let complex = fit_stat!(&mut line, x, y);
+    ///
+    /// let test = simple.f_test(&complex);
+    /// assert!(test.p_value < 0.001, "Slope is significant");
+    /// ```
+    pub fn f_test<Complex>(&self, complex: &FitStat<Complex>) -> FTest<Model::Scalar>
+    where
+        Complex: FitModelErrors + LevMarModel<RealScalar = Model::Scalar>,
+    {
+        let nan = FTest {
+            statistic: Model::Scalar::nan(),
+            dof: (0, complex.dof),
+            p_value: Model::Scalar::nan(),
+        };
+        if complex.dof >= self.dof || complex.dof == 0 {
+            return nan;
+        }
+        let extra = self.dof - complex.dof;
+        let dof1 = <Model::Scalar as NumCast>::from(extra).expect("Too many parameters");
+        let dof2 = <Model::Scalar as NumCast>::from(complex.dof).expect("Too many data points");
+        let statistic = ((self.chi2 - complex.chi2) / dof1) / (complex.chi2 / dof2);
+        FTest {
+            statistic,
+            dof: (extra, complex.dof),
+            p_value: stats::f_sf(statistic, dof1, dof2),
+        }
+    }
+}
+
+/// Result of nested models comparison (see [`FitStat::f_test`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FTest<Scalar> {
+    /// F statistic: relative $\chi^{2}$ improvement per extra parameter
+    pub statistic: Scalar,
+    /// Degrees of freedom of F-distribution: number of extra parameters, and degrees of freedom of the complex model fit.
+    pub dof: (usize, usize),
+    /// Probability of getting at least as big improvement by chance, given that simple model is correct.
+    pub p_value: Scalar,
 }
 
 #[macro_export]
@@ -521,6 +1726,10 @@ macro_rules! fit_stat {
         $y:expr
         $(, weights = $wights:expr)?
         $(, minimizer = $minimizer:expr)?
+        $(, sigma = $sigma:expr)?
+        $(, sigma_x = $sigma_x:expr)?
+        $(, loss = $loss:expr)?
+        $(, guess = $guess:literal)?
     ) => { ... };
 }
 
@@ -528,31 +1737,83 @@ macro_rules! fit_stat {
 #[cfg(not(doc))]
 #[doc = include_str!("../doc/fit_stat_macro.md")]
 macro_rules! fit_stat {
-    ($model:expr_2021, $x:expr_2021, $y:expr_2021 $(, $par_name:ident = $par_value:expr_2021) *) => {{
+    ($model:expr_2021, $x:expr_2021, $y:expr_2021 $(, $($params:tt)*)?) => {{
         use ::nacfahi::default_weights;
         #[allow(unused_mut)]
         let mut minimizer = &::nacfahi::LevenbergMarquardt::new();
         let model = $model;
         let x = $x;
         let y = $y;
-        ::nacfahi::fit_stat!(@ $($par_name = $par_value),*; model = model, x = x, y = y, minimizer = minimizer, weights = default_weights)
+        ::nacfahi::fit_stat!(@ model = model, x = x, y = y, sigma = [], sigma_x = [], loss = [], minimizer = minimizer, weights = default_weights; $($($params)*)?)
     }};
 
-    (@ minimizer = $new_minimizer:expr_2021 $(, $par_name:ident = $par_value:expr_2021) *; model = $model:ident, x = $x:ident, y = $y:ident, minimizer = $minimizer:ident, weights = $weights:ident) => {{
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; minimizer = $new_minimizer:expr_2021 $(, $($rest:tt)*)?) => {{
         use ::core::borrow::Borrow;
         let tmp = $new_minimizer;
         $minimizer = tmp.borrow();
-        ::nacfahi::fit_stat!(@ $($par_name = $par_value),*; model = $model, x = $x, y = $y, minimizer = $minimizer, weights = $weights)
+        ::nacfahi::fit_stat!(@ model = $model, x = $x, y = $y, sigma = [$($sigma)?], sigma_x = [$($sigma_x)?], loss = [$($loss)?], minimizer = $minimizer, weights = $weights; $($($rest)*)?)
     }};
 
-    (@ weights = $new_weights:expr_2021 $(, $par_name:ident = $par_value:expr_2021) *; model = $model:ident, x = $x:ident, y = $y:ident, minimizer = $minimizer:ident, weights = $weights:ident) => {{
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; guess = true $(, $($rest:tt)*)?) => {{
+        ::nacfahi::guess_params(&mut *$model, &$x, &$y);
+        ::nacfahi::fit_stat!(@ model = $model, x = $x, y = $y, sigma = [$($sigma)?], sigma_x = [$($sigma_x)?], loss = [$($loss)?], minimizer = $minimizer, weights = $weights; $($($rest)*)?)
+    }};
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; guess = false $(, $($rest:tt)*)?) => {
+        ::nacfahi::fit_stat!(@ model = $model, x = $x, y = $y, sigma = [$($sigma)?], sigma_x = [$($sigma_x)?], loss = [$($loss)?], minimizer = $minimizer, weights = $weights; $($($rest)*)?)
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; guess = $guess:expr_2021 $(, $($rest:tt)*)?) => {
+        ::core::compile_error!("`guess` only accepts literal `true` or `false`: model would have to implement `GuessParams` for any other value")
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; weights = $new_weights:expr_2021 $(, $($rest:tt)*)?) => {{
         let weights = $new_weights;
-        ::nacfahi::fit_stat!(@ $($par_name = $par_value),*; model = $model, x = $x, y = $y, minimizer = $minimizer, weights = weights)
+        ::nacfahi::fit_stat!(@ model = $model, x = $x, y = $y, sigma = [$($sigma)?], sigma_x = [$($sigma_x)?], loss = [$($loss)?], minimizer = $minimizer, weights = weights; $($($rest)*)?)
+    }};
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; sigma = $new_sigma:expr_2021 $(, $($rest:tt)*)?) => {{
+        let sigma = $new_sigma;
+        ::nacfahi::fit_stat!(@ model = $model, x = $x, y = $y, sigma = [sigma], sigma_x = [$($sigma_x)?], loss = [$($loss)?], minimizer = $minimizer, weights = $weights; $($($rest)*)?)
     }};
 
-    (@; model = $model:ident, x = $x:ident, y = $y:ident, minimizer = $minimizer:ident, weights = $weights:ident) => {
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; loss = $new_loss:expr_2021 $(, $($rest:tt)*)?) => {{
+        let loss = $new_loss;
+        ::nacfahi::fit_stat!(@ model = $model, x = $x, y = $y, sigma = [$($sigma)?], sigma_x = [$($sigma_x)?], loss = [loss], minimizer = $minimizer, weights = $weights; $($($rest)*)?)
+    }};
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$($sigma_x:ident)?], loss = [$($loss:ident)?], minimizer = $minimizer:ident, weights = $weights:ident; sigma_x = $new_sigma_x:expr_2021 $(, $($rest:tt)*)?) => {{
+        let sigma_x = $new_sigma_x;
+        ::nacfahi::fit_stat!(@ model = $model, x = $x, y = $y, sigma = [$($sigma)?], sigma_x = [sigma_x], loss = [$($loss)?], minimizer = $minimizer, weights = $weights; $($($rest)*)?)
+    }};
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [], sigma_x = [], loss = [], minimizer = $minimizer:ident, weights = $weights:ident;) => {
         ::nacfahi::fit_stat($model, $x, $y, $minimizer, $weights)
     };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$sigma:ident], sigma_x = [], loss = [], minimizer = $minimizer:ident, weights = $weights:ident;) => {
+        ::nacfahi::fit_stat_sigma($model, $x, $y, $sigma, $minimizer, $weights)
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [], sigma_x = [], loss = [$loss:ident], minimizer = $minimizer:ident, weights = $weights:ident;) => {
+        ::nacfahi::fit_stat_robust($model, $x, $y, $loss, $minimizer, $weights)
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$sigma:ident], sigma_x = [], loss = [$loss:ident], minimizer = $minimizer:ident, weights = $weights:ident;) => {
+        ::nacfahi::fit_stat_robust_sigma($model, $x, $y, $sigma, $loss, $minimizer, $weights)
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$sigma:ident], sigma_x = [$sigma_x:ident], loss = [], minimizer = $minimizer:ident, weights = $weights:ident;) => {
+        ::nacfahi::fit_stat_odr($model, $x, $y, $sigma_x, $sigma, $minimizer, $weights)
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [], sigma_x = [$sigma_x:ident], loss = [], minimizer = $minimizer:ident, weights = $weights:ident;) => {
+        ::core::compile_error!("`sigma_x` requires `sigma`: orthogonal distance regression needs errors of both `x` and `y`")
+    };
+
+    (@ model = $model:ident, x = $x:ident, y = $y:ident, sigma = [$($sigma:ident)?], sigma_x = [$sigma_x:ident], loss = [$loss:ident], minimizer = $minimizer:ident, weights = $weights:ident;) => {
+        ::core::compile_error!("`sigma_x` can't be combined with `loss`: orthogonal distance regression does not support robust fitting")
+    };
 }
 
 /// Same as [`function@fit`], but outputs a bunch of other stuff alongside [`MinimizationReport`].
@@ -579,6 +1840,179 @@ where
     Model: FitModelErrors + LevMarModel,
     FitterUnit: FitErrBound<Model, X, Y>,
 {
-    let report = FitterUnit::fit(minimizer, model.borrow_mut(), &x, &y, weights);
-    FitterUnit::produce_stat(model, report, x, y)
+    let report = FitterUnit::fit(
+        minimizer,
+        model.borrow_mut(),
+        &x,
+        &y,
+        None,
+        scalar_weights(&weights),
+    );
+    FitterUnit::produce_stat(
+        model,
+        report,
+        x,
+        y,
+        None,
+        false,
+        weights,
+        &GenericArray::generate(|_| false),
+    )
+}
+
+/// Same as [`function@fit`], but each data point has a known error (standard deviation) `sigma`, supplied in the same manner as `x` and `y`.
+///
+/// Residuals and jacobian rows are divided by corresponding `sigma` values, so that data points with bigger errors contribute less. `weights` are applied on top of that; use [`default_weights`] if you don't need those.
+///
+/// ```rust
+/// # use approx::assert_ulps_eq;
+/// # use nacfahi::{models::basic::Linear, *};
+/// let x = [0.0, 1.0, 2.0, 3.0];
+/// let y = [1.0, 3.0, 5.0, 100.0];
+/// // last point is basically garbage
+/// let sigma = [0.1, 0.1, 0.1, 1e12];
+///
+/// let mut line = Linear { a: 0.0, b: 0.0 };
+/// let report = fit_sigma(&mut line, x, y, sigma, LevenbergMarquardt::new(), default_weights);
+///
+/// assert!(report.termination.was_successful());
+/// assert_ulps_eq!(line.a, 2.0, epsilon = 1e-9);
+/// assert_ulps_eq!(line.b, 1.0, epsilon = 1e-9);
+/// ```
+#[must_use = "Minimization report is really important to check if approximation happened at all"]
+pub fn fit_sigma<Model, X, Y, Sigma>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    sigma: Sigma,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> MinimizationReport<Model::Scalar>
+where
+    Model: FitModel + LevMarModel,
+    FitterUnit: FitBound<Model, X, Y, Points = DataPoints<Sigma>>,
+    Sigma: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+{
+    FitterUnit::fit(
+        minimizer,
+        model,
+        x,
+        y,
+        Some(sigma.convert()),
+        scalar_weights(weights),
+    )
+}
+
+/// Same as [`function@fit_stat`], but each data point has a known error (standard deviation) `sigma` (see [`function@fit_sigma`]).
+///
+/// Since data errors are known, covariance matrix is computed from them directly, instead of being scaled by the $\chi^{2}/\text{dof}$ estimate. This also means that `reduced_chi2` is a meaningful goodness-of-fit criteria here.
+#[must_use = "Fit statistics are the only point to call this function specifically"]
+pub fn fit_stat_sigma<Model, X, Y, Sigma>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    sigma: Sigma,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> FitStat<Model>
+where
+    Model: FitModelErrors + LevMarModel,
+    FitterUnit: FitErrBound<Model, X, Y, Points = DataPoints<Sigma>>,
+    Sigma: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+{
+    let sigma = sigma.convert();
+    let report = FitterUnit::fit(
+        minimizer,
+        model.borrow_mut(),
+        &x,
+        &y,
+        Some(sigma),
+        scalar_weights(&weights),
+    );
+    FitterUnit::produce_stat(
+        model,
+        report,
+        x,
+        y,
+        Some(sigma),
+        true,
+        weights,
+        &GenericArray::generate(|_| false),
+    )
+}
+
+/// Same as [`function@fit`], but parameters marked in `frozen` are kept at their current values.
+///
+/// Unlike [`Fixed`](models::utility::Fixed) model, this does not change the type of the model, so set of frozen parameters can be decided at runtime. Frozen parameters are excluded from the problem entirely, so it has fewer dimensions.
+///
+/// ```rust
+/// # use approx::assert_ulps_eq;
+/// # use nacfahi::{models::basic::Linear, *};
+/// let x = [0.0, 1.0, 2.0, 3.0];
+/// let y = [1.0, 3.0, 5.0, 7.0];
+///
+/// // offset is known to be zero
+/// let mut line = Linear { a: 0.0, b: 0.0 };
+/// let report = fit_frozen(&mut line, x, y, [false, true], LevenbergMarquardt::new(), default_weights);
+///
+/// assert!(report.termination.was_successful());
+/// assert_ulps_eq!(line.b, 0.0);
+/// assert_ulps_eq!(line.a, 17.0 / 7.0);
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use = "Minimization report is really important to check if approximation happened at all"]
+pub fn fit_frozen<Model, X, Y>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    frozen: impl Into<GenericArray<bool, <Model::ParamCount as Conv>::TNum>>,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> MinimizationReport<Model::Scalar>
+where
+    Model: LevMarModel,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+{
+    let problem = DynFrozenProblem {
+        model,
+        frozen: frozen.into(),
+        x: x.convert(),
+        y: y.convert(),
+        sigma: None,
+        weights,
+    };
+    let (_, report) = LevenbergMarquardt::minimize::<Dyn, Dyn, _>(minimizer.borrow(), problem);
+    report
+}
+
+/// Same as [`function@fit_stat`], but parameters marked in `frozen` are kept at their current values (see [`function@fit_frozen`]).
+///
+/// Frozen parameters do not count towards degrees of freedom, and have zero rows and columns in the covariance matrix (and, consequently, zero errors).
+#[cfg(feature = "alloc")]
+#[must_use = "Fit statistics are the only point to call this function specifically"]
+pub fn fit_stat_frozen<Model, X, Y>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    frozen: impl Into<GenericArray<bool, <Model::ParamCount as Conv>::TNum>>,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> FitStat<Model>
+where
+    Model: FitModelErrors + LevMarModel,
+    FitterUnit: FitErrBound<Model, X, Y>,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+{
+    let frozen = frozen.into();
+    let report = fit_frozen(
+        model.borrow_mut(),
+        &x,
+        &y,
+        frozen.clone(),
+        minimizer,
+        &weights,
+    );
+    FitterUnit::produce_stat(model, report, x, y, None, false, weights, &frozen)
 }
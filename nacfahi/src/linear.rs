@@ -0,0 +1,216 @@
+use core::borrow::BorrowMut;
+
+use generic_array::sequence::GenericSequence;
+use generic_array_storage::Conv;
+use nalgebra::allocator::Allocator;
+use nalgebra::{DefaultAllocator, Dim, MatrixView, OMatrix, OVector};
+use num_traits::{Float, NumCast, One, Zero};
+use typenum::Unsigned;
+
+use crate::models::{FitModel, FitModelErrors, LevMarModel, LinearInParams};
+use crate::{
+    AsMatrixView, DataPoints, FitErrBound, FitStat, FitterUnit, GenericArray, MinimizationReport,
+    RealField, TerminationReason, weighted_jacobian,
+};
+
+/// Least squares solution $p$ of $J^{T} p = b$, where rows of `jacobian` $J$ correspond to parameters. Computed with Householder QR decomposition of $J^{T}$, which (unlike normal equations) does not square its condition number.
+///
+/// Parameters linearly dependent on the previous ones (so that data can't tell them apart) are set to zero.
+fn linear_least_squares<Scalar, Params, Points>(
+    mut jacobian: OMatrix<Scalar, Params, Points>,
+    mut rhs: OVector<Scalar, Points>,
+) -> OVector<Scalar, Params>
+where
+    Scalar: RealField + Float,
+    Params: Dim,
+    Points: Dim,
+    DefaultAllocator: Allocator<Params, Points> + Allocator<Params> + Allocator<Points>,
+{
+    let (params, points) = jacobian.shape_generic();
+    let (n, m) = (params.value(), points.value());
+    let zero = Scalar::zero();
+    let two = Scalar::one() + Scalar::one();
+    let largest = (0..n).fold(zero, |acc, k| Float::max(acc, jacobian.row(k).norm()));
+    let tolerance =
+        largest * Scalar::epsilon() * <Scalar as NumCast>::from(m).expect("Too many data points");
+
+    // row of R and diagonal element for each parameter, if it's independent
+    let mut pivots = OVector::<Option<(usize, Scalar)>, Params>::from_element_generic(
+        params,
+        nalgebra::U1,
+        None,
+    );
+    let mut row = 0;
+    for k in 0..n {
+        let norm =
+            Float::sqrt((row..m).fold(zero, |acc, i| acc + jacobian[(k, i)] * jacobian[(k, i)]));
+        if norm <= tolerance {
+            continue;
+        }
+        // reflection maps column k of J^T (below `row`) onto `diagonal * e_row`; its vector is stored in place
+        let diagonal = -Float::signum(jacobian[(k, row)]) * norm;
+        jacobian[(k, row)] -= diagonal;
+        let v_norm2 = (row..m).fold(zero, |acc, i| acc + jacobian[(k, i)] * jacobian[(k, i)]);
+        for j in (k + 1)..n {
+            let dot = (row..m).fold(zero, |acc, i| acc + jacobian[(k, i)] * jacobian[(j, i)]);
+            let factor = two * dot / v_norm2;
+            for i in row..m {
+                let v = jacobian[(k, i)];
+                jacobian[(j, i)] -= factor * v;
+            }
+        }
+        let dot = (row..m).fold(zero, |acc, i| acc + jacobian[(k, i)] * rhs[i]);
+        let factor = two * dot / v_norm2;
+        for i in row..m {
+            rhs[i] -= factor * jacobian[(k, i)];
+        }
+        pivots[k] = Some((row, diagonal));
+        row += 1;
+    }
+
+    // back substitution over R; dependent parameters are zero and don't contribute
+    let mut solution = OVector::from_element_generic(params, nalgebra::U1, zero);
+    for k in (0..n).rev() {
+        if let Some((row, diagonal)) = pivots[k] {
+            let rest = ((k + 1)..n).fold(zero, |acc, j| acc + jacobian[(j, row)] * solution[j]);
+            solution[k] = (rhs[row] - rest) / diagonal;
+        }
+    }
+    solution
+}
+
+/// Solves linear least squares problem for a model linear in its parameters. See [`function@fit_linear`].
+pub(crate) fn solve<Model, Points>(
+    model: &mut Model,
+    x: MatrixView<'_, Model::Scalar, Points, nalgebra::U1>,
+    y: MatrixView<'_, Model::Scalar, Points, nalgebra::U1>,
+    sigma: Option<MatrixView<'_, Model::Scalar, Points, nalgebra::U1>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> MinimizationReport<Model::Scalar>
+where
+    Model: LinearInParams + LevMarModel,
+    Model::Scalar: RealField + Float,
+    Points: Dim,
+    DefaultAllocator: Allocator<Points>
+        + Allocator<<Model::ParamCount as Conv>::Nalg>
+        + Allocator<<Model::ParamCount as Conv>::Nalg, Points>,
+{
+    let weight = |i: usize| {
+        let weight = weights(x[i], y[i]);
+        sigma.as_ref().map_or(weight, |sigma| weight / sigma[i])
+    };
+    let report = |termination, objective_function| MinimizationReport {
+        termination,
+        number_of_evaluations: 1,
+        objective_function,
+    };
+    if x.is_empty() {
+        return report(TerminationReason::NoResiduals, Model::Scalar::zero());
+    }
+    if <<Model::ParamCount as Conv>::TNum as Unsigned>::USIZE == 0 {
+        return report(TerminationReason::NoParameters, Model::Scalar::zero());
+    }
+
+    // residuals are linear in parameters, so weighted jacobian is exactly the design matrix
+    let jacobian = weighted_jacobian(&*model, &x, weight);
+    let rhs = OVector::from_fn_generic(x.shape_generic().0, nalgebra::U1, |i, _| weight(i) * y[i]);
+    let solution = linear_least_squares(jacobian, rhs);
+    if solution.iter().any(|p| !Float::is_finite(*p)) {
+        return report(
+            TerminationReason::Numerical("solution"),
+            Model::Scalar::nan(),
+        );
+    }
+    model.set_params(GenericArray::generate(|k| solution[k]));
+
+    let half = Model::Scalar::one() / (Model::Scalar::one() + Model::Scalar::one());
+    let chi2 =
+        x.iter()
+            .zip(y.iter())
+            .enumerate()
+            .fold(Model::Scalar::zero(), |acc, (i, (xi, &yi))| {
+                let dev = weight(i) * (FitModel::evaluate(&*model, xi) - yi);
+                acc + dev * dev
+            });
+    let termination = if chi2.is_zero() {
+        TerminationReason::ResidualsZero
+    } else {
+        TerminationReason::Converged {
+            ftol: true,
+            xtol: true,
+        }
+    };
+    report(termination, half * chi2)
+}
+
+/// Fits a model linear in its parameters (see [`LinearInParams`]) in one shot, by solving linear least squares problem directly. Result is exact up to rounding, and does not depend on starting parameters.
+///
+/// Outputs the same [`FitStat`] as [`function@crate::fit_stat`]. Reported termination is [`TerminationReason::ResidualsZero`] for a perfect fit, [`TerminationReason::Converged`] otherwise, and [`TerminationReason::Numerical`] if the data has non-finite values (in which case model is left intact).
+///
+/// Parameters data can't distinguish between are set to zero, and [`FitStat::conditioning`] reports them as singular.
+///
+/// ```rust
+/// # use approx::assert_relative_eq;
+/// # use nacfahi::{models::basic::Polynomial, *};
+/// let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+/// let y = x.map(|x: f64| 1.0 - 2.0 * x + 0.5 * x * x);
+///
+/// let mut parabola = Polynomial::<3, f64> { params: [0.0; 3] };
+/// let stat = fit_linear(&mut parabola, x, y, default_weights);
+///
+/// assert_eq!(stat.report.number_of_evaluations, 1);
+/// assert_relative_eq!(parabola.params[0], 1.0, epsilon = 1e-12);
+/// assert_relative_eq!(parabola.params[1], -2.0, epsilon = 1e-12);
+/// assert_relative_eq!(parabola.params[2], 0.5, epsilon = 1e-12);
+/// ```
+#[must_use = "Fit statistics are the only point to call this function specifically"]
+pub fn fit_linear<Model, X, Y>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> FitStat<Model>
+where
+    Model: LinearInParams + FitModelErrors + LevMarModel,
+    FitterUnit: FitErrBound<Model, X, Y>,
+{
+    let report = FitterUnit::fit_linear(model.borrow_mut(), &x, &y, None, &weights);
+    FitterUnit::produce_stat(
+        model,
+        report,
+        x,
+        y,
+        None,
+        false,
+        weights,
+        &GenericArray::generate(|_| false),
+    )
+}
+
+/// Same as [`function@fit_linear`], but each data point has a known error `sigma` (see [`function@crate::fit_stat_sigma`]).
+#[must_use = "Fit statistics are the only point to call this function specifically"]
+pub fn fit_linear_sigma<Model, X, Y, Sigma>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    sigma: Sigma,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> FitStat<Model>
+where
+    Model: LinearInParams + FitModelErrors + LevMarModel,
+    FitterUnit: FitErrBound<Model, X, Y, Points = DataPoints<Sigma>>,
+    Sigma: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+{
+    let sigma = sigma.convert();
+    let report = FitterUnit::fit_linear(model.borrow_mut(), &x, &y, Some(sigma), &weights);
+    FitterUnit::produce_stat(
+        model,
+        report,
+        x,
+        y,
+        Some(sigma),
+        true,
+        weights,
+        &GenericArray::generate(|_| false),
+    )
+}
@@ -0,0 +1,65 @@
+use num_traits::Float;
+
+/// Defines a loss function for robust fitting (see [`function@crate::fit_robust`]).
+///
+/// Robust fit is performed via iteratively reweighted least squares, so instead of the loss function $\rho(z)$ itself, the implementor only needs to provide corresponding point weight $w(z) = \rho'(z) / z$. Here, $z$ is a residual in the units of robust scale estimate.
+pub trait Loss<Scalar> {
+    /// Weight of the data point with (scaled) residual `z`.
+    ///
+    /// For the least squares, this is always 1. Robust losses typically assign smaller weights to bigger residuals.
+    fn weight(&self, z: Scalar) -> Scalar;
+}
+
+impl<Scalar, L: Loss<Scalar> + ?Sized> Loss<Scalar> for &L {
+    #[inline]
+    fn weight(&self, z: Scalar) -> Scalar {
+        (**self).weight(z)
+    }
+}
+
+/// Huber loss: quadratic for residuals smaller than `delta` (in the units of scale estimate), and linear for bigger ones.
+///
+/// A conventional choice is `delta = 1.345`, giving 95% efficiency for normally distributed data.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Huber<Scalar>(pub Scalar);
+
+impl<Scalar: Float> Loss<Scalar> for Huber<Scalar> {
+    #[inline]
+    fn weight(&self, z: Scalar) -> Scalar {
+        let z = z.abs();
+        if z <= self.0 {
+            Scalar::one()
+        } else {
+            self.0 / z
+        }
+    }
+}
+
+/// Cauchy (Lorentzian) loss: $\rho(z) = c^2 \ln(1 + (z/c)^2)$. Suppresses outliers way stronger than [`Huber`].
+///
+/// A conventional choice is `c = 2.385`, giving 95% efficiency for normally distributed data.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Cauchy<Scalar>(pub Scalar);
+
+impl<Scalar: Float> Loss<Scalar> for Cauchy<Scalar> {
+    #[inline]
+    fn weight(&self, z: Scalar) -> Scalar {
+        let z = z / self.0;
+        (Scalar::one() + z * z).recip()
+    }
+}
+
+/// Smooth approximation of absolute value loss: $\rho(z) = 2 c^2 (\sqrt{1 + (z/c)^2} - 1)$.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct SoftL1<Scalar>(pub Scalar);
+
+impl<Scalar: Float> Loss<Scalar> for SoftL1<Scalar> {
+    #[inline]
+    fn weight(&self, z: Scalar) -> Scalar {
+        let z = z / self.0;
+        (Scalar::one() + z * z).sqrt().recip()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,18 @@
+use super::{Cauchy, Huber, Loss, SoftL1};
+
+#[test]
+fn small_residuals_are_not_suppressed() {
+    let losses: [&dyn Loss<f64>; 3] = [&Huber(1.345), &Cauchy(2.385), &SoftL1(1.0)];
+    for loss in losses {
+        approx::assert_ulps_eq!(loss.weight(0.0), 1.0);
+        approx::assert_ulps_eq!(loss.weight(1e-3), loss.weight(-1e-3));
+        assert!(loss.weight(100.0) < 0.1, "Outliers should be suppressed");
+    }
+}
+
+#[test]
+fn huber() {
+    let loss = Huber(2.0);
+    approx::assert_ulps_eq!(loss.weight(1.5), 1.0);
+    approx::assert_ulps_eq!(loss.weight(-4.0), 0.5);
+}
@@ -1,6 +1,6 @@
-use crate::models::{FitModel, FitModelErrors, FitModelXDeriv};
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams, LinearInParams};
 use generic_array::GenericArray;
-use num_traits::{One, Zero};
+use num_traits::{Float, One, Zero};
 use typenum::U1;
 
 /// Model representing a constant, independent of `x`
@@ -53,11 +53,29 @@ where
     type OwnedModel = Self;
 
     #[inline]
-    fn with_errors(errors: GenericArray<Self::Scalar, Self::ParamCount>) -> Self::OwnedModel {
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
         let [c] = errors.into_array();
         Self { c }
     }
 }
 
+impl<Scalar: Float> GuessParams for Constant<Scalar> {
+    /// Mean of `y`.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let (count, sum) = points.fold((Scalar::zero(), Scalar::zero()), |(count, sum), (_, y)| {
+            (count + Scalar::one(), sum + y)
+        });
+        if !count.is_zero() {
+            self.c = sum / count;
+        }
+    }
+}
+
+impl<Scalar> LinearInParams for Constant<Scalar> where Self: FitModel {}
+
 #[cfg(test)]
 mod tests;
@@ -1,10 +1,12 @@
 use core::ops::Mul;
 
 use generic_array::GenericArray;
-use num_traits::{FloatConst, Pow};
+use num_traits::{Float, FloatConst, Pow};
 use typenum::U2;
 
-use crate::models::{FitModel, FitModelErrors, FitModelXDeriv};
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams};
+
+use super::linear::weighted_line;
 
 /// Exponent model $a \cdot \exp(b \cdot x )$
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -69,11 +71,36 @@ where
     type OwnedModel = Self;
 
     #[inline]
-    fn with_errors(errors: GenericArray<Self::Scalar, Self::ParamCount>) -> Self::OwnedModel {
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
         let [a, b] = errors.into_array();
         Exponent { a, b }
     }
 }
 
+impl<Scalar: Float> GuessParams for Exponent<Scalar>
+where
+    Self: FitModel<Scalar = Scalar>,
+{
+    /// Linear regression of $\ln |y|$, weighted by $y^2$ to compensate for logarithm amplifying noise of small values. Points of sign opposite to the majority of `y` are ignored.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let sign = points
+            .clone()
+            .fold(Scalar::zero(), |acc, (_, y)| acc + y)
+            .signum();
+        let logarithms = points.filter_map(move |(x, y)| {
+            let y = sign * y;
+            (y > Scalar::zero()).then(|| (x, y.ln(), y * y))
+        });
+        if let Some((b, ln_a)) = weighted_line(logarithms) {
+            self.a = sign * ln_a.exp();
+            self.b = b;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests;
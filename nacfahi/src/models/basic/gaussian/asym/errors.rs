@@ -51,7 +51,7 @@ impl<Scalar: Float + FloatConst> GaussianErrResolve<Scalar, true, false> for Gau
                 <<AsymmetricGenericGaussian<Scalar, true, false> as FitModel>::ParamCount as generic_array_storage::Conv>::TNum,
             >,
     ) -> Self::T {
-        let [a_err, s_err, x_c_err] = errors.into_array();
+        let [a_err, x_c_err, s_err] = errors.into_array();
         ErrorsSigma {
             a_err,
             s_err,
@@ -87,7 +87,7 @@ impl<Scalar: Float + FloatConst> GaussianErrResolve<Scalar, true, true> for Gaus
                 <<AsymmetricGenericGaussian<Scalar, true, true> as FitModel>::ParamCount as generic_array_storage::Conv>::TNum,
             >,
     ) -> Self::T {
-        let [a_err, s_err, x_c_err, s_p_err] = errors.into_array();
+        let [a_err, x_c_err, s_err, s_p_err] = errors.into_array();
         ErrorsFull {
             a_err,
             s_err,
@@ -11,10 +11,10 @@ use num_traits::{Float, FloatConst, One, Zero};
 
 use crate::{
     for_all_bool,
-    models::{FitModel, FitModelErrors, FitModelXDeriv},
+    models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams},
 };
 
-use super::common::{ff64, gaussian, gaussian_deriv_a, gaussian_deriv_s, gaussian_deriv_x_c};
+use super::common::{ff64, gaussian, gaussian_deriv_a, gaussian_deriv_s, gaussian_deriv_x_c, peak};
 
 #[doc(hidden)]
 mod errors;
@@ -272,6 +272,33 @@ where
     }
 }
 
+impl<Scalar: Float + FloatConst, const FIT_SIGMA: bool, const FIT_S_P: bool> GuessParams
+    for AsymmetricGenericGaussian<Scalar, FIT_SIGMA, FIT_S_P>
+where
+    Self: FitModel<Scalar = Scalar>,
+{
+    /// Moments of the highest (by absolute value) peak: center is the centroid of the points above half maximum, and $\sigma$ with $s_{p}$ are taken from half-maximum widths to the right and to the left of it.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let Some(peak) = peak(points, true) else {
+            return;
+        };
+        let hwhm_per_sigma = ff64::<Scalar>(f64::sqrt(2.0 * 2.0.ln()));
+        if FIT_S_P {
+            if FIT_SIGMA {
+                self.sigma = peak.right / hwhm_per_sigma;
+            }
+            self.s_p = peak.left / (hwhm_per_sigma * self.sigma) - Scalar::one();
+        } else if FIT_SIGMA {
+            let right = peak.right / hwhm_per_sigma;
+            let left = peak.left / (hwhm_per_sigma * (Scalar::one() + self.s_p));
+            self.sigma = (left + right) / ff64(2.0);
+        }
+        self.x_c = peak.x_c;
+        self.a = peak.height * Scalar::TAU().sqrt() * self.sigma;
+    }
+}
+
 pub type GaussianErr<Scalar, const FIT_SIGMA: bool = true, const FIT_S_P: bool = false> =
     <GaussianErrResolver as GaussianErrResolve<Scalar, FIT_SIGMA, FIT_S_P>>::T;
 
@@ -282,7 +309,7 @@ for_all_bool! {
         type OwnedModel = GaussianErr<Scalar, FIT_SIGMA, FIT_S_P>;
 
         #[inline]
-        fn with_errors(errors: GenericArray<Self::Scalar, Self::ParamCount>) -> Self::OwnedModel {
+        fn with_errors(&self, errors: GenericArray<Self::Scalar, Self::ParamCount>) -> Self::OwnedModel {
             <GaussianErrResolver as GaussianErrResolve<Scalar, FIT_SIGMA, FIT_S_P>>::create(errors)
         }
     }
@@ -90,6 +90,93 @@ pub(super) fn gaussian_deriv_a<Scalar: Float + FloatConst>(
     gaussian_sqrt(s) * gaussian_exp(x, x_c, s)
 }
 
+/// Rough description of the most prominent peak in the data.
+pub(in crate::models::basic) struct Peak<Scalar> {
+    /// Centroid of the points above half maximum
+    pub(in crate::models::basic) x_c: Scalar,
+    /// Peak value (may be negative for a dip)
+    pub(in crate::models::basic) height: Scalar,
+    /// Half width at half maximum, to the left of the peak
+    pub(in crate::models::basic) left: Scalar,
+    /// Half width at half maximum, to the right of the peak
+    pub(in crate::models::basic) right: Scalar,
+}
+
+/// Finds point of the largest absolute value, and the nearest points on each side where data falls below half of it.
+///
+/// With `per_side` set, each side is compared against half of its own maximum instead, which accounts for the jump of asymmetric gaussian at its center.
+///
+/// If there is no such point on one side, width of the other side is assumed. Returns [`None`] if there are no points, or they are all zero.
+pub(in crate::models::basic) fn peak<Scalar: Float>(
+    points: impl Iterator<Item = (Scalar, Scalar)> + Clone,
+    per_side: bool,
+) -> Option<Peak<Scalar>> {
+    let (x_peak, height) =
+        points
+            .clone()
+            .filter(|(_, y)| !y.is_nan())
+            .max_by(|(_, a), (_, b)| {
+                a.abs()
+                    .partial_cmp(&b.abs())
+                    .unwrap_or(core::cmp::Ordering::Equal)
+            })?;
+    if height.is_zero() {
+        return None;
+    }
+    let half = ff64::<Scalar>(0.5);
+    let below = |y: Scalar| y / height < half;
+    let half_width = |side: Scalar| {
+        // distance in the direction of the side
+        let distance = |x: Scalar| (x - x_peak) * side;
+        let threshold = if per_side {
+            let side_max = points
+                .clone()
+                .filter(|&(x, _)| distance(x) > Scalar::zero())
+                .fold(Scalar::zero(), |acc, (_, y)| acc.max(y / height));
+            half * side_max
+        } else {
+            half
+        };
+        points
+            .clone()
+            .filter(|&(x, y)| distance(x) > Scalar::zero() && y / height < threshold)
+            .map(|(x, _)| distance(x))
+            .reduce(Scalar::min)
+    };
+    let left = half_width(-Scalar::one());
+    let right = half_width(Scalar::one());
+    let (left, right) = match (left, right) {
+        (Some(left), Some(right)) => (left, right),
+        (Some(side), None) | (None, Some(side)) => (side, side),
+        (None, None) => {
+            // peak occupies all the data
+            let (min, max) = points.clone().fold(
+                (Scalar::infinity(), Scalar::neg_infinity()),
+                |(min, max), (x, _)| (min.min(x), max.max(x)),
+            );
+            let side = half * (max - min);
+            let side = if side > Scalar::zero() {
+                side
+            } else {
+                Scalar::one()
+            };
+            (side, side)
+        }
+    };
+    let (weight, moment) = points
+        .filter(|&(x, y)| x > x_peak - left && x < x_peak + right && !below(y))
+        .fold(
+            (Scalar::zero(), Scalar::zero()),
+            |(weight, moment), (x, y)| (weight + y, moment + y * x),
+        );
+    Some(Peak {
+        x_c: moment / weight,
+        height,
+        left,
+        right,
+    })
+}
+
 #[doc(hidden)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ErrorsNone<Scalar> {
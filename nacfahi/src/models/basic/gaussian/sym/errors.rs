@@ -51,7 +51,7 @@ impl<Scalar: Float + FloatConst> GaussianErrResolve<Scalar, true> for GaussianEr
                 <<SymmetricGenericGaussian<Scalar, true> as FitModel>::ParamCount as generic_array_storage::Conv>::TNum,
             >,
     ) -> Self::T {
-        let [a_err, s_err, x_c_err] = errors.into_array();
+        let [a_err, x_c_err, s_err] = errors.into_array();
         ErrorsSigma {
             a_err,
             s_err,
@@ -6,10 +6,10 @@ use num_traits::{Float, FloatConst};
 
 use crate::{
     for_all_bool,
-    models::{FitModel, FitModelErrors, FitModelXDeriv},
+    models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams},
 };
 
-use super::common::{ff64, gaussian, gaussian_deriv_a, gaussian_deriv_s, gaussian_deriv_x_c};
+use super::common::{ff64, gaussian, gaussian_deriv_a, gaussian_deriv_s, gaussian_deriv_x_c, peak};
 
 #[doc(hidden)]
 mod errors;
@@ -118,6 +118,26 @@ where
     }
 }
 
+impl<Scalar: Float + FloatConst, const FIT_SIGMA: bool> GuessParams
+    for SymmetricGenericGaussian<Scalar, FIT_SIGMA>
+where
+    Self: FitModel<Scalar = Scalar>,
+{
+    /// Moments of the highest (by absolute value) peak: center is the centroid of the points above half maximum, and $\sigma$ is taken from the half-maximum width.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let Some(peak) = peak(points, false) else {
+            return;
+        };
+        if FIT_SIGMA {
+            let hwhm = (peak.left + peak.right) / ff64(2.0);
+            self.sigma = hwhm / ff64(f64::sqrt(2.0 * 2.0.ln()));
+        }
+        self.x_c = peak.x_c;
+        self.a = peak.height * Scalar::TAU().sqrt() * self.sigma;
+    }
+}
+
 pub type GaussianErr<Scalar, const FIT_SIGMA: bool = true> =
     <GaussianErrResolver as GaussianErrResolve<Scalar, FIT_SIGMA>>::T;
 
@@ -128,7 +148,7 @@ for_all_bool! {
         type OwnedModel = GaussianErr<Scalar, FIT_SIGMA>;
 
         #[inline]
-        fn with_errors(errors: GenericArray<Self::Scalar, Self::ParamCount>) -> Self::OwnedModel {
+        fn with_errors(&self, errors: GenericArray<Self::Scalar, Self::ParamCount>) -> Self::OwnedModel {
             <GaussianErrResolver as GaussianErrResolve<Scalar, FIT_SIGMA>>::create(errors)
         }
     }
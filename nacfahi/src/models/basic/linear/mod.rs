@@ -1,11 +1,11 @@
 use core::ops::{Add, Mul};
 
 use generic_array::GenericArray;
-use num_traits::One;
+use num_traits::{Float, One};
 
 use typenum::U2;
 
-use crate::models::{FitModel, FitModelErrors, FitModelXDeriv};
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams, LinearInParams};
 
 /// Line model $a \cdot x + b$
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -70,11 +70,52 @@ where
     type OwnedModel = Linear<Scalar>;
 
     #[inline]
-    fn with_errors(errors: GenericArray<Self::Scalar, Self::ParamCount>) -> Self::OwnedModel {
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
         let [a, b] = errors.into_array();
         Linear { a, b }
     }
 }
 
+/// Weighted least squares line through `points` of `(x, y, weight)`, returned as `(slope, offset)`.
+///
+/// Slope is zero, if all the `x` are the same. Returns [`None`] if there are no points with non-zero weight.
+pub(super) fn weighted_line<Scalar: Float>(
+    points: impl Iterator<Item = (Scalar, Scalar, Scalar)> + Clone,
+) -> Option<(Scalar, Scalar)> {
+    let zero = Scalar::zero();
+    let (total, x_sum, y_sum) = points
+        .clone()
+        .fold((zero, zero, zero), |(total, x_sum, y_sum), (x, y, w)| {
+            (total + w, x_sum + w * x, y_sum + w * y)
+        });
+    if total.is_zero() {
+        return None;
+    }
+    let (x_mean, y_mean) = (x_sum / total, y_sum / total);
+    // centered sums are way more precise than raw ones
+    let (xx, xy) = points.fold((zero, zero), |(xx, xy), (x, y, w)| {
+        let dx = x - x_mean;
+        (xx + w * dx * dx, xy + w * dx * (y - y_mean))
+    });
+    let slope = if xx.is_zero() { zero } else { xy / xx };
+    Some((slope, y_mean - slope * x_mean))
+}
+
+impl<Scalar: Float> GuessParams for Linear<Scalar> {
+    /// Least squares line.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        if let Some((a, b)) = weighted_line(points.map(|(x, y)| (x, y, Scalar::one()))) {
+            self.a = a;
+            self.b = b;
+        }
+    }
+}
+
+impl<Scalar> LinearInParams for Linear<Scalar> where Self: FitModel {}
+
 #[cfg(test)]
 mod tests;
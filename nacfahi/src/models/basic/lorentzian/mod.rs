@@ -0,0 +1,110 @@
+use generic_array::GenericArray;
+use num_traits::{Float, FloatConst};
+use typenum::U3;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams};
+
+use super::gaussian::peak;
+
+/// Lorentzian (Cauchy) model $\dfrac{A}{\pi} \cdot \dfrac{\gamma}{(x - x_{c})^2 + \gamma^2}$
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lorentzian<Scalar> {
+    /// Area of the lorentzian, $A$
+    pub a: Scalar,
+    /// Center of the lorentzian, $x_{c}$
+    pub x_c: Scalar,
+    /// Half width at half maximum, $\gamma$
+    pub gamma: Scalar,
+}
+
+impl<Scalar: Float + FloatConst> Lorentzian<Scalar> {
+    /// [FWHM](https://en.wikipedia.org/wiki/Full_width_at_half_maximum), $2 \gamma$
+    #[inline]
+    pub fn fwhm(&self) -> Scalar {
+        (self.gamma + self.gamma).abs()
+    }
+
+    /// Distance from the center $d = x - x_{c}$, and $d^2 + \gamma^2$
+    #[inline]
+    fn denominator(&self, x: Scalar) -> (Scalar, Scalar) {
+        let d = x - self.x_c;
+        (d, d * d + self.gamma * self.gamma)
+    }
+}
+
+impl<Scalar: Float + FloatConst> FitModel for Lorentzian<Scalar> {
+    type Scalar = Scalar;
+    type ParamCount = U3;
+
+    #[inline]
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let (_, denominator) = self.denominator(x);
+        self.a * Scalar::FRAC_1_PI() * self.gamma / denominator
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        // y = A/pi * g / D, D = d^2 + g^2, d = x - x_c
+        // - derivative over A is g / (pi * D)
+        // - derivative over x_c is A/pi * 2 g d / D^2
+        // - derivative over g is A/pi * (d^2 - g^2) / D^2
+        let (d, denominator) = self.denominator(x);
+        let scale = self.a * Scalar::FRAC_1_PI() / (denominator * denominator);
+        [
+            Scalar::FRAC_1_PI() * self.gamma / denominator,
+            scale * (self.gamma + self.gamma) * d,
+            scale * (d * d - self.gamma * self.gamma),
+        ]
+    }
+
+    #[inline]
+    fn set_params(&mut self, new_params: GenericArray<Self::Scalar, Self::ParamCount>) {
+        [self.a, self.x_c, self.gamma] = new_params.into_array();
+    }
+
+    #[inline]
+    fn get_params(&self) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        [self.a, self.x_c, self.gamma]
+    }
+}
+
+impl<Scalar: Float + FloatConst> FitModelXDeriv for Lorentzian<Scalar> {
+    #[inline]
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let (d, denominator) = self.denominator(x);
+        -self.a * Scalar::FRAC_1_PI() * (self.gamma + self.gamma) * d / (denominator * denominator)
+    }
+}
+
+impl<Scalar: Float + FloatConst + 'static> FitModelErrors for Lorentzian<Scalar> {
+    type OwnedModel = Self;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
+        let [a, x_c, gamma] = errors.into_array();
+        Self { a, x_c, gamma }
+    }
+}
+
+impl<Scalar: Float + FloatConst> GuessParams for Lorentzian<Scalar> {
+    /// Same as for [`Gaussian`](super::Gaussian): center and width come from the highest (by absolute value) peak, and area is set to match its height.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let Some(peak) = peak(points, false) else {
+            return;
+        };
+        let two = Scalar::one() + Scalar::one();
+        self.x_c = peak.x_c;
+        self.gamma = (peak.left + peak.right) / two;
+        self.a = peak.height * Scalar::PI() * self.gamma;
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,16 @@
+crate::test_model_derivative!(
+    Lorentzian::<f64>,
+    Lorentzian {
+        a: -5.0,
+        x_c: 1.0,
+        gamma: 3.0,
+    },
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
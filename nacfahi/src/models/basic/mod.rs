@@ -7,10 +7,31 @@ mod gaussian;
 #[doc(hidden)]
 mod linear;
 #[doc(hidden)]
+mod lorentzian;
+#[doc(hidden)]
+mod orthogonal;
+#[doc(hidden)]
+mod oscillation;
+#[doc(hidden)]
 mod polynomial;
+#[doc(hidden)]
+mod power;
+#[doc(hidden)]
+mod pseudo_voigt;
+#[doc(hidden)]
+mod sigmoid;
+#[doc(hidden)]
+mod voigt;
 
 pub use constant::*;
 pub use exponent::*;
 pub use gaussian::*;
 pub use linear::*;
+pub use lorentzian::*;
+pub use orthogonal::*;
+pub use oscillation::*;
 pub use polynomial::*;
+pub use power::*;
+pub use pseudo_voigt::*;
+pub use sigmoid::*;
+pub use voigt::*;
@@ -0,0 +1,16 @@
+crate::test_model_derivative!(
+    Chebyshev::<5, f64>,
+    Chebyshev {
+        x_min: -1.0,
+        x_max: 6.0,
+        params: [-4.0, -15.0, -2.0, 0.7, -0.01]
+    },
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
@@ -0,0 +1,91 @@
+use num_traits::Float;
+
+/// Coefficients $(a_{n}, c_{n})$ of three-term recurrence $p_{n+1}(t) = a_{n} t p_{n}(t) - c_{n} p_{n-1}(t)$, with $p_{0} = 1$.
+pub(super) type Recurrence<Scalar> = fn(usize) -> (Scalar, Scalar);
+
+/// Linear map $t = \alpha x + \beta$ of `[x_min, x_max]` onto `[-1, 1]`, returned as $(\alpha, \beta)$.
+#[inline]
+pub(super) fn domain<Scalar: Float>(x_min: Scalar, x_max: Scalar) -> (Scalar, Scalar) {
+    let width = x_max - x_min;
+    (
+        (Scalar::one() + Scalar::one()) / width,
+        -(x_min + x_max) / width,
+    )
+}
+
+/// Values of the first `N` basis polynomials at `t`, along with their derivatives over `t`.
+pub(super) fn basis<Scalar: Float, const N: usize>(
+    recurrence: Recurrence<Scalar>,
+    t: Scalar,
+) -> ([Scalar; N], [Scalar; N]) {
+    let mut values = [Scalar::zero(); N];
+    let mut derivs = [Scalar::zero(); N];
+    let (mut value, mut prev_value) = (Scalar::one(), Scalar::zero());
+    let (mut deriv, mut prev_deriv) = (Scalar::zero(), Scalar::zero());
+    for (n, (value_n, deriv_n)) in values.iter_mut().zip(&mut derivs).enumerate() {
+        *value_n = value;
+        *deriv_n = deriv;
+        // p'_{n+1} = a_n (p_n + t p'_n) - c_n p'_{n-1}
+        let (a, c) = recurrence(n);
+        (value, prev_value) = (a * t * value - c * prev_value, value);
+        (deriv, prev_deriv) = (a * (prev_value + t * deriv) - c * prev_deriv, deriv);
+    }
+    (values, derivs)
+}
+
+/// Monomial coefficients (in terms of `x`, not `t`) of the first `N` basis polynomials, one polynomial per row.
+pub(super) fn monomials<Scalar: Float, const N: usize>(
+    recurrence: Recurrence<Scalar>,
+    (alpha, beta): (Scalar, Scalar),
+) -> [[Scalar; N]; N] {
+    let mut rows = [[Scalar::zero(); N]; N];
+    let mut current = [Scalar::zero(); N];
+    let mut previous = [Scalar::zero(); N];
+    if let Some(first) = current.first_mut() {
+        *first = Scalar::one();
+    }
+    for (n, row) in rows.iter_mut().enumerate() {
+        *row = current;
+        let (a, c) = recurrence(n);
+        // (alpha x + beta) * p_n shifts coefficients up by one power
+        let mut next = [Scalar::zero(); N];
+        let mut lower = Scalar::zero();
+        for ((next, &coefficient), &previous) in next.iter_mut().zip(&current).zip(&previous) {
+            *next = a * (alpha * lower + beta * coefficient) - c * previous;
+            lower = coefficient;
+        }
+        (current, previous) = (next, current);
+    }
+    rows
+}
+
+/// Expands sum of basis polynomials with coefficients `params` into monomial coefficients.
+pub(super) fn to_monomials<Scalar: Float, const N: usize>(
+    rows: &[[Scalar; N]; N],
+    params: &[Scalar; N],
+) -> [Scalar; N] {
+    let mut res = [Scalar::zero(); N];
+    for (row, &param) in rows.iter().zip(params) {
+        for (res, &value) in res.iter_mut().zip(row) {
+            *res = *res + param * value;
+        }
+    }
+    res
+}
+
+/// Finds coefficients of basis polynomials summing up to polynomial with monomial coefficients `coefficients`.
+///
+/// Since `n`-th basis polynomial has degree `n`, this is a back substitution starting from the highest power.
+pub(super) fn from_monomials<Scalar: Float, const N: usize>(
+    rows: &[[Scalar; N]; N],
+    mut coefficients: [Scalar; N],
+) -> [Scalar; N] {
+    let mut params = [Scalar::zero(); N];
+    for (n, (row, param)) in rows.iter().zip(&mut params).enumerate().rev() {
+        *param = coefficients[n] / row[n];
+        for (coefficient, &value) in coefficients.iter_mut().zip(row).take(n + 1) {
+            *coefficient = *coefficient - *param * value;
+        }
+    }
+    params
+}
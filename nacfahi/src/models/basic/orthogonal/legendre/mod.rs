@@ -0,0 +1,159 @@
+use generic_array::{GenericArray, IntoArrayLength};
+use generic_array_storage::Conv;
+use num_traits::Float;
+use typenum::Const;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams, LinearInParams};
+
+use super::{
+    super::Polynomial,
+    common::{basis, domain, from_monomials, monomials, to_monomials},
+};
+
+/// Recurrence $(n + 1) P_{n+1}(t) = (2 n + 1) t P_{n}(t) - n P_{n-1}(t)$.
+fn recurrence<Scalar: Float>(n: usize) -> (Scalar, Scalar) {
+    let n = Scalar::from(n).unwrap_or_else(Scalar::nan);
+    let next = n + Scalar::one();
+    ((n + next) / next, n / next)
+}
+
+/// [Legendre](https://en.wikipedia.org/wiki/Legendre_polynomials) series model, $\sum\limits_{i=0}^{order-1} a_{i} \cdot P_{i}(t)$, where $t = \dfrac{2 x - x_{min} - x_{max}}{x_{max} - x_{min}}$.
+///
+/// Same as [`Chebyshev`](super::Chebyshev), but basis polynomials are orthogonal with uniform weight over the domain, so coefficients of a least squares fit over evenly spread data are nearly independent. Domain is **not** a fit parameter, so the model is [linear in its parameters](LinearInParams).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Legendre<const ORDER: usize, Scalar> {
+    /// Start of the domain, mapped to $t = -1$
+    pub x_min: Scalar,
+    /// End of the domain, mapped to $t = 1$. Should differ from `x_min`.
+    pub x_max: Scalar,
+    /// Array of $a_{i}$
+    pub params: [Scalar; ORDER],
+}
+
+impl<const ORDER: usize, Scalar: Float> Legendre<ORDER, Scalar> {
+    /// Converts to the same polynomial in monomial basis.
+    ///
+    /// Note that monomial coefficients can be huge and cancel each other out, if domain is far from zero.
+    #[must_use]
+    pub fn to_polynomial(&self) -> Polynomial<ORDER, Scalar> {
+        let rows = monomials(recurrence, domain(self.x_min, self.x_max));
+        Polynomial {
+            params: to_monomials(&rows, &self.params),
+        }
+    }
+
+    /// Converts `polynomial` into Legendre basis over `[x_min, x_max]` domain.
+    #[must_use]
+    pub fn from_polynomial(
+        polynomial: &Polynomial<ORDER, Scalar>,
+        x_min: Scalar,
+        x_max: Scalar,
+    ) -> Self {
+        let rows = monomials(recurrence, domain(x_min, x_max));
+        Self {
+            x_min,
+            x_max,
+            params: from_monomials(&rows, polynomial.params),
+        }
+    }
+
+    /// Values of basis polynomials at `x` and their derivatives over `x`.
+    #[inline]
+    fn basis(&self, x: Scalar) -> ([Scalar; ORDER], [Scalar; ORDER]) {
+        let (alpha, beta) = domain(self.x_min, self.x_max);
+        let (values, derivs) = basis(recurrence, alpha * x + beta);
+        (values, derivs.map(|deriv| deriv * alpha))
+    }
+}
+
+impl<const ORDER: usize, Scalar: Float> FitModel for Legendre<ORDER, Scalar>
+where
+    Const<ORDER>: IntoArrayLength,
+{
+    type Scalar = Scalar;
+    type ParamCount = Const<ORDER>;
+
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let (values, _) = self.basis(x);
+        values
+            .iter()
+            .zip(&self.params)
+            .fold(Scalar::zero(), |acc, (&value, &param)| acc + value * param)
+    }
+
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>> {
+        self.basis(x).0
+    }
+
+    fn set_params(
+        &mut self,
+        new_params: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) {
+        self.params = new_params.into_array();
+    }
+
+    fn get_params(
+        &self,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>> {
+        self.params
+    }
+}
+
+impl<const ORDER: usize, Scalar: Float> FitModelXDeriv for Legendre<ORDER, Scalar>
+where
+    Self: FitModel<Scalar = Scalar, ParamCount = Const<ORDER>>,
+{
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let (_, derivs) = self.basis(x);
+        derivs
+            .iter()
+            .zip(&self.params)
+            .fold(Scalar::zero(), |acc, (&deriv, &param)| acc + deriv * param)
+    }
+}
+
+impl<const ORDER: usize, Scalar: Float + 'static> FitModelErrors for Legendre<ORDER, Scalar>
+where
+    Const<ORDER>: IntoArrayLength,
+    Self: FitModel<Scalar = Scalar, ParamCount = Const<ORDER>>,
+{
+    type OwnedModel = Legendre<ORDER, Scalar>;
+
+    /// Domain is not a parameter, so its errors are reported as `0`.
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) -> Self::OwnedModel {
+        Legendre {
+            x_min: Scalar::zero(),
+            x_max: Scalar::zero(),
+            params: errors.into_array(),
+        }
+    }
+}
+
+impl<const ORDER: usize, Scalar: Float> GuessParams for Legendre<ORDER, Scalar>
+where
+    Const<ORDER>: IntoArrayLength,
+    Self: FitModel<Scalar = Scalar>,
+{
+    /// Least squares [`Polynomial`], converted into the current domain, which is left as is.
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        if points.clone().next().is_none() {
+            return;
+        }
+        let mut polynomial = Polynomial {
+            params: [Scalar::zero(); ORDER],
+        };
+        polynomial.guess(points);
+        *self = Self::from_polynomial(&polynomial, self.x_min, self.x_max);
+    }
+}
+
+impl<const ORDER: usize, Scalar> LinearInParams for Legendre<ORDER, Scalar> where Self: FitModel {}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,9 @@
+#[doc(hidden)]
+mod chebyshev;
+#[doc(hidden)]
+mod common;
+#[doc(hidden)]
+mod legendre;
+
+pub use chebyshev::*;
+pub use legendre::*;
@@ -0,0 +1,117 @@
+use num_traits::{Float, FloatConst};
+
+use super::super::linear::weighted_line;
+
+/// Values `y` at points immediately before and after `x` in data, if there are any.
+fn neighbours<Scalar: Float>(
+    points: impl Iterator<Item = (Scalar, Scalar)>,
+    x: Scalar,
+) -> (Option<Scalar>, Option<Scalar>) {
+    let (before, after) = points.fold((None, None), |(before, after), point| {
+        if point.0 < x && before.is_none_or(|(before_x, _)| point.0 > before_x) {
+            (Some(point), after)
+        } else if point.0 > x && after.is_none_or(|(after_x, _)| point.0 < after_x) {
+            (before, Some(point))
+        } else {
+            (before, after)
+        }
+    });
+    (before.map(|(_, y)| y), after.map(|(_, y)| y))
+}
+
+/// Decay rate of oscillations in data: slope of a line through logarithms of `|y|` peaks.
+///
+/// Zero, if there are less than two peaks.
+pub(super) fn decay_rate<Scalar: Float>(
+    points: impl Iterator<Item = (Scalar, Scalar)> + Clone,
+) -> Scalar {
+    let all = points.clone();
+    let peaks = points.filter_map(move |(x, y)| {
+        let (Some(before), Some(after)) = neighbours(all.clone(), x) else {
+            return None;
+        };
+        let y = y.abs();
+        (y > Scalar::zero() && y >= before.abs() && y >= after.abs()).then(|| (x, y.ln(), y * y))
+    });
+    if peaks.clone().nth(1).is_none() {
+        return Scalar::zero();
+    }
+    weighted_line(peaks).map_or_else(Scalar::zero, |(slope, _)| -slope)
+}
+
+/// Least squares amplitude and phase of $A e^{-\gamma x} \sin(\omega x + \varphi)$ with fixed `omega` and `gamma`, returned as `(A, phi, rss)`.
+///
+/// Returns [`None`] if amplitude and phase can't be determined (like for less than two points).
+fn amplitude_phase<Scalar: Float>(
+    points: impl Iterator<Item = (Scalar, Scalar)>,
+    omega: Scalar,
+    gamma: Scalar,
+) -> Option<(Scalar, Scalar, Scalar)> {
+    // A sin(wx + phi) = p sin(wx) + q cos(wx), where p = A cos(phi) and q = A sin(phi)
+    let zero = Scalar::zero();
+    let (ss, sc, cc, sy, cy, yy) = points.fold(
+        (zero, zero, zero, zero, zero, zero),
+        |(ss, sc, cc, sy, cy, yy), (x, y)| {
+            let envelope = (-gamma * x).exp();
+            let (sin, cos) = (omega * x).sin_cos();
+            let (s, c) = (envelope * sin, envelope * cos);
+            (
+                ss + s * s,
+                sc + s * c,
+                cc + c * c,
+                sy + s * y,
+                cy + c * y,
+                yy + y * y,
+            )
+        },
+    );
+    let determinant = ss * cc - sc * sc;
+    if determinant <= Scalar::epsilon() * ss * cc || !determinant.is_finite() {
+        return None;
+    }
+    let p = (cc * sy - sc * cy) / determinant;
+    let q = (ss * cy - sc * sy) / determinant;
+    Some((p.hypot(q), q.atan2(p), yy - p * sy - q * cy))
+}
+
+/// Guesses $A e^{-\gamma x} \sin(\omega x + \varphi)$ parameters for known decay rate `gamma`, returned as `(A, omega, phi)`.
+///
+/// Half-periods are counted by zero crossings between neighbouring points, and frequencies around the resulting one are tried with least squares amplitude and phase, keeping the closest fit.
+pub(super) fn oscillation<Scalar: Float + FloatConst>(
+    points: impl Iterator<Item = (Scalar, Scalar)> + Clone,
+    gamma: Scalar,
+) -> Option<(Scalar, Scalar, Scalar)> {
+    let (x_min, x_max) = points.clone().fold(
+        (Scalar::infinity(), Scalar::neg_infinity()),
+        |(min, max), (x, _)| (min.min(x), max.max(x)),
+    );
+    let range = x_max - x_min;
+    if !range.is_finite() || range <= Scalar::zero() {
+        return None;
+    }
+    let zero = Scalar::zero();
+    let crossings = points
+        .clone()
+        .filter(|&(x, y)| {
+            neighbours(points.clone(), x)
+                .1
+                .is_some_and(|next| (y <= zero && next > zero) || (y >= zero && next < zero))
+        })
+        .count();
+    let crossings = Scalar::from(crossings)?;
+
+    // crossing count is only accurate up to a crossing, so frequency is scanned in steps of a quarter of that
+    let quarter = (Scalar::one() + Scalar::one()).powi(-2);
+    (-4..=4)
+        .filter_map(|step| {
+            let half_periods = crossings + Scalar::from(step)? * quarter;
+            let omega = Scalar::PI() * half_periods / range;
+            if omega <= zero {
+                return None;
+            }
+            let (a, phi, rss) = amplitude_phase(points.clone(), omega, gamma)?;
+            Some((a, omega, phi, rss))
+        })
+        .min_by(|(.., a), (.., b)| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal))
+        .map(|(a, omega, phi, _)| (a, omega, phi))
+}
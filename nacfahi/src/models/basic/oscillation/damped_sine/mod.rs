@@ -0,0 +1,116 @@
+use generic_array::GenericArray;
+use num_traits::{Float, FloatConst};
+use typenum::U4;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams};
+
+use super::common::{decay_rate, oscillation};
+
+/// Damped oscillation model $A e^{-\gamma x} \sin(\omega x + \varphi)$
+///
+/// Same as [`Sine`](super::Sine), but with exponentially decaying envelope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DampedSine<Scalar> {
+    /// Amplitude at `x = 0`, $A$
+    pub a: Scalar,
+    /// Angular frequency, $\omega$
+    pub omega: Scalar,
+    /// Phase, $\varphi$
+    pub phi: Scalar,
+    /// Decay rate, $\gamma$. Negative values make a growing oscillation.
+    pub gamma: Scalar,
+}
+
+impl<Scalar: Float> DampedSine<Scalar> {
+    /// Returns envelope (without amplitude), sine and cosine at `x`.
+    #[inline]
+    fn parts(&self, x: Scalar) -> (Scalar, Scalar, Scalar) {
+        let (sin, cos) = (self.omega * x + self.phi).sin_cos();
+        ((-self.gamma * x).exp(), sin, cos)
+    }
+}
+
+impl<Scalar: Float> FitModel for DampedSine<Scalar> {
+    type Scalar = Scalar;
+    type ParamCount = U4;
+
+    #[inline]
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let (envelope, sin, _) = self.parts(x);
+        self.a * envelope * sin
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        // y = A exp(-gx) sin(wx + phi)
+        // - derivative over A is exp(-gx) sin(wx + phi)
+        // - derivative over w is Ax exp(-gx) cos(wx + phi)
+        // - derivative over phi is A exp(-gx) cos(wx + phi)
+        // - derivative over g is -x * y
+        let (envelope, sin, cos) = self.parts(x);
+        let scaled = self.a * envelope;
+        [
+            envelope * sin,
+            scaled * x * cos,
+            scaled * cos,
+            -scaled * x * sin,
+        ]
+    }
+
+    #[inline]
+    fn set_params(&mut self, new_params: GenericArray<Self::Scalar, Self::ParamCount>) {
+        [self.a, self.omega, self.phi, self.gamma] = new_params.into_array();
+    }
+
+    #[inline]
+    fn get_params(&self) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        [self.a, self.omega, self.phi, self.gamma]
+    }
+}
+
+impl<Scalar: Float> FitModelXDeriv for DampedSine<Scalar> {
+    #[inline]
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let (envelope, sin, cos) = self.parts(x);
+        self.a * envelope * (self.omega * cos - self.gamma * sin)
+    }
+}
+
+impl<Scalar: Float + 'static> FitModelErrors for DampedSine<Scalar> {
+    type OwnedModel = Self;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
+        let [a, omega, phi, gamma] = errors.into_array();
+        Self {
+            a,
+            omega,
+            phi,
+            gamma,
+        }
+    }
+}
+
+impl<Scalar: Float + FloatConst> GuessParams for DampedSine<Scalar> {
+    /// Damping from a line through logarithms of `|y|` peaks, the rest as for [`Sine`](super::Sine) under that envelope.
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let gamma = decay_rate(points.clone());
+        if let Some((a, omega, phi)) = oscillation(points, gamma) {
+            *self = Self {
+                a,
+                omega,
+                phi,
+                gamma,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
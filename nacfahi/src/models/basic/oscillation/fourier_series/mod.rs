@@ -0,0 +1,171 @@
+use core::ops::{Add, Mul};
+
+use generic_array::{ArrayLength, GenericArray, sequence::GenericSequence};
+use generic_array_storage::Conv;
+use num_traits::Float;
+use typenum::{Add1, B1, Const, Prod, ToUInt, U2};
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams, LinearInParams, TNum};
+
+use super::super::polynomial::solve;
+
+/// Parameter count of a series with `N` harmonics: a constant term, and a cosine and a sine for each harmonic.
+type SeriesParams<const N: usize> = Add1<Prod<TNum<N>, U2>>;
+
+/// Truncated Fourier series model up to `N`-th harmonic, $a_{0} + \sum\limits_{k = 1}^{N} \left( a_{k} \cos(k \omega x) + b_{k} \sin(k \omega x) \right)$.
+///
+/// Model has $2N + 1$ parameters, which go interleaved in [`FitModel::get_params`], as $[a_{0}, a_{1}, b_{1}, a_{2}, b_{2}, \ldots]$.
+///
+/// Frequency is **not** a fit parameter: with it fixed, the model is [linear in its parameters](LinearInParams), so it can be fitted with [`fit_linear`](crate::fit_linear) directly. If the frequency is unknown, fit a [`Sine`](super::Sine) first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FourierSeries<const N: usize, Scalar> {
+    /// Fundamental angular frequency, $\omega$
+    pub omega: Scalar,
+    /// Constant term, $a_{0}$
+    pub a0: Scalar,
+    /// Cosine amplitudes $a_{1}, \ldots, a_{N}$
+    pub a: [Scalar; N],
+    /// Sine amplitudes $b_{1}, \ldots, b_{N}$
+    pub b: [Scalar; N],
+}
+
+impl<const N: usize, Scalar: Float> FourierSeries<N, Scalar> {
+    /// Frequency of the `k`-th harmonic.
+    #[inline]
+    fn frequency(&self, k: usize) -> Scalar {
+        Scalar::from(k).unwrap_or_else(Scalar::nan) * self.omega
+    }
+
+    /// Series with parameters in order of [`FitModel::get_params`].
+    #[inline]
+    fn from_params(omega: Scalar, params: &[Scalar]) -> Self {
+        Self {
+            omega,
+            a0: params[0],
+            a: core::array::from_fn(|k| params[2 * k + 1]),
+            b: core::array::from_fn(|k| params[2 * k + 2]),
+        }
+    }
+}
+
+impl<const N: usize, Scalar: Float> FitModel for FourierSeries<N, Scalar>
+where
+    Const<N>: ToUInt,
+    TNum<N>: Mul<U2>,
+    Prod<TNum<N>, U2>: Add<B1>,
+    SeriesParams<N>: Conv<TNum = SeriesParams<N>> + ArrayLength,
+{
+    type Scalar = Scalar;
+    type ParamCount = SeriesParams<N>;
+
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        self.a
+            .iter()
+            .zip(&self.b)
+            .enumerate()
+            .fold(self.a0, |acc, (k, (&a, &b))| {
+                let (sin, cos) = (self.frequency(k + 1) * x).sin_cos();
+                acc + a * cos + b * sin
+            })
+    }
+
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>> {
+        // y is a sum of a_k * cos(k w x) + b_k * sin(k w x), so derivatives are the basis functions themselves
+        // - derivative over a_0 is 1
+        // - derivative over a_k is cos(k w x)
+        // - derivative over b_k is sin(k w x)
+        GenericArray::<_, SeriesParams<N>>::generate(|i| {
+            if i == 0 {
+                return Scalar::one();
+            }
+            let (sin, cos) = (self.frequency(i.div_ceil(2)) * x).sin_cos();
+            if i % 2 == 1 { cos } else { sin }
+        })
+    }
+
+    fn set_params(
+        &mut self,
+        new_params: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) {
+        *self = Self::from_params(self.omega, &new_params);
+    }
+
+    fn get_params(
+        &self,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>> {
+        GenericArray::<_, SeriesParams<N>>::generate(|i| match i {
+            0 => self.a0,
+            i if i % 2 == 1 => self.a[i / 2],
+            i => self.b[i / 2 - 1],
+        })
+    }
+}
+
+impl<const N: usize, Scalar: Float> FitModelXDeriv for FourierSeries<N, Scalar>
+where
+    Self: FitModel<Scalar = Scalar>,
+{
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        self.a
+            .iter()
+            .zip(&self.b)
+            .enumerate()
+            .fold(Scalar::zero(), |acc, (k, (&a, &b))| {
+                let frequency = self.frequency(k + 1);
+                let (sin, cos) = (frequency * x).sin_cos();
+                acc + frequency * (b * cos - a * sin)
+            })
+    }
+}
+
+impl<const N: usize, Scalar: Float + 'static> FitModelErrors for FourierSeries<N, Scalar>
+where
+    Self: FitModel<Scalar = Scalar>,
+{
+    type OwnedModel = FourierSeries<N, Scalar>;
+
+    /// Frequency is not a parameter, so its error is reported as `0`.
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) -> Self::OwnedModel {
+        Self::from_params(Scalar::zero(), &errors)
+    }
+}
+
+impl<const N: usize, Scalar: Float> GuessParams for FourierSeries<N, Scalar>
+where
+    Const<N>: ToUInt,
+    TNum<N>: Mul<U2>,
+    Prod<TNum<N>, U2>: Add<B1>,
+    SeriesParams<N>: Conv<TNum = SeriesParams<N>> + ArrayLength,
+{
+    /// Least squares coefficients at current frequency, which is left as is.
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let zeros = || GenericArray::<_, SeriesParams<N>>::generate(|_| Scalar::zero());
+        let mut matrix = GenericArray::<_, SeriesParams<N>>::generate(|_| zeros());
+        let mut rhs = zeros();
+        let mut any = false;
+        for (x, y) in points {
+            any = true;
+            let basis = self.jacobian(&x).into();
+            for (i, row) in matrix.iter_mut().enumerate() {
+                for (j, value) in row.iter_mut().enumerate() {
+                    *value = *value + basis[i] * basis[j];
+                }
+                rhs[i] = rhs[i] + basis[i] * y;
+            }
+        }
+        if any {
+            *self = Self::from_params(self.omega, &solve(matrix, rhs));
+        }
+    }
+}
+
+impl<const N: usize, Scalar> LinearInParams for FourierSeries<N, Scalar> where Self: FitModel {}
+
+#[cfg(test)]
+mod tests;
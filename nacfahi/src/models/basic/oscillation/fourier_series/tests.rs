@@ -0,0 +1,18 @@
+crate::test_model_derivative!(
+    FourierSeries::<2, f64>,
+    FourierSeries {
+        omega: 0.8,
+        a0: -4.0,
+        a: [1.5, 0.7],
+        b: [-2.0, 0.3]
+    },
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5),
+        (6.0, 1.0)
+    ]
+);
@@ -0,0 +1,12 @@
+#[doc(hidden)]
+mod common;
+#[doc(hidden)]
+mod damped_sine;
+#[doc(hidden)]
+mod fourier_series;
+#[doc(hidden)]
+mod sine;
+
+pub use damped_sine::*;
+pub use fourier_series::*;
+pub use sine::*;
@@ -0,0 +1,85 @@
+use generic_array::GenericArray;
+use num_traits::{Float, FloatConst};
+use typenum::U3;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams};
+
+use super::common::oscillation;
+
+/// Sinusoid model $A \sin(\omega x + \varphi)$
+///
+/// Fits of periodic models are notoriously sensitive to initial frequency, so make sure to start close to the actual one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sine<Scalar> {
+    /// Amplitude, $A$
+    pub a: Scalar,
+    /// Angular frequency, $\omega$
+    pub omega: Scalar,
+    /// Phase, $\varphi$
+    pub phi: Scalar,
+}
+
+impl<Scalar: Float> FitModel for Sine<Scalar> {
+    type Scalar = Scalar;
+    type ParamCount = U3;
+
+    #[inline]
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        self.a * (self.omega * x + self.phi).sin()
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        // y = A sin(wx + phi)
+        // - derivative over A is sin(wx + phi)
+        // - derivative over w is Ax cos(wx + phi)
+        // - derivative over phi is A cos(wx + phi)
+        let (sin, cos) = (self.omega * x + self.phi).sin_cos();
+        [sin, self.a * x * cos, self.a * cos]
+    }
+
+    #[inline]
+    fn set_params(&mut self, new_params: GenericArray<Self::Scalar, Self::ParamCount>) {
+        [self.a, self.omega, self.phi] = new_params.into_array();
+    }
+
+    #[inline]
+    fn get_params(&self) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        [self.a, self.omega, self.phi]
+    }
+}
+
+impl<Scalar: Float> FitModelXDeriv for Sine<Scalar> {
+    #[inline]
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        self.a * self.omega * (self.omega * x + self.phi).cos()
+    }
+}
+
+impl<Scalar: Float + 'static> FitModelErrors for Sine<Scalar> {
+    type OwnedModel = Self;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
+        let [a, omega, phi] = errors.into_array();
+        Self { a, omega, phi }
+    }
+}
+
+impl<Scalar: Float + FloatConst> GuessParams for Sine<Scalar> {
+    /// Frequency from the number of zero crossings, refined along with least squares amplitude and phase. Needs at least two points with distinct `x`.
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        if let Some((a, omega, phi)) = oscillation(points, Scalar::zero()) {
+            *self = Self { a, omega, phi };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,16 @@
+crate::test_model_derivative!(
+    Sine::<f64>,
+    Sine {
+        a: -5.0,
+        omega: 1.3,
+        phi: 0.4,
+    },
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
@@ -1,11 +1,11 @@
 use core::ops::{Add, Mul};
 
-use generic_array::{GenericArray, IntoArrayLength, sequence::GenericSequence};
+use generic_array::{ArrayLength, GenericArray, IntoArrayLength, sequence::GenericSequence};
 use generic_array_storage::Conv;
-use num_traits::{One, Zero};
+use num_traits::{Float, One, Zero};
 use typenum::Const;
 
-use crate::models::{FitModel, FitModelErrors, FitModelXDeriv};
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams, LinearInParams};
 
 /// Polynomial model, $\sum\limits_{i=0}^{order-1} a_{i} \cdot x^{i}$.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -85,6 +85,7 @@ where
     type OwnedModel = Polynomial<ORDER, Scalar>;
 
     fn with_errors(
+        &self,
         errors: GenericArray<Self::Scalar, <Self::ParamCount as generic_array_storage::Conv>::TNum>,
     ) -> Self::OwnedModel {
         Polynomial {
@@ -93,5 +94,119 @@ where
     }
 }
 
+/// Solves linear system `matrix * solution = rhs` by Gaussian elimination with partial pivoting.
+///
+/// Components corresponding to (numerically) zero pivots are set to zero, so underdetermined systems still yield some solution.
+pub(super) fn solve<Scalar: Float, N: ArrayLength>(
+    mut matrix: GenericArray<GenericArray<Scalar, N>, N>,
+    mut rhs: GenericArray<Scalar, N>,
+) -> GenericArray<Scalar, N> {
+    let size = N::USIZE;
+    let largest = matrix
+        .iter()
+        .flatten()
+        .fold(Scalar::zero(), |acc, v| acc.max(v.abs()));
+    let tolerance = largest * Scalar::epsilon() * Scalar::from(size).unwrap_or_else(Scalar::one);
+    let mut pivots = GenericArray::<_, N>::generate(|_| false);
+    for col in 0..size {
+        let Some(pivot) = (col..size).max_by(|&a, &b| {
+            matrix[a][col]
+                .abs()
+                .partial_cmp(&matrix[b][col].abs())
+                .unwrap_or(core::cmp::Ordering::Equal)
+        }) else {
+            break;
+        };
+        if matrix[pivot][col].abs() <= tolerance {
+            continue;
+        }
+        matrix.swap(col, pivot);
+        rhs.swap(col, pivot);
+        pivots[col] = true;
+        let (upper, lower) = matrix.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            for (value, &pivot_value) in row.iter_mut().zip(pivot_row).skip(col) {
+                *value = *value - factor * pivot_value;
+            }
+            rhs[col + 1 + offset] = rhs[col + 1 + offset] - factor * rhs[col];
+        }
+    }
+    let mut solution = GenericArray::<_, N>::generate(|_| Scalar::zero());
+    for row in (0..size).rev() {
+        if pivots[row] {
+            let rest =
+                ((row + 1)..size).fold(Scalar::zero(), |acc, k| acc + matrix[row][k] * solution[k]);
+            solution[row] = (rhs[row] - rest) / matrix[row][row];
+        }
+    }
+    solution
+}
+
+impl<const ORDER: usize, Scalar: Float> GuessParams for Polynomial<ORDER, Scalar>
+where
+    Const<ORDER>: IntoArrayLength,
+    Self: FitModel<Scalar = Scalar>,
+{
+    /// Least squares polynomial. With fewer points than parameters, higher order coefficients are mostly zero.
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let (count, sum) = points
+            .clone()
+            .fold((0usize, Scalar::zero()), |(count, sum), (x, _)| {
+                (count + 1, sum + x)
+            });
+        let Some(count) = Scalar::from(count).filter(|count| !count.is_zero()) else {
+            return;
+        };
+        // normal equations are solved in terms of t = (x - shift) / scale, as powers of raw x are terribly conditioned
+        let shift = sum / count;
+        let scale = points
+            .clone()
+            .fold(Scalar::zero(), |acc, (x, _)| acc.max((x - shift).abs()));
+        let scale = if scale.is_zero() {
+            Scalar::one()
+        } else {
+            scale
+        };
+        let mut matrix = [[Scalar::zero(); ORDER]; ORDER];
+        let mut rhs = [Scalar::zero(); ORDER];
+        for (x, y) in points {
+            let t = (x - shift) / scale;
+            let mut powers = [Scalar::one(); ORDER];
+            for k in 1..ORDER {
+                powers[k] = powers[k - 1] * t;
+            }
+            for (i, row) in matrix.iter_mut().enumerate() {
+                for (j, value) in row.iter_mut().enumerate() {
+                    *value = *value + powers[i] * powers[j];
+                }
+                rhs[i] = rhs[i] + powers[i] * y;
+            }
+        }
+        let coefficients = solve(
+            GenericArray::from_array(matrix.map(GenericArray::from_array)),
+            GenericArray::from_array(rhs),
+        );
+
+        // expand sum of c_k * t^k back into powers of x, Horner-style
+        let mut params = [Scalar::zero(); ORDER];
+        for &coefficient in coefficients.iter().rev() {
+            let mut next = [Scalar::zero(); ORDER];
+            for j in 0..ORDER {
+                let lower = if j > 0 { params[j - 1] } else { Scalar::zero() };
+                next[j] = (lower - shift * params[j]) / scale;
+            }
+            if let Some(first) = next.first_mut() {
+                *first = *first + coefficient;
+            }
+            params = next;
+        }
+        self.params = params;
+    }
+}
+
+impl<const ORDER: usize, Scalar> LinearInParams for Polynomial<ORDER, Scalar> where Self: FitModel {}
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,116 @@
+use generic_array::GenericArray;
+use num_traits::Float;
+use typenum::U4;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams};
+
+use super::common::{broken_guess, ln_positive};
+
+/// Broken power law model, $a \cdot (x / x_{b})^{k_{1}}$ for $x < x_{b}$ and $a \cdot (x / x_{b})^{k_{2}}$ otherwise.
+///
+/// Knee is sharp, so derivative over $x$ (and over the break position) jumps at $x_{b}$; use [`SmoothlyBrokenPowerLaw`](super::SmoothlyBrokenPowerLaw) if that troubles the fit.
+///
+/// Same as [`PowerLaw`](super::PowerLaw), defined only for positive $x$ and $x_{b}$, with `NaN` value and derivatives otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BrokenPowerLaw<Scalar> {
+    /// Value at the break, $a$
+    pub a: Scalar,
+    /// Break position, $x_{b}$
+    pub x_b: Scalar,
+    /// Exponent below the break, $k_{1}$
+    pub k_1: Scalar,
+    /// Exponent above the break, $k_{2}$
+    pub k_2: Scalar,
+}
+
+impl<Scalar: Float> BrokenPowerLaw<Scalar> {
+    /// Returns $\ln(x / x_{b})$, exponent active at `x`, and whether it is the lower one.
+    #[inline]
+    fn branch(&self, x: Scalar) -> (Scalar, Scalar, bool) {
+        let ln_ratio = ln_positive(x) - ln_positive(self.x_b);
+        if x < self.x_b {
+            (ln_ratio, self.k_1, true)
+        } else {
+            (ln_ratio, self.k_2, false)
+        }
+    }
+}
+
+impl<Scalar: Float> FitModel for BrokenPowerLaw<Scalar> {
+    type Scalar = Scalar;
+    type ParamCount = U4;
+
+    #[inline]
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let (ln_ratio, k, _) = self.branch(x);
+        self.a * (k * ln_ratio).exp()
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        // y = a * (x / x_b)^k, with k being k_1 or k_2 depending on side
+        // - derivative over a is (x / x_b)^k
+        // - derivative over x_b is -y * k / x_b
+        // - derivative over active k is y * ln(x / x_b), and zero for the other one
+        let (ln_ratio, k, lower) = self.branch(x);
+        let power = (k * ln_ratio).exp();
+        let value = self.a * power;
+        let deriv_k = value * ln_ratio;
+        let (deriv_k_1, deriv_k_2) = if lower {
+            (deriv_k, Scalar::zero())
+        } else {
+            (Scalar::zero(), deriv_k)
+        };
+        [power, -value * k / self.x_b, deriv_k_1, deriv_k_2]
+    }
+
+    #[inline]
+    fn set_params(&mut self, new_params: GenericArray<Self::Scalar, Self::ParamCount>) {
+        [self.a, self.x_b, self.k_1, self.k_2] = new_params.into_array();
+    }
+
+    #[inline]
+    fn get_params(&self) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        [self.a, self.x_b, self.k_1, self.k_2]
+    }
+}
+
+impl<Scalar: Float> FitModelXDeriv for BrokenPowerLaw<Scalar> {
+    #[inline]
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let (ln_ratio, k, _) = self.branch(x);
+        self.a * (k * ln_ratio).exp() * k / x
+    }
+}
+
+impl<Scalar: Float + 'static> FitModelErrors for BrokenPowerLaw<Scalar> {
+    type OwnedModel = Self;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
+        let [a, x_b, k_1, k_2] = errors.into_array();
+        Self { a, x_b, k_1, k_2 }
+    }
+}
+
+impl<Scalar: Float> GuessParams for BrokenPowerLaw<Scalar> {
+    /// Break at the median `x`, with exponents from log-log lines on either side of it, like for [`PowerLaw`](super::PowerLaw).
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        if let Some((a, x_b, k_1, k_2)) = broken_guess(points) {
+            self.a = a;
+            self.x_b = x_b;
+            self.k_1 = k_1;
+            self.k_2 = k_2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,79 @@
+use num_traits::Float;
+
+use super::super::linear::weighted_line;
+
+/// Natural logarithm of `x`, which is `NaN` for non-positive `x` (including zero).
+///
+/// Power laws are defined only for positive `x`, and this makes sure they evaluate to `NaN` outside, instead of giving `0` or infinity at `x = 0` depending on exponent.
+#[inline]
+pub(super) fn ln_positive<Scalar: Float>(x: Scalar) -> Scalar {
+    if x > Scalar::zero() {
+        x.ln()
+    } else {
+        Scalar::nan()
+    }
+}
+
+/// Points of a power law in log-log scale, as `(ln x, ln |y|, weight)` for [`weighted_line`], along with sign of the majority of `y`.
+///
+/// Points with non-positive `x` or of sign opposite to the majority are dropped, and the rest are weighted by $y^2$ to compensate for logarithm amplifying noise of small values.
+pub(super) fn log_log<Scalar: Float>(
+    points: impl Iterator<Item = (Scalar, Scalar)> + Clone,
+) -> (
+    Scalar,
+    impl Iterator<Item = (Scalar, Scalar, Scalar)> + Clone,
+) {
+    let sign = points
+        .clone()
+        .filter(|&(x, _)| x > Scalar::zero())
+        .fold(Scalar::zero(), |acc, (_, y)| acc + y)
+        .signum();
+    let logarithms = points.filter_map(move |(x, y)| {
+        let y = sign * y;
+        (x > Scalar::zero() && y > Scalar::zero()).then(|| (x.ln(), y.ln(), y * y))
+    });
+    (sign, logarithms)
+}
+
+/// Guesses broken power law parameters, returned as `(a, x_b, k_1, k_2)`.
+///
+/// Break is put at the median `x`, and exponents are slopes of log-log lines on either side of it (median point belongs to both). Returns [`None`] if there are no usable points.
+pub(super) fn broken_guess<Scalar: Float>(
+    points: impl Iterator<Item = (Scalar, Scalar)> + Clone,
+) -> Option<(Scalar, Scalar, Scalar, Scalar)> {
+    let (sign, logarithms) = log_log(points);
+    let count = logarithms.clone().count();
+    // lower median without sorting: smallest value with at least half of the points not above it
+    let median = logarithms
+        .clone()
+        .map(|(ln_x, _, _)| ln_x)
+        .filter(|&ln_x| {
+            2 * logarithms
+                .clone()
+                .filter(|&(other, _, _)| other <= ln_x)
+                .count()
+                >= count
+        })
+        .reduce(Scalar::min)?;
+    let side = |lower: bool| {
+        logarithms
+            .clone()
+            .filter(move |&(ln_x, _, _)| {
+                if lower {
+                    ln_x <= median
+                } else {
+                    ln_x >= median
+                }
+            })
+            .map(move |(ln_x, ln_y, weight)| (ln_x - median, ln_y, weight))
+    };
+    let (k_1, ln_a_1) = weighted_line(side(true))?;
+    let (k_2, ln_a_2) = weighted_line(side(false))?;
+    let two = Scalar::one() + Scalar::one();
+    Some((
+        sign * ((ln_a_1 + ln_a_2) / two).exp(),
+        median.exp(),
+        k_1,
+        k_2,
+    ))
+}
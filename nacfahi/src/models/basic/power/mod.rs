@@ -0,0 +1,12 @@
+#[doc(hidden)]
+mod broken_power_law;
+#[doc(hidden)]
+mod common;
+#[doc(hidden)]
+mod power_law;
+#[doc(hidden)]
+mod smoothly_broken_power_law;
+
+pub use broken_power_law::*;
+pub use power_law::*;
+pub use smoothly_broken_power_law::*;
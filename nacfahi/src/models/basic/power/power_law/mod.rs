@@ -0,0 +1,91 @@
+use generic_array::GenericArray;
+use num_traits::Float;
+use typenum::U2;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams};
+
+use super::{
+    super::linear::weighted_line,
+    common::{ln_positive, log_log},
+};
+
+/// Power law model $a \cdot x^{k}$
+///
+/// Defined only for positive $x$: for $x \leq 0$ value and all the derivatives are `NaN`, so fit over such points fails with [numerical error](crate::TerminationReason::Numerical).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PowerLaw<Scalar> {
+    /// Value at `x = 1`, $a$
+    pub a: Scalar,
+    /// Exponent, $k$
+    pub k: Scalar,
+}
+
+impl<Scalar: Float> FitModel for PowerLaw<Scalar> {
+    type Scalar = Scalar;
+    type ParamCount = U2;
+
+    #[inline]
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        self.a * (self.k * ln_positive(x)).exp()
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        // y = a * x^k
+        // - derivative over a is x^k
+        // - derivative over k is y * ln(x)
+        let ln_x = ln_positive(x);
+        let power = (self.k * ln_x).exp();
+        [power, self.a * power * ln_x]
+    }
+
+    #[inline]
+    fn set_params(&mut self, new_params: GenericArray<Self::Scalar, Self::ParamCount>) {
+        [self.a, self.k] = new_params.into_array();
+    }
+
+    #[inline]
+    fn get_params(&self) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        [self.a, self.k]
+    }
+}
+
+impl<Scalar: Float> FitModelXDeriv for PowerLaw<Scalar> {
+    #[inline]
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        // y = a * x^k
+        // - derivative over x is y * k / x
+        self.evaluate(&x) * self.k / x
+    }
+}
+
+impl<Scalar: Float + 'static> FitModelErrors for PowerLaw<Scalar> {
+    type OwnedModel = Self;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
+        let [a, k] = errors.into_array();
+        Self { a, k }
+    }
+}
+
+impl<Scalar: Float> GuessParams for PowerLaw<Scalar> {
+    /// Same as for [`Exponent`](super::super::Exponent), but in $\ln x$. Points with non-positive `x` are ignored.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let (sign, logarithms) = log_log(points);
+        if let Some((k, ln_a)) = weighted_line(logarithms) {
+            self.a = sign * ln_a.exp();
+            self.k = k;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
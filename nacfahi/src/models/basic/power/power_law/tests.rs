@@ -0,0 +1,12 @@
+crate::test_model_derivative!(
+    PowerLaw::<f64>,
+    PowerLaw { a: -5.0, k: 1.7 },
+    [
+        (0.5, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
@@ -0,0 +1,148 @@
+use generic_array::GenericArray;
+use num_traits::Float;
+use typenum::U5;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams};
+
+use super::{
+    super::sigmoid::{logistic, softplus},
+    common::{broken_guess, ln_positive},
+};
+
+/// Smoothly broken power law model $a \cdot \left( \dfrac{x}{x_{b}} \right)^{k_{1}} \left( \dfrac{1 + (x / x_{b})^{1 / \Delta}}{2} \right)^{(k_{2} - k_{1}) \Delta}$
+///
+/// Goes as $x^{k_{1}}$ well below $x_{b}$ and as $x^{k_{2}}$ well above it, with knee width $\Delta$ measured in $\ln x$: as $\Delta \to 0$ this becomes [`BrokenPowerLaw`](super::BrokenPowerLaw). Value at $x_{b}$ is exactly $a$.
+///
+/// Same as [`PowerLaw`](super::PowerLaw), defined only for positive $x$ and $x_{b}$, with `NaN` value and derivatives otherwise. $\Delta$ should be positive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SmoothlyBrokenPowerLaw<Scalar> {
+    /// Value at the break, $a$
+    pub a: Scalar,
+    /// Break position, $x_{b}$
+    pub x_b: Scalar,
+    /// Exponent below the break, $k_{1}$
+    pub k_1: Scalar,
+    /// Exponent above the break, $k_{2}$
+    pub k_2: Scalar,
+    /// Knee smoothness, $\Delta$
+    pub delta: Scalar,
+}
+
+/// Intermediate values of [`SmoothlyBrokenPowerLaw`] at some `x`.
+struct Context<Scalar> {
+    /// $\ln(x / x_{b})$
+    ln_ratio: Scalar,
+    /// Model value divided by $a$
+    shape: Scalar,
+    /// $\ln\left( \left( 1 + (x / x_{b})^{1 / \Delta} \right) / 2 \right)$
+    knee: Scalar,
+    /// How far the local exponent went from $k_{1}$ to $k_{2}$, $\sigma(\ln(x / x_{b}) / \Delta)$
+    fraction: Scalar,
+}
+
+impl<Scalar: Float> SmoothlyBrokenPowerLaw<Scalar> {
+    #[inline]
+    fn context(&self, x: Scalar) -> Context<Scalar> {
+        let ln_ratio = ln_positive(x) - ln_positive(self.x_b);
+        let u = ln_ratio / self.delta;
+        // ln(1 + e^u) does not overflow for large x, unlike (x / x_b)^(1 / delta)
+        let knee = softplus(u) - (Scalar::one() + Scalar::one()).ln();
+        Context {
+            ln_ratio,
+            shape: (self.k_1 * ln_ratio + (self.k_2 - self.k_1) * self.delta * knee).exp(),
+            knee,
+            fraction: logistic(u),
+        }
+    }
+
+    /// Local exponent, i.e. derivative of $\ln y$ over $\ln x$.
+    #[inline]
+    fn slope(&self, context: &Context<Scalar>) -> Scalar {
+        self.k_1 + (self.k_2 - self.k_1) * context.fraction
+    }
+}
+
+impl<Scalar: Float> FitModel for SmoothlyBrokenPowerLaw<Scalar> {
+    type Scalar = Scalar;
+    type ParamCount = U5;
+
+    #[inline]
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        self.a * self.context(x).shape
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        // ln y = ln a + k_1 l + (k_2 - k_1) delta knee(l / delta), l = ln(x / x_b)
+        // - derivative over x_b is -y * slope / x_b
+        // - derivative over k_1 is y * (l - delta knee)
+        // - derivative over k_2 is y * delta knee
+        // - derivative over delta is y * (k_2 - k_1) (knee - l logistic(l / delta) / delta)
+        let c = self.context(x);
+        let value = self.a * c.shape;
+        let spread = self.delta * c.knee;
+        [
+            c.shape,
+            -value * self.slope(&c) / self.x_b,
+            value * (c.ln_ratio - spread),
+            value * spread,
+            value * (self.k_2 - self.k_1) * (c.knee - c.ln_ratio * c.fraction / self.delta),
+        ]
+    }
+
+    #[inline]
+    fn set_params(&mut self, new_params: GenericArray<Self::Scalar, Self::ParamCount>) {
+        [self.a, self.x_b, self.k_1, self.k_2, self.delta] = new_params.into_array();
+    }
+
+    #[inline]
+    fn get_params(&self) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        [self.a, self.x_b, self.k_1, self.k_2, self.delta]
+    }
+}
+
+impl<Scalar: Float> FitModelXDeriv for SmoothlyBrokenPowerLaw<Scalar> {
+    #[inline]
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let c = self.context(x);
+        self.a * c.shape * self.slope(&c) / x
+    }
+}
+
+impl<Scalar: Float + 'static> FitModelErrors for SmoothlyBrokenPowerLaw<Scalar> {
+    type OwnedModel = Self;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
+        let [a, x_b, k_1, k_2, delta] = errors.into_array();
+        Self {
+            a,
+            x_b,
+            k_1,
+            k_2,
+            delta,
+        }
+    }
+}
+
+impl<Scalar: Float> GuessParams for SmoothlyBrokenPowerLaw<Scalar> {
+    /// Same as for [`BrokenPowerLaw`](super::BrokenPowerLaw). Knee smoothness is left as is.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        if let Some((a, x_b, k_1, k_2)) = broken_guess(points) {
+            self.a = a;
+            self.x_b = x_b;
+            self.k_1 = k_1;
+            self.k_2 = k_2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
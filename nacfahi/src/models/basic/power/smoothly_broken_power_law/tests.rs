@@ -0,0 +1,18 @@
+crate::test_model_derivative!(
+    SmoothlyBrokenPowerLaw::<f64>,
+    SmoothlyBrokenPowerLaw {
+        a: -5.0,
+        x_b: 2.5,
+        k_1: 1.7,
+        k_2: -0.6,
+        delta: 0.3,
+    },
+    [
+        (0.5, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
@@ -0,0 +1,149 @@
+use generic_array::GenericArray;
+use num_traits::{Float, FloatConst};
+use typenum::U4;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams};
+
+use super::gaussian::peak;
+
+/// [Pseudo-Voigt](https://en.wikipedia.org/wiki/Voigt_profile#Pseudo-Voigt_approximation) model $A \cdot \left( \eta L(x) + (1 - \eta) G(x) \right)$,
+///
+/// where $L(x)$ and $G(x)$ are lorentzian and gaussian of unit area, both centered at $x_{c}$ and having the same FWHM $w$:
+/// - $G(x) = \dfrac{2}{w} \sqrt{\dfrac{\ln 2}{\pi}} \cdot \exp\left( -4 \ln 2 \dfrac{(x - x_{c})^2}{w^2} \right)$,
+/// - $L(x) = \dfrac{2}{\pi w} \cdot \dfrac{1}{1 + 4 (x - x_{c})^2 / w^2}$.
+///
+/// This is a cheap approximation of a [`Voigt`](super::Voigt) profile, accurate to about 1%.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PseudoVoigt<Scalar> {
+    /// Area of the profile, $A$
+    pub a: Scalar,
+    /// Center of the profile, $x_{c}$
+    pub x_c: Scalar,
+    /// Full width at half maximum, $w$
+    pub w: Scalar,
+    /// Mixing fraction, $\eta$: `0` is pure gaussian, `1` is pure lorentzian
+    pub eta: Scalar,
+}
+
+/// Values of unit-area components at some `x`, and their derivatives over $x_{c}$ and $w$.
+struct Components<Scalar> {
+    gaussian: Scalar,
+    lorentzian: Scalar,
+    gaussian_x_c: Scalar,
+    lorentzian_x_c: Scalar,
+    gaussian_w: Scalar,
+    lorentzian_w: Scalar,
+}
+
+impl<Scalar: Float + FloatConst> PseudoVoigt<Scalar> {
+    /// [FWHM](https://en.wikipedia.org/wiki/Full_width_at_half_maximum), $w$
+    #[inline]
+    pub fn fwhm(&self) -> Scalar {
+        self.w.abs()
+    }
+
+    #[inline]
+    fn components(&self, x: Scalar) -> Components<Scalar> {
+        let one = Scalar::one();
+        let two = one + one;
+        let four = two + two;
+        let four_ln_2 = four * Scalar::LN_2();
+        let (d, w) = (x - self.x_c, self.w);
+        let (d2, w2) = (d * d, w * w);
+
+        // G = 2/w * sqrt(ln2/pi) * exp(-4 ln2 d^2 / w^2)
+        let gaussian =
+            two / w * (Scalar::LN_2() * Scalar::FRAC_1_PI()).sqrt() * (-four_ln_2 * d2 / w2).exp();
+        // L = 2/pi * w / (w^2 + 4d^2)
+        let denominator = w2 + four * d2;
+        let lorentzian = two * Scalar::FRAC_1_PI() * w / denominator;
+        Components {
+            gaussian,
+            lorentzian,
+            gaussian_x_c: gaussian * two * four_ln_2 * d / w2,
+            lorentzian_x_c: lorentzian * two * four * d / denominator,
+            gaussian_w: gaussian * (two * four_ln_2 * d2 / (w2 * w) - one / w),
+            lorentzian_w: two * Scalar::FRAC_1_PI() * (four * d2 - w2)
+                / (denominator * denominator),
+        }
+    }
+}
+
+impl<Scalar: Float + FloatConst> FitModel for PseudoVoigt<Scalar> {
+    type Scalar = Scalar;
+    type ParamCount = U4;
+
+    #[inline]
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let c = self.components(x);
+        self.a * (self.eta * c.lorentzian + (Scalar::one() - self.eta) * c.gaussian)
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        let c = self.components(x);
+        let (eta, one_eta) = (self.eta, Scalar::one() - self.eta);
+        [
+            eta * c.lorentzian + one_eta * c.gaussian,
+            self.a * (eta * c.lorentzian_x_c + one_eta * c.gaussian_x_c),
+            self.a * (eta * c.lorentzian_w + one_eta * c.gaussian_w),
+            self.a * (c.lorentzian - c.gaussian),
+        ]
+    }
+
+    #[inline]
+    fn set_params(&mut self, new_params: GenericArray<Self::Scalar, Self::ParamCount>) {
+        [self.a, self.x_c, self.w, self.eta] = new_params.into_array();
+    }
+
+    #[inline]
+    fn get_params(&self) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        [self.a, self.x_c, self.w, self.eta]
+    }
+}
+
+impl<Scalar: Float + FloatConst> FitModelXDeriv for PseudoVoigt<Scalar> {
+    #[inline]
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let c = self.components(x);
+        -self.a * (self.eta * c.lorentzian_x_c + (Scalar::one() - self.eta) * c.gaussian_x_c)
+    }
+}
+
+impl<Scalar: Float + FloatConst + 'static> FitModelErrors for PseudoVoigt<Scalar> {
+    type OwnedModel = Self;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
+        let [a, x_c, w, eta] = errors.into_array();
+        Self { a, x_c, w, eta }
+    }
+}
+
+impl<Scalar: Float + FloatConst> GuessParams for PseudoVoigt<Scalar> {
+    /// Same as for [`Gaussian`](super::Gaussian): center and width come from the highest (by absolute value) peak, and area is set to match its height. Mixing fraction is kept, unless it's outside of `[0, 1]` - then it's reset to `0.5`.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let Some(peak) = peak(points, false) else {
+            return;
+        };
+        if !(self.eta >= Scalar::zero() && self.eta <= Scalar::one()) {
+            self.eta = (Scalar::one() + Scalar::one()).recip();
+        }
+        self.x_c = peak.x_c;
+        self.w = peak.left + peak.right;
+        // height of the unit-area profile
+        self.a = Scalar::one();
+        let unit_height = self.evaluate(&self.x_c);
+        self.a = peak.height / unit_height;
+    }
+}
+
+#[cfg(test)]
+mod tests;
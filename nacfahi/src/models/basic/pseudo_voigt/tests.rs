@@ -0,0 +1,17 @@
+crate::test_model_derivative!(
+    PseudoVoigt::<f64>,
+    PseudoVoigt {
+        a: -5.0,
+        x_c: 1.0,
+        w: 3.0,
+        eta: 0.3,
+    },
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
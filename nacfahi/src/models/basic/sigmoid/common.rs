@@ -0,0 +1,75 @@
+use num_traits::Float;
+
+/// Logistic function $\dfrac{1}{1 + e^{-u}}$, computed without overflow for any `u`.
+#[inline]
+pub(in crate::models::basic) fn logistic<Scalar: Float>(u: Scalar) -> Scalar {
+    if u >= Scalar::zero() {
+        (Scalar::one() + (-u).exp()).recip()
+    } else {
+        let e = u.exp();
+        e / (Scalar::one() + e)
+    }
+}
+
+/// Softplus function $\ln(1 + e^{u})$, computed without overflow for any `u`.
+#[inline]
+pub(in crate::models::basic) fn softplus<Scalar: Float>(u: Scalar) -> Scalar {
+    u.max(Scalar::zero()) + (-u.abs()).exp().ln_1p()
+}
+
+/// Rough position and steepness of a transition in data.
+pub(super) struct Transition<Scalar> {
+    /// Point where data is halfway between `low` and `high`
+    pub(super) x_c: Scalar,
+    /// Distance between points where data covers 25% and 75% of the way. Negative, if data goes from `high` to `low`.
+    pub(super) width: Scalar,
+}
+
+/// Finds points where data is closest to covering 25%, 50% and 75% of the way from `low` to `high`.
+///
+/// Returns [`None`] if there are no points, or `low` and `high` are the same.
+pub(super) fn transition<Scalar: Float>(
+    points: impl Iterator<Item = (Scalar, Scalar)> + Clone,
+    low: Scalar,
+    high: Scalar,
+) -> Option<Transition<Scalar>> {
+    let range = high - low;
+    if range.is_zero() || !range.is_finite() {
+        return None;
+    }
+    let quarter = (Scalar::one() + Scalar::one()).powi(-2);
+    let closest = |fraction: Scalar| {
+        points
+            .clone()
+            .map(|(x, y)| (x, ((y - low) / range - fraction).abs()))
+            .filter(|(_, distance)| !distance.is_nan())
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal))
+            .map(|(x, _)| x)
+    };
+    let x_c = closest(quarter + quarter)?;
+    let lower = closest(quarter)?;
+    let upper = closest(Scalar::one() - quarter)?;
+    Some(Transition {
+        x_c,
+        width: upper - lower,
+    })
+}
+
+/// Values of `y` at the smallest and the largest `x`.
+pub(super) fn ends<Scalar: Float>(
+    points: impl Iterator<Item = (Scalar, Scalar)>,
+) -> Option<(Scalar, Scalar)> {
+    let (first, last) = points.fold(
+        None,
+        |acc: Option<((Scalar, Scalar), (Scalar, Scalar))>, point| {
+            Some(match acc {
+                None => (point, point),
+                Some((first, last)) => (
+                    if point.0 < first.0 { point } else { first },
+                    if point.0 > last.0 { point } else { last },
+                ),
+            })
+        },
+    )?;
+    Some((first.1, last.1))
+}
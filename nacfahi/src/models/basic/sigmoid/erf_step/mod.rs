@@ -0,0 +1,114 @@
+use generic_array::GenericArray;
+use num_traits::{Float, FloatConst};
+use typenum::U3;
+
+use crate::{
+    models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams},
+    stats::{normal_cdf, normal_quantile},
+};
+
+use super::common::{ends, transition};
+
+/// Error function step model $\dfrac{A}{2} \left( 1 + \mathrm{erf} \dfrac{x - x_{c}}{\sqrt{2} \sigma} \right)$
+///
+/// Edge of a step blurred by a gaussian with width $\sigma$, e.g. knife-edge beam profile. Goes from `0` to $A$, so add a [`Constant`](super::super::Constant) if your data has a baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ErfStep<Scalar> {
+    /// Step height, $A$
+    pub a: Scalar,
+    /// Step position, $x_{c}$
+    pub x_c: Scalar,
+    /// Blur width, $\sigma$. Negative values make a descending step.
+    pub sigma: Scalar,
+}
+
+impl<Scalar: Float + FloatConst> ErfStep<Scalar> {
+    /// Returns $z = (x - x_{c}) / \sigma$ and model derivative over $x$.
+    #[inline]
+    fn slope(&self, x: Scalar) -> (Scalar, Scalar) {
+        let z = (x - self.x_c) / self.sigma;
+        let two = Scalar::one() + Scalar::one();
+        let density = (-z * z / two).exp() / (two * Scalar::PI()).sqrt();
+        (z, self.a * density / self.sigma)
+    }
+}
+
+impl<Scalar: Float + FloatConst> FitModel for ErfStep<Scalar> {
+    type Scalar = Scalar;
+    type ParamCount = U3;
+
+    #[inline]
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        self.a * normal_cdf((x - self.x_c) / self.sigma)
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        // y = A Phi(z), z = (x - x_c) / sigma
+        // - derivative over x_c is -A phi(z) / sigma
+        // - derivative over sigma is -A phi(z) z / sigma
+        let (z, slope) = self.slope(x);
+        [normal_cdf(z), -slope, -slope * z]
+    }
+
+    #[inline]
+    fn set_params(&mut self, new_params: GenericArray<Self::Scalar, Self::ParamCount>) {
+        [self.a, self.x_c, self.sigma] = new_params.into_array();
+    }
+
+    #[inline]
+    fn get_params(&self) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        [self.a, self.x_c, self.sigma]
+    }
+}
+
+impl<Scalar: Float + FloatConst> FitModelXDeriv for ErfStep<Scalar> {
+    #[inline]
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        self.slope(x).1
+    }
+}
+
+impl<Scalar: Float + FloatConst + 'static> FitModelErrors for ErfStep<Scalar> {
+    type OwnedModel = Self;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
+        let [a, x_c, sigma] = errors.into_array();
+        Self { a, x_c, sigma }
+    }
+}
+
+impl<Scalar: Float + FloatConst> GuessParams for ErfStep<Scalar> {
+    /// Same as for [`Logistic`](super::Logistic), with width taken from quartiles of the normal distribution.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let Some((first, last)) = ends(points.clone()) else {
+            return;
+        };
+        let a = if first.abs() > last.abs() {
+            first
+        } else {
+            last
+        };
+        let Some(transition) = transition(points, Scalar::zero(), a) else {
+            return;
+        };
+        self.a = a;
+        self.x_c = transition.x_c;
+        if !transition.width.is_zero() {
+            let half = (Scalar::one() + Scalar::one()).recip();
+            let quartile = normal_quantile(half + half * half);
+            self.sigma = transition.width / (quartile + quartile);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
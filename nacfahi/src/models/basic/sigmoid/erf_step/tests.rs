@@ -0,0 +1,16 @@
+crate::test_model_derivative!(
+    ErfStep::<f64>,
+    ErfStep {
+        a: -5.0,
+        x_c: 2.0,
+        sigma: 1.5,
+    },
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
@@ -0,0 +1,145 @@
+use generic_array::GenericArray;
+use num_traits::Float;
+use typenum::U4;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams};
+
+use super::common::{ends, logistic, transition};
+
+/// [Hill](https://en.wikipedia.org/wiki/Hill_equation_(biochemistry)) (four-parameter logistic) model $B + \dfrac{T - B}{1 + (\mathrm{EC}_{50} / x)^{n}}$
+///
+/// Typical dose-response curve, which is a [`Logistic`](super::Logistic) in $\ln x$. Defined only for non-negative $x$.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hill<Scalar> {
+    /// Response at zero, $B$
+    pub bottom: Scalar,
+    /// Response at infinity, $T$
+    pub top: Scalar,
+    /// Point where response is halfway between $B$ and $T$, $\mathrm{EC}_{50}$. Should be positive.
+    pub ec50: Scalar,
+    /// Hill coefficient, $n$
+    pub n: Scalar,
+}
+
+/// Intermediate values of [`Hill`] at some `x`.
+struct Context<Scalar> {
+    /// $\ln(x / \mathrm{EC}_{50})$
+    log: Scalar,
+    /// Fraction of the way from $B$ to $T$, $\sigma(n \ln(x / \mathrm{EC}_{50}))$
+    fraction: Scalar,
+    /// Derivative of the model over $\ln x$ divided by $n$
+    slope: Scalar,
+}
+
+impl<Scalar: Float> Hill<Scalar> {
+    #[inline]
+    fn context(&self, x: Scalar) -> Context<Scalar> {
+        let log = (x / self.ec50).ln();
+        let fraction = logistic(self.n * log);
+        Context {
+            log,
+            fraction,
+            slope: (self.top - self.bottom) * fraction * (Scalar::one() - fraction),
+        }
+    }
+}
+
+impl<Scalar: Float> FitModel for Hill<Scalar> {
+    type Scalar = Scalar;
+    type ParamCount = U4;
+
+    #[inline]
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let fraction = self.context(x).fraction;
+        self.bottom + (self.top - self.bottom) * fraction
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        // y = B + (T - B) f(n ln(x / EC50)), f' = f (1 - f)
+        let c = self.context(x);
+        if c.slope.is_zero() {
+            // saturated (or x = 0, where logarithm is infinite)
+            return [
+                Scalar::one() - c.fraction,
+                c.fraction,
+                Scalar::zero(),
+                Scalar::zero(),
+            ];
+        }
+        [
+            Scalar::one() - c.fraction,
+            c.fraction,
+            -c.slope * self.n / self.ec50,
+            c.slope * c.log,
+        ]
+    }
+
+    #[inline]
+    fn set_params(&mut self, new_params: GenericArray<Self::Scalar, Self::ParamCount>) {
+        [self.bottom, self.top, self.ec50, self.n] = new_params.into_array();
+    }
+
+    #[inline]
+    fn get_params(&self) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        [self.bottom, self.top, self.ec50, self.n]
+    }
+}
+
+impl<Scalar: Float> FitModelXDeriv for Hill<Scalar> {
+    #[inline]
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let slope = self.context(x).slope;
+        if slope.is_zero() {
+            return Scalar::zero();
+        }
+        slope * self.n / x
+    }
+}
+
+impl<Scalar: Float + 'static> FitModelErrors for Hill<Scalar> {
+    type OwnedModel = Self;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
+        let [bottom, top, ec50, n] = errors.into_array();
+        Self {
+            bottom,
+            top,
+            ec50,
+            n,
+        }
+    }
+}
+
+impl<Scalar: Float> GuessParams for Hill<Scalar> {
+    /// Bottom and top are the values at the smallest and the largest `x`, the rest is guessed like for [`Logistic`](super::Logistic) in $\ln x$. Non-positive `x` are ignored for the latter.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let Some((bottom, top)) = ends(points.clone()) else {
+            return;
+        };
+        let log_points = points
+            .filter(|&(x, _)| x > Scalar::zero())
+            .map(|(x, y)| (x.ln(), y));
+        let Some(transition) = transition(log_points, bottom, top) else {
+            return;
+        };
+        self.bottom = bottom;
+        self.top = top;
+        self.ec50 = transition.x_c.exp();
+        if !transition.width.is_zero() {
+            let ln_3 = (Scalar::one() + Scalar::one() + Scalar::one()).ln();
+            self.n = (ln_3 + ln_3) / transition.width;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,17 @@
+crate::test_model_derivative!(
+    Hill::<f64>,
+    Hill {
+        bottom: 1.0,
+        top: 6.0,
+        ec50: 2.0,
+        n: 1.7,
+    },
+    [
+        (0.5, 1.0),
+        (1.0, 2.0),
+        (2.0, 3.5),
+        (3.0, 5.0),
+        (4.0, 5.5),
+        (5.0, 6.0)
+    ]
+);
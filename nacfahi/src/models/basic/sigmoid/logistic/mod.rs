@@ -0,0 +1,109 @@
+use generic_array::GenericArray;
+use num_traits::Float;
+use typenum::U3;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams};
+
+use super::common::{ends, logistic, transition};
+
+/// [Logistic](https://en.wikipedia.org/wiki/Logistic_function) model $\dfrac{A}{1 + \exp(-k (x - x_{c}))}$
+///
+/// Goes from `0` to $A$, so add a [`Constant`](super::super::Constant) if your data has a baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Logistic<Scalar> {
+    /// Upper asymptote, $A$
+    pub a: Scalar,
+    /// Midpoint, $x_{c}$
+    pub x_c: Scalar,
+    /// Steepness, $k$. Negative values make a descending curve.
+    pub k: Scalar,
+}
+
+impl<Scalar: Float> Logistic<Scalar> {
+    /// Derivative of the logistic function at `x` over its argument $k (x - x_{c})$, scaled by amplitude.
+    #[inline]
+    fn slope(&self, x: Scalar) -> (Scalar, Scalar) {
+        let d = x - self.x_c;
+        let value = logistic(self.k * d);
+        (d, self.a * value * (Scalar::one() - value))
+    }
+}
+
+impl<Scalar: Float> FitModel for Logistic<Scalar> {
+    type Scalar = Scalar;
+    type ParamCount = U3;
+
+    #[inline]
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        self.a * logistic(self.k * (x - self.x_c))
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        // y = A * f(k (x - x_c)), f' = f (1 - f)
+        let (d, slope) = self.slope(x);
+        [logistic(self.k * d), -slope * self.k, slope * d]
+    }
+
+    #[inline]
+    fn set_params(&mut self, new_params: GenericArray<Self::Scalar, Self::ParamCount>) {
+        [self.a, self.x_c, self.k] = new_params.into_array();
+    }
+
+    #[inline]
+    fn get_params(&self) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        [self.a, self.x_c, self.k]
+    }
+}
+
+impl<Scalar: Float> FitModelXDeriv for Logistic<Scalar> {
+    #[inline]
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let (_, slope) = self.slope(x);
+        slope * self.k
+    }
+}
+
+impl<Scalar: Float + 'static> FitModelErrors for Logistic<Scalar> {
+    type OwnedModel = Self;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
+        let [a, x_c, k] = errors.into_array();
+        Self { a, x_c, k }
+    }
+}
+
+impl<Scalar: Float> GuessParams for Logistic<Scalar> {
+    /// Asymptote is the end value of data (the larger one by absolute value), midpoint is where data crosses half of it, and steepness comes from the distance between 25% and 75% crossings.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let Some((first, last)) = ends(points.clone()) else {
+            return;
+        };
+        let a = if first.abs() > last.abs() {
+            first
+        } else {
+            last
+        };
+        let Some(transition) = transition(points, Scalar::zero(), a) else {
+            return;
+        };
+        self.a = a;
+        self.x_c = transition.x_c;
+        if !transition.width.is_zero() {
+            // logistic function is at 1/4 and 3/4 at -ln(3) and ln(3)
+            let ln_3 = (Scalar::one() + Scalar::one() + Scalar::one()).ln();
+            self.k = (ln_3 + ln_3) / transition.width;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
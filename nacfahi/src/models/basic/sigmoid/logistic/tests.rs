@@ -0,0 +1,16 @@
+crate::test_model_derivative!(
+    Logistic::<f64>,
+    Logistic {
+        a: -5.0,
+        x_c: 1.0,
+        k: 1.5,
+    },
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
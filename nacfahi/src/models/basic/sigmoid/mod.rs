@@ -0,0 +1,16 @@
+#[doc(hidden)]
+mod common;
+#[doc(hidden)]
+mod erf_step;
+#[doc(hidden)]
+mod hill;
+#[doc(hidden)]
+mod logistic;
+#[doc(hidden)]
+mod richards;
+
+pub(in crate::models::basic) use common::{logistic, softplus};
+pub use erf_step::*;
+pub use hill::*;
+pub use logistic::*;
+pub use richards::*;
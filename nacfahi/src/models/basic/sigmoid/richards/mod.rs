@@ -0,0 +1,135 @@
+use generic_array::GenericArray;
+use num_traits::Float;
+use typenum::U4;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams};
+
+use super::{
+    Logistic,
+    common::{logistic, softplus},
+};
+
+/// [Generalized logistic (Richards)](https://en.wikipedia.org/wiki/Generalised_logistic_function) model $\dfrac{A}{\left( 1 + \exp(-k (x - x_{c})) \right)^{1 / \nu}}$
+///
+/// Shape parameter $\nu$ controls asymmetry of the curve: for $\nu = 1$ this is a regular [`Logistic`], and the smaller $\nu$ is, the closer to the upper asymptote the steepest point gets. $x_{c}$ is the midpoint only for $\nu = 1$.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Richards<Scalar> {
+    /// Upper asymptote, $A$
+    pub a: Scalar,
+    /// Location of the curve, $x_{c}$
+    pub x_c: Scalar,
+    /// Steepness, $k$. Negative values make a descending curve.
+    pub k: Scalar,
+    /// Shape parameter, $\nu$. Should be positive.
+    pub nu: Scalar,
+}
+
+/// Intermediate values of [`Richards`] at some `x`.
+struct Context<Scalar> {
+    /// $x - x_{c}$
+    d: Scalar,
+    /// Model value divided by amplitude, $(1 + e^{-u})^{-1 / \nu}$
+    value: Scalar,
+    /// Derivative of `value` over $u = k (x - x_{c})$
+    slope: Scalar,
+    /// $\ln(1 + e^{-u})$
+    log_base: Scalar,
+}
+
+impl<Scalar: Float> Richards<Scalar> {
+    #[inline]
+    fn context(&self, x: Scalar) -> Context<Scalar> {
+        let d = x - self.x_c;
+        let u = self.k * d;
+        // (1 + e^-u)^(-1/nu) = exp(-ln(1 + e^-u) / nu), which does not overflow
+        let log_base = softplus(-u);
+        let value = (-log_base / self.nu).exp();
+        Context {
+            d,
+            value,
+            slope: value * logistic(-u) / self.nu,
+            log_base,
+        }
+    }
+}
+
+impl<Scalar: Float> FitModel for Richards<Scalar> {
+    type Scalar = Scalar;
+    type ParamCount = U4;
+
+    #[inline]
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        self.a * self.context(x).value
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        // y = A * exp(-ln(1 + e^-u) / nu), u = k (x - x_c)
+        // - derivative over u is y * logistic(-u) / nu
+        // - derivative over nu is y * ln(1 + e^-u) / nu^2
+        let c = self.context(x);
+        [
+            c.value,
+            -self.a * c.slope * self.k,
+            self.a * c.slope * c.d,
+            self.a * c.value * c.log_base / (self.nu * self.nu),
+        ]
+    }
+
+    #[inline]
+    fn set_params(&mut self, new_params: GenericArray<Self::Scalar, Self::ParamCount>) {
+        [self.a, self.x_c, self.k, self.nu] = new_params.into_array();
+    }
+
+    #[inline]
+    fn get_params(&self) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        [self.a, self.x_c, self.k, self.nu]
+    }
+}
+
+impl<Scalar: Float> FitModelXDeriv for Richards<Scalar> {
+    #[inline]
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        self.a * self.context(x).slope * self.k
+    }
+}
+
+impl<Scalar: Float + 'static> FitModelErrors for Richards<Scalar> {
+    type OwnedModel = Self;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
+        let [a, x_c, k, nu] = errors.into_array();
+        Self { a, x_c, k, nu }
+    }
+}
+
+impl<Scalar: Float> GuessParams for Richards<Scalar> {
+    /// Same as for [`Logistic`]. Shape parameter is reset to `1`, unless it's already positive.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let mut logistic = Logistic {
+            a: self.a,
+            x_c: self.x_c,
+            k: self.k,
+        };
+        logistic.guess(points);
+        Logistic {
+            a: self.a,
+            x_c: self.x_c,
+            k: self.k,
+        } = logistic;
+        if !(self.nu > Scalar::zero() && self.nu.is_finite()) {
+            self.nu = Scalar::one();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
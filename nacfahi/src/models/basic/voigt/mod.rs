@@ -0,0 +1,176 @@
+use generic_array::GenericArray;
+use num_traits::{Float, FloatConst, NumCast};
+use typenum::U4;
+
+use crate::{
+    models::{FitModel, FitModelErrors, FitModelXDeriv, GuessParams},
+    stats::faddeeva,
+};
+
+use super::gaussian::peak;
+
+/// [Voigt](https://en.wikipedia.org/wiki/Voigt_profile) model - convolution of gaussian with standard deviation $\sigma$ and lorentzian with half width $\gamma$, both of unit area:
+///
+/// $\dfrac{A}{\sqrt{2 \pi} \sigma} \cdot \text{Re}\left[ w\left( \dfrac{x - x_{c} + i \gamma}{\sqrt{2} \sigma} \right) \right]$,
+///
+/// where $w(z)$ is the [Faddeeva function](crate::stats::faddeeva). Negative $\gamma$ is treated as $|\gamma|$.
+///
+/// Each evaluation costs a 40-term rational function in complex numbers, so consider [`PseudoVoigt`](super::PseudoVoigt) if you don't need exact profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Voigt<Scalar> {
+    /// Area of the profile, $A$
+    pub a: Scalar,
+    /// Center of the profile, $x_{c}$
+    pub x_c: Scalar,
+    /// Standard deviation of the gaussian component, $\sigma$
+    pub sigma: Scalar,
+    /// Half width at half maximum of the lorentzian component, $\gamma$
+    pub gamma: Scalar,
+}
+
+/// Profile value at some `x`, with its derivatives over real and imaginary parts of Faddeeva function argument.
+struct Context<Scalar> {
+    /// $\dfrac{1}{\sqrt{2 \pi} \sigma}$
+    norm: Scalar,
+    /// $\text{Re} \, w(z)$
+    re_w: Scalar,
+    /// Derivative of $\text{Re} \, w(z)$ over $\text{Re} \, z$
+    re_w_x: Scalar,
+    /// Derivative of $\text{Re} \, w(z)$ over $\text{Im} \, z$ (before taking absolute value)
+    re_w_y: Scalar,
+    /// $\text{Re} \, z$
+    x: Scalar,
+    /// $\text{Im} \, z$ (before taking absolute value)
+    y: Scalar,
+}
+
+impl<Scalar: Float + FloatConst> Voigt<Scalar> {
+    /// [FWHM](https://en.wikipedia.org/wiki/Full_width_at_half_maximum), computed with [Olivero-Longbothum](https://doi.org/10.1016/0022-4073(77)90161-3) approximation $0.5346 f_{L} + \sqrt{0.2166 f_{L}^2 + f_{G}^2}$ (accurate to about 0.02%),
+    ///
+    /// where $f_{G} = 2 \sqrt{2 \ln 2} \sigma$ and $f_{L} = 2 \gamma$ are FWHM of components.
+    #[inline]
+    pub fn fwhm(&self) -> Scalar {
+        let c = |value: f64| {
+            <Scalar as NumCast>::from(value).expect("Should be able to represent constant")
+        };
+        let two = Scalar::one() + Scalar::one();
+        let f_g = two * (two * Scalar::LN_2()).sqrt() * self.sigma.abs();
+        let f_l = two * self.gamma.abs();
+        c(0.5346) * f_l + (c(0.2166) * f_l * f_l + f_g * f_g).sqrt()
+    }
+
+    #[inline]
+    fn context(&self, x: Scalar) -> Context<Scalar> {
+        let two = Scalar::one() + Scalar::one();
+        let sqrt_2_sigma = two.sqrt() * self.sigma;
+        let (x, y) = ((x - self.x_c) / sqrt_2_sigma, self.gamma / sqrt_2_sigma);
+        let (re_w, im_w) = faddeeva(x, y.abs());
+        // w'(z) = -2z w(z) + 2i/sqrt(pi), and w is analytic, so Re w changes
+        // as Re w' over Re z, and as -Im w' over Im z
+        let re_dw = -two * (x * re_w - y.abs() * im_w);
+        let im_dw = -two * (x * im_w + y.abs() * re_w) + Scalar::FRAC_2_SQRT_PI();
+        Context {
+            norm: (Scalar::TAU().sqrt() * self.sigma).recip(),
+            re_w,
+            re_w_x: re_dw,
+            re_w_y: -im_dw * y.signum(),
+            x,
+            y,
+        }
+    }
+}
+
+impl<Scalar: Float + FloatConst> FitModel for Voigt<Scalar> {
+    type Scalar = Scalar;
+    type ParamCount = U4;
+
+    #[inline]
+    fn evaluate(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let c = self.context(x);
+        self.a * c.norm * c.re_w
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        &x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        // y = A / (sqrt(2pi) s) * Re w(z), z = (x - x_c + i g) / (sqrt(2) s)
+        // - derivative over x_c goes through Re z only
+        // - derivative over s goes through both, and the prefactor
+        // - derivative over g goes through Im z only
+        let c = self.context(x);
+        let sqrt_2_sigma = (Scalar::one() + Scalar::one()).sqrt() * self.sigma;
+        let scale = self.a * c.norm;
+        [
+            c.norm * c.re_w,
+            -scale * c.re_w_x / sqrt_2_sigma,
+            -scale * (c.re_w + c.re_w_x * c.x + c.re_w_y * c.y) / self.sigma,
+            scale * c.re_w_y / sqrt_2_sigma,
+        ]
+    }
+
+    #[inline]
+    fn set_params(&mut self, new_params: GenericArray<Self::Scalar, Self::ParamCount>) {
+        [self.a, self.x_c, self.sigma, self.gamma] = new_params.into_array();
+    }
+
+    #[inline]
+    fn get_params(&self) -> impl Into<GenericArray<Self::Scalar, Self::ParamCount>> {
+        [self.a, self.x_c, self.sigma, self.gamma]
+    }
+}
+
+impl<Scalar: Float + FloatConst> FitModelXDeriv for Voigt<Scalar> {
+    #[inline]
+    fn deriv_x(&self, &x: &Self::Scalar) -> Self::Scalar {
+        let c = self.context(x);
+        self.a * c.norm * c.re_w_x / ((Scalar::one() + Scalar::one()).sqrt() * self.sigma)
+    }
+}
+
+impl<Scalar: Float + FloatConst + 'static> FitModelErrors for Voigt<Scalar> {
+    type OwnedModel = Self;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, Self::ParamCount>,
+    ) -> Self::OwnedModel {
+        let [a, x_c, sigma, gamma] = errors.into_array();
+        Self {
+            a,
+            x_c,
+            sigma,
+            gamma,
+        }
+    }
+}
+
+impl<Scalar: Float + FloatConst> GuessParams for Voigt<Scalar> {
+    /// Same as for [`Gaussian`](super::Gaussian): center and width come from the highest (by absolute value) peak, and area is set to match its height. Width is split between components in the current $\gamma / \sigma$ ratio (or evenly, if it's not usable).
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        let Some(peak) = peak(points, false) else {
+            return;
+        };
+        let ratio = (self.gamma / self.sigma).abs();
+        let ratio = if ratio.is_finite() {
+            ratio
+        } else {
+            Scalar::one()
+        };
+        // FWHM is proportional to sigma for a fixed ratio
+        self.x_c = peak.x_c;
+        self.sigma = Scalar::one();
+        self.gamma = ratio;
+        self.sigma = (peak.left + peak.right) / self.fwhm();
+        self.gamma = ratio * self.sigma;
+        self.a = Scalar::one();
+        let unit_height = self.evaluate(&self.x_c);
+        self.a = peak.height / unit_height;
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,35 @@
+crate::test_model_derivative!(
+    Voigt::<f64>,
+    Voigt {
+        a: -5.0,
+        x_c: 1.0,
+        sigma: 1.5,
+        gamma: 0.7,
+    },
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
+
+crate::test_model_derivative!(
+    negative_gamma,
+    Voigt::<f64>,
+    Voigt {
+        a: 2.0,
+        x_c: -1.0,
+        sigma: 0.8,
+        gamma: -1.2,
+    },
+    [
+        (-3.0, 0.1),
+        (-1.5, 0.7),
+        (-1.0, 1.0),
+        (0.0, 0.4),
+        (2.0, 0.2)
+    ]
+);
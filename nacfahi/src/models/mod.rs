@@ -1,15 +1,17 @@
 use core::{
     iter::Sum,
-    ops::{Div, Mul},
+    ops::{Div, Mul, Sub},
 };
 
 use crate::{Conv, GenericArray};
 use generic_array::{
     ArrayLength,
     functional::FunctionalSequence,
-    sequence::{Flatten, Unflatten},
+    sequence::{Flatten, GenericSequence, Unflatten},
 };
-use typenum::{Prod, ToUInt};
+use generic_array_storage::{GenericArrayStorage, GenericMatrix};
+use num_traits::Float;
+use typenum::{Prod, ToUInt, U1, Unsigned};
 
 /// Basic building blocks for the models.
 pub mod basic;
@@ -72,6 +74,125 @@ pub trait FitModel {
     ) -> impl Into<crate::GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>>;
 }
 
+/// Defines a model of several independent variables, for example a surface $z = f(x, y)$.
+///
+/// Every [`FitModel`] is also a [`MultiInputModel`] with a single input (see blanket implementation below), so you only need to implement this trait for models actually depending on multiple variables. Such models are fitted with [`function@crate::fit_multi_input`], with `x` being a matrix containing a column per input variable.
+pub trait MultiInputModel {
+    /// Type of `x` components, `y`, model parameters and all the derivatives.
+    type Scalar;
+
+    /// Type representing number of independent variables (matrix columns of `x` data).
+    ///
+    /// **Hint**: `nacfahi::{U<1>, U<2>, ..}` types would most likely work for you.
+    type Inputs: Conv;
+
+    /// Type representing number of parameters. Same as [`FitModel::ParamCount`].
+    type ParamCount: Conv;
+
+    /// Computes model value for supplied `x` vector and current parameters.
+    fn evaluate(
+        &self,
+        x: &GenericArray<Self::Scalar, <Self::Inputs as Conv>::TNum>,
+    ) -> Self::Scalar;
+
+    /// Computes jacobian (array of derivatives) for supplied `x` vector and current parameters.
+    fn jacobian(
+        &self,
+        x: &GenericArray<Self::Scalar, <Self::Inputs as Conv>::TNum>,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>>;
+
+    /// Sets model parameters to ones contained in a generic array. Same as [`FitModel::set_params`].
+    fn set_params(
+        &mut self,
+        new_params: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    );
+
+    /// Returns current values of model params. Same as [`FitModel::get_params`].
+    fn get_params(&self)
+    -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>>;
+}
+
+impl<Model: FitModel> MultiInputModel for Model {
+    type Scalar = Model::Scalar;
+    type Inputs = U1;
+    type ParamCount = Model::ParamCount;
+
+    #[inline]
+    fn evaluate(&self, x: &GenericArray<Model::Scalar, U1>) -> Model::Scalar {
+        <Model as FitModel>::evaluate(self, &x[0])
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        x: &GenericArray<Model::Scalar, U1>,
+    ) -> impl Into<GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum>> {
+        <Model as FitModel>::jacobian(self, &x[0])
+    }
+
+    #[inline]
+    fn set_params(
+        &mut self,
+        new_params: GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum>,
+    ) {
+        <Model as FitModel>::set_params(self, new_params);
+    }
+
+    #[inline]
+    fn get_params(
+        &self,
+    ) -> impl Into<GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum>> {
+        <Model as FitModel>::get_params(self)
+    }
+}
+
+/// Defines a model producing several outputs (channels) for a single `x` value, all sharing the same set of parameters.
+///
+/// Such models are fitted with [`function@crate::fit_multi_output`], with `y` being a matrix containing a column per model output.
+pub trait MultiOutputModel {
+    /// Type of `x`, outputs, model parameters and all the derivatives.
+    type Scalar;
+
+    /// Type representing number of outputs (matrix columns of `y` data).
+    ///
+    /// **Hint**: `nacfahi::{U<1>, U<2>, ..}` types would most likely work for you.
+    type Outputs: Conv;
+
+    /// Type representing number of parameters. Same as [`FitModel::ParamCount`].
+    type ParamCount: Conv;
+
+    /// Computes all model outputs for supplied `x` and current parameters.
+    fn evaluate(
+        &self,
+        x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::Outputs as Conv>::TNum>>;
+
+    /// Computes jacobian block for supplied `x` and current parameters: an array of derivatives over parameters for each of the outputs.
+    #[allow(
+        clippy::type_complexity,
+        reason = "Type alias would only hide the actual block shape from the implementor."
+    )]
+    fn jacobian(
+        &self,
+        x: &Self::Scalar,
+    ) -> impl Into<
+        GenericArray<
+            GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+            <Self::Outputs as Conv>::TNum,
+        >,
+    >;
+
+    /// Sets model parameters to ones contained in a generic array. Same as [`FitModel::set_params`].
+    fn set_params(
+        &mut self,
+        new_params: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    );
+
+    /// Returns current values of model params. Same as [`FitModel::get_params`].
+    fn get_params(&self)
+    -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>>;
+}
+
 /// Helper trait for user code to avoid dependency on `nalgebra` and `num_traits` required to use `levenberg_marquardt`. If need be, you can ignore it, and specify corresponding bounds manually (as there's a blanket impl for it, see below).
 pub trait LevMarModel: FitModel<Scalar = Self::RealScalar> {
     #[doc(hidden)]
@@ -92,6 +213,21 @@ pub trait FitModelXDeriv: FitModel {
     fn deriv_x(&self, x: &Self::Scalar) -> Self::Scalar;
 }
 
+/// Defines models able to estimate their parameters from data, to start the fit from.
+///
+/// This trait is meant to extend [`FitModel`] to allow `guess = true` option of [`macro@crate::fit!`]. Levenberg-Marquardt only finds a local minimum, so decent starting point is often the difference between a successful fit and a failed one.
+pub trait GuessParams: FitModel {
+    /// Sets model parameters to rough estimates from data `points` (pairs of `x` and `y`).
+    ///
+    /// Points come in no particular order, and may be iterated over several times. Parameters model does not fit for (like `sigma` of `Gaussian<_, false>`) are left intact, and so is the model in case there are no points at all.
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone);
+}
+
+/// Marks models linear in their parameters: model value is exactly a dot product of [`FitModel::jacobian`] with current parameters, and jacobian itself does not depend on them.
+///
+/// Such models can be fit in one shot with [`function@crate::fit_linear`], instead of iterative minimization. Implementing this for a model that is not linear in parameters will silently produce garbage.
+pub trait LinearInParams: FitModel {}
+
 /// Defines models having a corresponding error-defining type.
 ///
 /// This trait is meant to extend [`FitModel`] to allow usage of [`macro@crate::fit_stat!`].
@@ -101,10 +237,66 @@ pub trait FitModelErrors: FitModel {
     /// Most of the time, this can be just `Self`.
     type OwnedModel: 'static;
 
-    /// Creates new model representing errors from the error array
+    /// Creates new model representing errors from the error array.
+    ///
+    /// Current model (with fitted parameters) is supplied as well, in case error representation depends on parameter values.
     fn with_errors(
+        &self,
         errors: GenericArray<Self::Scalar, <Self::ParamCount as generic_array_storage::Conv>::TNum>,
     ) -> Self::OwnedModel;
+
+    /// Creates new model representing errors from the parameter covariance matrix.
+    ///
+    /// By default, errors are square roots of the covariance diagonal, passed to [`FitModelErrors::with_errors`]. Override this in case errors of some of the parameters should account for correlations (for example, if they are computed from other parameters).
+    fn with_covariance(
+        &self,
+        covariance: &GenericMatrix<Self::Scalar, Self::ParamCount, Self::ParamCount>,
+    ) -> Self::OwnedModel
+    where
+        Self::Scalar: Float,
+    {
+        let errors = GenericArray::generate(|i| covariance[(i, i)].sqrt());
+        self.with_errors(errors)
+    }
+
+    /// Creates new model representing errors of actual parameter values (see [`FitModelErrors::get_external_params`]).
+    ///
+    /// Unlike [`FitModelErrors::with_errors`], errors here are already in actual parameters, so they should not be propagated through the parameter transform. By default, this is just [`FitModelErrors::with_errors`].
+    fn with_external_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, <Self::ParamCount as generic_array_storage::Conv>::TNum>,
+    ) -> Self::OwnedModel {
+        self.with_errors(errors)
+    }
+
+    /// Derivatives of actual parameter values (see [`FitModelErrors::get_external_params`]) over the fitted ones, in order of [`FitModel::get_params`].
+    ///
+    /// By default, these are all ones. Each actual parameter is assumed to depend on its own fitted parameter only, so parameter covariance $C$ propagates into actual parameters as $D C D$, where $D$ is a diagonal matrix of these.
+    fn external_derivatives(
+        &self,
+    ) -> GenericArray<Self::Scalar, <Self::ParamCount as generic_array_storage::Conv>::TNum>
+    where
+        Self::Scalar: Float,
+    {
+        GenericArray::generate(|_| <Self::Scalar as num_traits::One>::one())
+    }
+
+    /// Actual parameter values, in order of [`FitModel::get_params`].
+    ///
+    /// By default, these are just [`FitModel::get_params`]. Override this (along with [`FitModelErrors::set_external_params`]) in case the model is fitted in some transformed parameters, like [`Bounded`](utility::Bounded) is. Statistics over parameter values (like mean of bootstrapped fits) are computed in these.
+    fn get_external_params(
+        &self,
+    ) -> GenericArray<Self::Scalar, <Self::ParamCount as generic_array_storage::Conv>::TNum> {
+        self.get_params().into()
+    }
+
+    /// Sets actual parameter values, inverse of [`FitModelErrors::get_external_params`].
+    fn set_external_params(
+        &mut self,
+        values: GenericArray<Self::Scalar, <Self::ParamCount as generic_array_storage::Conv>::TNum>,
+    ) {
+        self.set_params(values);
+    }
 }
 
 impl<Model> FitModel for &'_ mut Model
@@ -159,16 +351,68 @@ impl<Model: FitModelErrors> FitModelErrors for &'_ mut Model {
 
     #[inline]
     fn with_errors(
+        &self,
+        errors: GenericArray<Model::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) -> Self::OwnedModel {
+        <Model as FitModelErrors>::with_errors(self, errors)
+    }
+
+    #[inline]
+    fn with_covariance(
+        &self,
+        covariance: &GenericMatrix<Self::Scalar, Self::ParamCount, Self::ParamCount>,
+    ) -> Self::OwnedModel
+    where
+        Self::Scalar: Float,
+    {
+        <Model as FitModelErrors>::with_covariance(self, covariance)
+    }
+
+    #[inline]
+    fn with_external_errors(
+        &self,
         errors: GenericArray<Model::Scalar, <Self::ParamCount as Conv>::TNum>,
     ) -> Self::OwnedModel {
-        <Model as FitModelErrors>::with_errors(errors)
+        <Model as FitModelErrors>::with_external_errors(self, errors)
+    }
+
+    #[inline]
+    fn external_derivatives(&self) -> GenericArray<Model::Scalar, <Self::ParamCount as Conv>::TNum>
+    where
+        Self::Scalar: Float,
+    {
+        <Model as FitModelErrors>::external_derivatives(self)
+    }
+
+    #[inline]
+    fn get_external_params(&self) -> GenericArray<Model::Scalar, <Self::ParamCount as Conv>::TNum> {
+        <Model as FitModelErrors>::get_external_params(self)
+    }
+
+    #[inline]
+    fn set_external_params(
+        &mut self,
+        values: GenericArray<Model::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) {
+        <Model as FitModelErrors>::set_external_params(self, values);
+    }
+}
+
+impl<Model: GuessParams> GuessParams for &'_ mut Model {
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        <Model as GuessParams>::guess(self, points);
     }
 }
 
+impl<Model: LinearInParams> LinearInParams for &'_ mut Model {}
+
 #[cfg(test)]
 static_assertions::assert_impl_all!([basic::Gaussian<f64>; 1]: FitModel);
 #[cfg(test)]
 static_assertions::assert_impl_all!([basic::Exponent<f64>; 5]: FitModel);
+#[cfg(test)]
+static_assertions::assert_impl_all!(basic::Linear<f64>: MultiInputModel<Inputs = U1>);
 
 impl<const N: usize, Model> FitModel for [Model; N]
 where
@@ -256,14 +500,121 @@ where
 
     #[inline]
     fn with_errors(
+        &self,
         errors: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
     ) -> Self::OwnedModel {
         let unflat: GenericArray<
             GenericArray<_, <<Model as FitModel>::ParamCount as Conv>::TNum>,
             <typenum::Const<N> as ToUInt>::Output,
         > = errors.unflatten();
-        unflat.map(Model::with_errors).into_array()
+        let mut models = self.iter();
+        unflat
+            .map(|errors| {
+                models
+                    .next()
+                    .expect("Should have exactly N models")
+                    .with_errors(errors)
+            })
+            .into_array()
     }
+
+    #[inline]
+    fn with_covariance(
+        &self,
+        covariance: &GenericMatrix<Self::Scalar, Self::ParamCount, Self::ParamCount>,
+    ) -> Self::OwnedModel
+    where
+        Self::Scalar: Float,
+    {
+        // parameters of different models are correlated in general, but each model only reports errors of its own parameters, so off-diagonal blocks are dropped
+        let size = <<Model::ParamCount as Conv>::TNum as Unsigned>::USIZE;
+        let mut offset = 0;
+        self.each_ref().map(|model| {
+            let block =
+                GenericMatrix::<Self::Scalar, Model::ParamCount, Model::ParamCount>::from_data(
+                    GenericArrayStorage(GenericArray::generate(|col| {
+                        GenericArray::generate(|row| covariance[(offset + row, offset + col)])
+                    })),
+                );
+            offset += size;
+            model.with_covariance(&block)
+        })
+    }
+
+    #[inline]
+    fn with_external_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) -> Self::OwnedModel {
+        let unflat: GenericArray<
+            GenericArray<_, <<Model as FitModel>::ParamCount as Conv>::TNum>,
+            <typenum::Const<N> as ToUInt>::Output,
+        > = errors.unflatten();
+        let mut models = self.iter();
+        unflat
+            .map(|errors| {
+                models
+                    .next()
+                    .expect("Should have exactly N models")
+                    .with_external_errors(errors)
+            })
+            .into_array()
+    }
+
+    #[inline]
+    fn external_derivatives(&self) -> GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>
+    where
+        Self::Scalar: Float,
+    {
+        self.iter().flat_map(Model::external_derivatives).collect()
+    }
+
+    #[inline]
+    fn get_external_params(&self) -> GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum> {
+        self.iter().flat_map(Model::get_external_params).collect()
+    }
+
+    #[inline]
+    fn set_external_params(
+        &mut self,
+        values: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) {
+        let unflat: GenericArray<
+            GenericArray<_, <<Model as FitModel>::ParamCount as Conv>::TNum>,
+            <typenum::Const<N> as ToUInt>::Output,
+        > = values.unflatten();
+        let inners: &mut GenericArray<Model, TNum<N>> =
+            GenericArray::from_mut_slice(self.as_mut_slice());
+        inners.zip(unflat, Model::set_external_params);
+    }
+}
+
+impl<const N: usize, Model> GuessParams for [Model; N]
+where
+    Self: FitModel<Scalar = Model::Scalar>,
+    Model: GuessParams,
+    Model::Scalar: Sum + Clone + Sub<Output = Model::Scalar>,
+{
+    /// Models are guessed one after another, each one from what's left of the data after subtracting the previous ones. For peak-like models (like [`Gaussian`](basic::Gaussian)), this finds peaks from the highest to the lowest.
+    #[inline]
+    fn guess(&mut self, points: impl Iterator<Item = (Self::Scalar, Self::Scalar)> + Clone) {
+        for i in 0..N {
+            let (guessed, rest) = self.split_at_mut(i);
+            let guessed: &[Model] = guessed;
+            rest[0].guess(points.clone().map(move |(x, y)| {
+                let fitted = guessed.iter().map(|model| model.evaluate(&x)).sum();
+                (x, y - fitted)
+            }));
+        }
+    }
+}
+
+/// Sum of linear models is linear.
+impl<const N: usize, Model> LinearInParams for [Model; N]
+where
+    Self: FitModel<Scalar = Model::Scalar>,
+    Model: LinearInParams,
+{
 }
 
 #[cfg(doc)]
@@ -322,7 +673,7 @@ macro_rules! test_model_derivative {
 
             struct T<'l, F>(Opt<'l, F>);
 
-            impl<F: Fn(f64, f64) -> f64>
+            impl<F: Fn(::generic_array::GenericArray<f64, ::typenum::U1>, f64) -> f64>
                 ::levenberg_marquardt::LeastSquaresProblem<
                     f64,
                     ::nalgebra::Const<M>,
@@ -390,13 +741,14 @@ macro_rules! test_model_derivative {
             }
 
             // arrange
-            let model = $model;
+            let mut model = $model;
             let x = [$($x),*].convert();
             let y = [$($y),*].convert();
             let mut combined = T($crate::const_problem::ConstOptimizationProblem {
-                model,
+                model: &mut model,
                 x,
                 y,
+                sigma: None,
                 weights: |_, _| 1.0,
             });
 
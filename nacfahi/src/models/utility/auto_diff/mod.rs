@@ -0,0 +1,168 @@
+use generic_array::{GenericArray, functional::FunctionalSequence, sequence::GenericSequence};
+use generic_array_storage::Conv;
+use num_traits::{Float, FloatConst};
+use typenum::U1;
+
+use crate::{
+    dual::Dual,
+    models::{FitModel, FitModelErrors, FitModelXDeriv},
+};
+
+/// Scalar type [`AutoDiffModel::evaluate`] is called with.
+///
+/// This is either model scalar itself, or a [`Dual`] number over it.
+pub trait AutoDiffScalar<Scalar>: Float + FloatConst + From<Scalar> {}
+
+impl<Scalar, T: Float + FloatConst + From<Scalar>> AutoDiffScalar<Scalar> for T {}
+
+/// Model defined by a generic `evaluate` function only. Wrap it into [`AutoDiff`] to get a [`FitModel`].
+///
+/// See [`AutoDiff`] for an example.
+pub trait AutoDiffModel {
+    /// Type of `x`, `y` and model parameters.
+    type Scalar: Float + FloatConst;
+
+    /// Type representing number of parameters.
+    type ParamCount: Conv;
+
+    /// Computes model value for supplied `x` value and `params`.
+    ///
+    /// This should be written generically over `T`, using [`Float`] and [`FloatConst`] functions only. Model scalars can be converted into `T` with [`From`].
+    fn evaluate<T: AutoDiffScalar<Self::Scalar>>(
+        &self,
+        params: &GenericArray<T, <Self::ParamCount as Conv>::TNum>,
+        x: T,
+    ) -> T;
+
+    /// Sets model parameters to ones contained in a generic array. Same as [`FitModel::set_params`].
+    fn set_params(
+        &mut self,
+        new_params: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    );
+
+    /// Returns current values of model params. Same as [`FitModel::get_params`].
+    fn get_params(&self)
+    -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>>;
+}
+
+/// Model computing jacobian and `x` derivative of [`AutoDiffModel`] via forward-mode automatic differentiation (see [`Dual`]).
+///
+/// Everything is computed on the stack, so this works without `alloc` feature.
+///
+/// ```rust
+/// # use approx::assert_relative_eq;
+/// # use nacfahi::{models::utility::{AutoDiff, AutoDiffModel, AutoDiffScalar}, *};
+/// // a / (1 + (x - x_c)^2)
+/// struct Peak {
+///     a: f64,
+///     x_c: f64,
+/// }
+///
+/// impl AutoDiffModel for Peak {
+///     type Scalar = f64;
+///     type ParamCount = U<2>;
+///
+///     fn evaluate<T: AutoDiffScalar<f64>>(&self, params: &GenericArray<T, U<2>>, x: T) -> T {
+///         let [a, x_c] = (*params).into_array();
+///         a / (T::one() + (x - x_c).powi(2))
+///     }
+///
+///     fn set_params(&mut self, new_params: GenericArray<f64, U<2>>) {
+///         [self.a, self.x_c] = new_params.into_array();
+///     }
+///
+///     fn get_params(&self) -> impl Into<GenericArray<f64, U<2>>> {
+///         [self.a, self.x_c]
+///     }
+/// }
+///
+/// let x: [f64; 11] = core::array::from_fn(|i| i as f64 - 5.0);
+/// let y = x.map(|x| 3.0 / (1.0 + (x - 0.5).powi(2)));
+///
+/// let mut model = AutoDiff(Peak { a: 1.0, x_c: 0.0 });
+/// let report = fit!(&mut model, x, y);
+///
+/// assert!(report.termination.was_successful());
+/// assert_relative_eq!(model.0.a, 3.0, max_relative = 1e-10);
+/// assert_relative_eq!(model.0.x_c, 0.5, max_relative = 1e-10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct AutoDiff<Model>(pub Model);
+
+impl<Model: AutoDiffModel> FitModel for AutoDiff<Model>
+where
+    GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum>: Copy,
+{
+    type Scalar = Model::Scalar;
+    type ParamCount = Model::ParamCount;
+
+    #[inline]
+    fn evaluate(&self, x: &Self::Scalar) -> Self::Scalar {
+        let params = self.0.get_params().into();
+        self.0.evaluate(&params, *x)
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>> {
+        // seed each of the parameters as a separate variable
+        let params: GenericArray<_, _> = self.0.get_params().into();
+        let params = GenericArray::generate(|i| Dual::variable(params[i], i));
+        self.0.evaluate(&params, Dual::constant(*x)).grad
+    }
+
+    #[inline]
+    fn set_params(
+        &mut self,
+        new_params: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) {
+        self.0.set_params(new_params);
+    }
+
+    #[inline]
+    fn get_params(
+        &self,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>> {
+        self.0.get_params()
+    }
+}
+
+impl<Model: AutoDiffModel> FitModelXDeriv for AutoDiff<Model>
+where
+    GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum>: Copy,
+    GenericArray<Model::Scalar, U1>: Copy,
+{
+    #[inline]
+    fn deriv_x(&self, x: &Self::Scalar) -> Self::Scalar {
+        let params: GenericArray<_, _> = self.0.get_params().into();
+        let params = params.map(Dual::<_, U1>::constant);
+        let [deriv] = self
+            .0
+            .evaluate(&params, Dual::variable(*x, 0))
+            .grad
+            .into_array();
+        deriv
+    }
+}
+
+impl<Model: AutoDiffModel + Clone + 'static> FitModelErrors for AutoDiff<Model>
+where
+    GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum>: Copy,
+{
+    type OwnedModel = Model;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) -> Self::OwnedModel {
+        let mut errors_model = self.0.clone();
+        errors_model.set_params(errors);
+        errors_model
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,72 @@
+use generic_array::GenericArray;
+use typenum::U3;
+
+use super::{AutoDiff, AutoDiffModel, AutoDiffScalar};
+
+/// Gaussian with explicit formula, to be compared with the analytic one.
+#[derive(Debug, Clone)]
+struct AutoGaussian {
+    params: [f64; 3],
+}
+
+impl AutoDiffModel for AutoGaussian {
+    type Scalar = f64;
+    type ParamCount = U3;
+
+    fn evaluate<T: AutoDiffScalar<f64>>(&self, params: &GenericArray<T, U3>, x: T) -> T {
+        let [a, x_c, sigma] = (*params).into_array();
+        let two = T::one() + T::one();
+        a / ((two * T::PI()).sqrt() * sigma) * (-(x - x_c).powi(2) / (two * sigma * sigma)).exp()
+    }
+
+    fn set_params(&mut self, new_params: GenericArray<f64, U3>) {
+        self.params = new_params.into_array();
+    }
+
+    fn get_params(&self) -> impl Into<GenericArray<f64, U3>> {
+        self.params
+    }
+}
+
+crate::test_model_derivative!(
+    AutoDiff<AutoGaussian>,
+    AutoDiff(AutoGaussian {
+        params: [2.0, 2.5, 1.5]
+    }),
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
+
+#[test]
+fn matches_analytic() {
+    use crate::models::{FitModel, FitModelXDeriv, basic::Gaussian};
+
+    let analytic = Gaussian::<f64> {
+        a: 2.0,
+        x_c: 0.5,
+        sigma: 1.5,
+    };
+    let auto = AutoDiff(AutoGaussian {
+        params: [2.0, 0.5, 1.5],
+    });
+
+    for x in [-3.0, -0.5, 0.0, 0.7, 2.0, 4.5] {
+        approx::assert_relative_eq!(
+            auto.evaluate(&x),
+            analytic.evaluate(&x),
+            max_relative = 1e-12
+        );
+        approx::assert_relative_eq!(auto.deriv_x(&x), analytic.deriv_x(&x), max_relative = 1e-12);
+        let auto_jacobian: GenericArray<f64, U3> = FitModel::jacobian(&auto, &x).into();
+        let analytic_jacobian: GenericArray<f64, U3> = FitModel::jacobian(&analytic, &x).into();
+        for (auto, analytic) in auto_jacobian.into_iter().zip(analytic_jacobian) {
+            approx::assert_relative_eq!(auto, analytic, max_relative = 1e-12, epsilon = 1e-15);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use generic_array::{GenericArray, functional::FunctionalSequence, sequence::GenericSequence};
+use generic_array_storage::{Conv, GenericArrayStorage, GenericMatrix};
+use num_traits::Float;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv};
+
+/// Bound imposed on a single parameter by [`Bounded`] model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ParamBound<Scalar> {
+    /// Parameter is not bounded.
+    #[default]
+    Free,
+    /// Parameter is bounded from below.
+    Lower(Scalar),
+    /// Parameter is bounded from above.
+    Upper(Scalar),
+    /// Parameter is bounded from both sides, lower bound being the first one.
+    Both(Scalar, Scalar),
+}
+
+impl<Scalar: Float> ParamBound<Scalar> {
+    /// Converts parameter value seen by the optimizer into the actual (bounded) one.
+    fn external(self, internal: Scalar) -> Scalar {
+        let one = Scalar::one();
+        let two = one + one;
+        match self {
+            Self::Free => internal,
+            Self::Lower(min) => min - one + (internal * internal + one).sqrt(),
+            Self::Upper(max) => max + one - (internal * internal + one).sqrt(),
+            Self::Both(min, max) => min + (max - min) / two * (internal.sin() + one),
+        }
+    }
+
+    /// Converts the actual parameter value into the one seen by the optimizer. Values outside of the bounds are clamped to them.
+    fn internal(self, external: Scalar) -> Scalar {
+        let one = Scalar::one();
+        let two = one + one;
+        match self {
+            Self::Free => external,
+            Self::Lower(min) => {
+                let shifted = external.max(min) - min + one;
+                (shifted * shifted - one).sqrt()
+            }
+            Self::Upper(max) => {
+                let shifted = max - external.min(max) + one;
+                (shifted * shifted - one).sqrt()
+            }
+            Self::Both(min, max) => (two * (external - min) / (max - min) - one)
+                .max(-one)
+                .min(one)
+                .asin(),
+        }
+    }
+
+    /// Derivative of [`ParamBound::external`] over `internal`.
+    fn derivative(self, internal: Scalar) -> Scalar {
+        let one = Scalar::one();
+        let two = one + one;
+        match self {
+            Self::Free => one,
+            Self::Lower(_) => internal / (internal * internal + one).sqrt(),
+            Self::Upper(_) => -internal / (internal * internal + one).sqrt(),
+            Self::Both(min, max) => (max - min) / two * internal.cos(),
+        }
+    }
+}
+
+/// Model keeping `inner` model parameters within supplied bounds during the fit.
+///
+/// Bounded parameters are reparametrized with smooth transforms (same as in MINUIT): `sin` for two-sided bounds, and `sqrt` for one-sided ones. Optimizer only sees transformed ("internal") parameters, that can take any value, while `inner` model always has parameters within the bounds.
+///
+/// [`FitStat`](crate::FitStat) reports errors of the `inner` model parameters, propagated from the internal ones. Covariance matrix is left in terms of internal parameters.
+///
+/// Note, that transforms are flat at the bounds themselves, so parameters starting exactly at the bound won't be able to leave it. Initial values outside of the bounds are clamped to them.
+///
+/// ```rust
+/// # use approx::assert_abs_diff_eq;
+/// # use nacfahi::{models::{basic::Gaussian, utility::{Bounded, ParamBound}}, *};
+/// // gaussian with negative sigma is the same gaussian, so let's keep it positive
+/// let gaussian = Gaussian::<f64> { a: 1.0, x_c: 0.0, sigma: 2.0 };
+/// let mut bounded = Bounded::new(
+///     gaussian,
+///     [ParamBound::Free, ParamBound::Free, ParamBound::Lower(0.0)],
+/// );
+///
+/// let x = [-2.0, -1.0, 0.0, 1.0, 2.0, 3.0];
+/// let y = x.map(|x: f64| 3.0 * (-(x - 0.5).powi(2) / 2.0).exp() / (2.0 * core::f64::consts::PI).sqrt());
+/// let report = fit!(&mut bounded, x, y);
+///
+/// assert!(report.termination.was_successful());
+/// let gaussian = bounded.into_inner();
+/// assert_abs_diff_eq!(gaussian.sigma, 1.0, epsilon = 1e-6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bounded<Inner: FitModel> {
+    inner: Inner,
+    bounds: GenericArray<ParamBound<Inner::Scalar>, <Inner::ParamCount as Conv>::TNum>,
+    internal: GenericArray<Inner::Scalar, <Inner::ParamCount as Conv>::TNum>,
+}
+
+impl<Inner: FitModel> Bounded<Inner>
+where
+    Inner::Scalar: Float,
+{
+    /// Imposes `bounds` on `inner` model parameters, in order of [`FitModel::get_params`].
+    pub fn new(
+        mut inner: Inner,
+        bounds: impl Into<GenericArray<ParamBound<Inner::Scalar>, <Inner::ParamCount as Conv>::TNum>>,
+    ) -> Self {
+        let bounds = bounds.into();
+        let params: GenericArray<_, _> = inner.get_params().into();
+        let internal = params.zip(&bounds, |p, bound| bound.internal(p));
+        inner.set_params((&bounds).zip(&internal, |bound, &p| bound.external(p)));
+        Self {
+            inner,
+            bounds,
+            internal,
+        }
+    }
+
+    /// Bounded model.
+    pub fn inner(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Consumes the wrapper, returning bounded model.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    /// Imposed bounds.
+    pub fn bounds(
+        &self,
+    ) -> &GenericArray<ParamBound<Inner::Scalar>, <Inner::ParamCount as Conv>::TNum> {
+        &self.bounds
+    }
+}
+
+impl<Inner: FitModel> FitModel for Bounded<Inner>
+where
+    Inner::Scalar: Float,
+{
+    type Scalar = Inner::Scalar;
+    type ParamCount = Inner::ParamCount;
+
+    #[inline]
+    fn evaluate(&self, x: &Self::Scalar) -> Self::Scalar {
+        self.inner.evaluate(x)
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>> {
+        // chain rule: d/d(int) = d/d(ext) * d(ext)/d(int)
+        let jacobian: GenericArray<_, _> = self.inner.jacobian(x).into();
+        jacobian
+            .zip(&self.bounds, |d, bound| (d, *bound))
+            .zip(&self.internal, |(d, bound), &p| d * bound.derivative(p))
+    }
+
+    #[inline]
+    fn set_params(
+        &mut self,
+        new_params: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) {
+        self.inner
+            .set_params((&self.bounds).zip(&new_params, |bound, &p| bound.external(p)));
+        self.internal = new_params;
+    }
+
+    #[inline]
+    fn get_params(
+        &self,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>> {
+        self.internal.clone()
+    }
+}
+
+impl<Inner: FitModelXDeriv> FitModelXDeriv for Bounded<Inner>
+where
+    Inner::Scalar: Float,
+{
+    #[inline]
+    fn deriv_x(&self, x: &Self::Scalar) -> Self::Scalar {
+        self.inner.deriv_x(x)
+    }
+}
+
+impl<Inner: FitModelErrors> FitModelErrors for Bounded<Inner>
+where
+    Inner::Scalar: Float,
+{
+    type OwnedModel = Inner::OwnedModel;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) -> Self::OwnedModel {
+        // linear error propagation: σ(ext) = |d(ext)/d(int)| * σ(int)
+        let errors = errors
+            .zip(&self.bounds, |e, bound| (e, *bound))
+            .zip(&self.internal, |(e, bound), &p| {
+                e * bound.derivative(p).abs()
+            });
+        self.inner.with_errors(errors)
+    }
+
+    #[inline]
+    fn with_covariance(
+        &self,
+        covariance: &GenericMatrix<Self::Scalar, Self::ParamCount, Self::ParamCount>,
+    ) -> Self::OwnedModel
+    where
+        Self::Scalar: Float,
+    {
+        // C(ext) = D * C(int) * D, where D is a diagonal of d(ext)/d(int)
+        let derivatives = self.external_derivatives();
+        let covariance =
+            GenericMatrix::from_data(GenericArrayStorage(GenericArray::generate(|col| {
+                GenericArray::generate(|row| {
+                    derivatives[row] * covariance[(row, col)] * derivatives[col]
+                })
+            })));
+        self.inner.with_covariance(&covariance)
+    }
+
+    #[inline]
+    fn with_external_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) -> Self::OwnedModel {
+        // errors are already in inner model parameters
+        self.inner.with_errors(errors)
+    }
+
+    #[inline]
+    fn external_derivatives(&self) -> GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum> {
+        (&self.bounds).zip(&self.internal, |bound, &p| bound.derivative(p))
+    }
+
+    #[inline]
+    fn get_external_params(&self) -> GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum> {
+        self.inner.get_params().into()
+    }
+
+    #[inline]
+    fn set_external_params(
+        &mut self,
+        values: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) {
+        // values outside of the bounds are clamped
+        self.set_params(values.zip(&self.bounds, |v, bound| bound.internal(v)));
+    }
+}
+
+#[cfg(test)]
+mod tests;
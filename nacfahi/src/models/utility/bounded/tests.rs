@@ -0,0 +1,108 @@
+crate::test_model_derivative!(
+    lower,
+    Bounded<Gaussian<f64>>,
+    Bounded::new(
+        Gaussian::<f64> {
+            a: -3.0,
+            x_c: 1.0,
+            sigma: 0.3,
+        },
+        [ParamBound::Free, ParamBound::Free, ParamBound::Lower(0.1)],
+    ),
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
+
+crate::test_model_derivative!(
+    all_kinds,
+    Bounded<Gaussian<f64>>,
+    Bounded::new(
+        Gaussian::<f64> {
+            a: -3.0,
+            x_c: 1.0,
+            sigma: 0.3,
+        },
+        [
+            ParamBound::Upper(0.0),
+            ParamBound::Both(-1.0, 2.0),
+            ParamBound::Lower(0.1),
+        ],
+    ),
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
+
+crate::test_model_derivative!(
+    array,
+    Bounded<[Exponent<f64>; 2]>,
+    Bounded::new(
+        [Exponent { a: 1.0, b: -0.5 }, Exponent { a: 2.0, b: 0.3 }],
+        [
+            ParamBound::Lower(0.0),
+            ParamBound::Both(-1.0, 0.0),
+            ParamBound::Free,
+            ParamBound::Upper(1.0),
+        ],
+    ),
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
+
+#[test]
+fn transforms_roundtrip() {
+    use super::ParamBound;
+
+    let bounds = [
+        ParamBound::Free,
+        ParamBound::Lower(-2.0),
+        ParamBound::Upper(3.0),
+        ParamBound::Both(-1.0, 4.0),
+    ];
+    for bound in bounds {
+        for value in [-0.5, 0.0, 1.5, 2.5] {
+            let internal = bound.internal(value);
+            approx::assert_ulps_eq!(bound.external(internal), value, epsilon = 1e-12);
+        }
+        for internal in [-10.0, -1.0, 0.3, 7.0] {
+            let external = bound.external(internal);
+            match bound {
+                ParamBound::Free => {}
+                ParamBound::Lower(min) => assert!(external >= min),
+                ParamBound::Upper(max) => assert!(external <= max),
+                ParamBound::Both(min, max) => assert!((min..=max).contains(&external)),
+            }
+        }
+    }
+}
+
+#[test]
+fn clamps_initial_values() {
+    use super::{Bounded, ParamBound};
+    use crate::models::{FitModel, basic::Linear};
+
+    let bounded = Bounded::new(
+        Linear { a: -5.0, b: 10.0 },
+        [ParamBound::Lower(0.0), ParamBound::Both(0.0, 1.0)],
+    );
+    let [a, b] = bounded.inner().get_params().into().into_array();
+    approx::assert_ulps_eq!(a, 0.0);
+    approx::assert_ulps_eq!(b, 1.0);
+}
@@ -65,6 +65,7 @@ where
     type OwnedModel = ();
 
     fn with_errors(
+        &self,
         _errors: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
     ) -> Self::OwnedModel {
     }
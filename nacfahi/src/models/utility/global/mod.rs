@@ -0,0 +1,136 @@
+use alloc::vec::Vec;
+
+use generic_array::GenericArray;
+use generic_array_storage::Conv;
+
+use crate::models::FitModel;
+
+/// Several instances of the same model, fitted simultaneously to a dataset each, with some of the parameters shared between all of them.
+///
+/// For example, several spectra might share a linewidth, while having individual amplitudes. This type only defines a correspondence between per-dataset model parameters and parameters of the combined problem; see [`function@crate::fit_global`] for the fit itself.
+///
+/// Combined parameters are laid out as shared parameters first, followed by non-shared parameters of each model in order.
+#[derive(Debug, Clone)]
+pub struct GlobalFit<Model: FitModel> {
+    models: Vec<Model>,
+    shared: GenericArray<bool, <Model::ParamCount as Conv>::TNum>,
+}
+
+impl<Model: FitModel> GlobalFit<Model>
+where
+    Model::Scalar: Clone,
+{
+    /// Creates a combination of models, with parameters marked in `shared` being shared between all of them.
+    ///
+    /// Initial values of shared parameters are taken from the first model, and written to the rest of them.
+    pub fn new(
+        models: Vec<Model>,
+        shared: impl Into<GenericArray<bool, <Model::ParamCount as Conv>::TNum>>,
+    ) -> Self {
+        let mut res = Self {
+            models,
+            shared: shared.into(),
+        };
+        let params = res.get_params();
+        res.set_params(&params);
+        res
+    }
+
+    /// Per-dataset models.
+    pub fn models(&self) -> &[Model] {
+        &self.models
+    }
+
+    /// Consumes the combination, returning per-dataset models. Shared parameters are the same for all of them.
+    pub fn into_models(self) -> Vec<Model> {
+        self.models
+    }
+
+    /// Mask of shared parameters.
+    pub fn shared(&self) -> &GenericArray<bool, <Model::ParamCount as Conv>::TNum> {
+        &self.shared
+    }
+
+    fn shared_count(&self) -> usize {
+        self.shared.iter().filter(|&&s| s).count()
+    }
+
+    fn local_count(&self) -> usize {
+        self.shared.len() - self.shared_count()
+    }
+
+    /// Number of parameters of the combined problem.
+    pub fn param_count(&self) -> usize {
+        if self.models.is_empty() {
+            0
+        } else {
+            self.shared_count() + self.local_count() * self.models.len()
+        }
+    }
+
+    /// Index of `param`-th parameter of `dataset`-th model in the combined parameters.
+    ///
+    /// ### Panics
+    ///
+    /// - If `param` is out of model parameter bounds
+    pub fn param_index(&self, dataset: usize, param: usize) -> usize {
+        let preceding = self.shared[..param].iter().filter(|&&s| s).count();
+        if self.shared[param] {
+            preceding
+        } else {
+            self.shared_count() + dataset * self.local_count() + (param - preceding)
+        }
+    }
+
+    /// Returns current values of combined parameters.
+    pub fn get_params(&self) -> Vec<Model::Scalar> {
+        let mut res = Vec::with_capacity(self.param_count());
+        let mut models = self.models.iter().map(|model| {
+            let params: GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum> =
+                model.get_params().into();
+            params
+        });
+        let Some(first) = models.next() else {
+            return res;
+        };
+        res.extend(
+            first
+                .iter()
+                .zip(&self.shared)
+                .filter(|&(_, &s)| s)
+                .map(|(p, _)| p.clone()),
+        );
+        for params in core::iter::once(first).chain(models) {
+            res.extend(
+                params
+                    .into_iter()
+                    .zip(&self.shared)
+                    .filter(|&(_, &s)| !s)
+                    .map(|(p, _)| p),
+            );
+        }
+        res
+    }
+
+    /// Sets combined parameters, writing them to all of the models.
+    ///
+    /// ### Panics
+    ///
+    /// - If `params` length is not [`GlobalFit::param_count`]
+    pub fn set_params(&mut self, params: &[Model::Scalar]) {
+        assert_eq!(
+            params.len(),
+            self.param_count(),
+            "Combined parameters count mismatch"
+        );
+        for dataset in 0..self.models.len() {
+            let new_params = (0..self.shared.len())
+                .map(|param| params[self.param_index(dataset, param)].clone())
+                .collect();
+            self.models[dataset].set_params(new_params);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
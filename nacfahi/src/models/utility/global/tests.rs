@@ -0,0 +1,69 @@
+use alloc::vec;
+
+use crate::{
+    AsMatrixView,
+    dyn_problem::DynGlobalProblem,
+    models::{
+        FitModel,
+        basic::{Gaussian, Linear},
+    },
+};
+
+use super::GlobalFit;
+
+#[test]
+fn param_layout() {
+    let models = vec![
+        Linear { a: 1.0, b: 2.0 },
+        Linear { a: 3.0, b: 4.0 },
+        Linear { a: 5.0, b: 6.0 },
+    ];
+    let mut global = GlobalFit::new(models, [false, true]);
+
+    // shared `b` is taken from the first model
+    assert_eq!(global.param_count(), 4);
+    assert_eq!(global.get_params(), [2.0, 1.0, 3.0, 5.0]);
+    assert_eq!(global.param_index(0, 1), 0);
+    assert_eq!(global.param_index(2, 0), 3);
+
+    global.set_params(&[-1.0, 10.0, 20.0, 30.0]);
+    let models = global.into_models();
+    assert_eq!(models[1].a, 20.0);
+    assert!(models.iter().all(|model| model.b == -1.0));
+}
+
+#[test]
+#[cfg_attr(
+    miri,
+    ignore = "Miri gets angry because of stacked borrows rules somewhere inside nalgebra's storage"
+)]
+fn global_numeric_test() {
+    let model = Gaussian::<f64> {
+        a: 2.0,
+        x_c: 0.5,
+        sigma: 1.5,
+    };
+    let mut other = model;
+    other.set_params([-1.0, 1.0, 0.7].into());
+    let mut global = GlobalFit::new(vec![model, other], [false, true, false]);
+
+    let x1 = [0.0, 1.0, 2.0, 3.0];
+    let y1 = [1.0, -2.0, 0.5, 3.0];
+    let x2 = [-1.0, 0.5, 1.5, 2.5];
+    let y2 = [0.0, 2.0, -1.0, 1.0];
+    let mut problem = DynGlobalProblem {
+        global: &mut global,
+        data: vec![(x1.convert(), y1.convert()), (x2.convert(), y2.convert())],
+    };
+
+    let analytic = <DynGlobalProblem<'_, _, _> as levenberg_marquardt::LeastSquaresProblem<
+        _,
+        _,
+        _,
+    >>::jacobian(&problem)
+    .expect("Should be able to compute jacobian analytically");
+    let numerical = levenberg_marquardt::differentiate_numerically(&mut problem)
+        .expect("Should be able to compute jacobian numerically");
+
+    approx::assert_ulps_eq!(analytic, numerical, epsilon = 1e-6);
+}
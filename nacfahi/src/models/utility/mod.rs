@@ -1,13 +1,30 @@
 #[doc(hidden)]
+mod auto_diff;
+#[doc(hidden)]
+mod bounded;
+#[doc(hidden)]
 mod composition;
 #[doc(hidden)]
 mod fixed;
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+mod global;
 #[doc(hidden)]
 mod model_map;
 #[doc(hidden)]
+mod numeric_jacobian;
+#[doc(hidden)]
 mod ranged;
+#[doc(hidden)]
+mod tied;
 
+pub use auto_diff::*;
+pub use bounded::*;
 pub use composition::*;
 pub use fixed::*;
+#[cfg(feature = "alloc")]
+pub use global::*;
 pub use model_map::*;
+pub use numeric_jacobian::*;
 pub use ranged::*;
+pub use tied::*;
@@ -0,0 +1,270 @@
+use generic_array::{
+    ConstArrayLength, GenericArray, IntoArrayLength, sequence::GenericSequence, typenum::Const,
+};
+use generic_array_storage::Conv;
+use num_traits::Float;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv};
+
+/// Model defined by value evaluation only. Wrap it into [`NumericJacobian`] to get a [`FitModel`].
+///
+/// In case you'd rather not define a type, see [`FnModel`].
+pub trait NumericModel {
+    /// Type of `x`, `y` and model parameters.
+    type Scalar: Float;
+
+    /// Type representing number of parameters.
+    type ParamCount: Conv;
+
+    /// Computes model value for supplied `x` value and `params`.
+    fn evaluate(
+        &self,
+        params: &GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+        x: &Self::Scalar,
+    ) -> Self::Scalar;
+
+    /// Sets model parameters to ones contained in a generic array. Same as [`FitModel::set_params`].
+    fn set_params(
+        &mut self,
+        new_params: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    );
+
+    /// Returns current values of model params. Same as [`FitModel::get_params`].
+    fn get_params(&self)
+    -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>>;
+}
+
+/// [`NumericModel`] defined by a function of parameters and `x`.
+///
+/// Function receives parameters as a core Rust array, so these can be destructured in the signature.
+///
+/// ```rust
+/// # use approx::assert_relative_eq;
+/// # use nacfahi::{models::utility::{FnModel, NumericJacobian}, *};
+/// // a * x^b
+/// let power = FnModel::new([1.0, 1.0], |[a, b]: [f64; 2], x: f64| a * x.powf(b));
+/// let mut model = NumericJacobian::new(power);
+///
+/// let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let y = x.map(|x: f64| 3.0 * x.powf(1.5));
+/// let report = fit!(&mut model, x, y);
+///
+/// assert!(report.termination.was_successful());
+/// let [a, b] = model.inner.params;
+/// assert_relative_eq!(a, 3.0, max_relative = 1e-8);
+/// assert_relative_eq!(b, 1.5, max_relative = 1e-8);
+/// ```
+#[derive(Clone, Copy)]
+pub struct FnModel<Scalar, const N: usize, Function> {
+    /// Current parameter values
+    pub params: [Scalar; N],
+    /// Model function
+    pub function: Function,
+}
+
+impl<Scalar, const N: usize, Function> FnModel<Scalar, N, Function> {
+    /// Creates a model from initial parameter values and model `function`.
+    pub fn new(params: [Scalar; N], function: Function) -> Self {
+        Self { params, function }
+    }
+}
+
+impl<Scalar: core::fmt::Debug, const N: usize, Function> core::fmt::Debug
+    for FnModel<Scalar, N, Function>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FnModel")
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Scalar: Float, const N: usize, Function> NumericModel for FnModel<Scalar, N, Function>
+where
+    Const<N>: IntoArrayLength,
+    ConstArrayLength<N>: Conv<TNum = ConstArrayLength<N>>,
+    Function: Fn([Scalar; N], Scalar) -> Scalar,
+{
+    type Scalar = Scalar;
+    type ParamCount = ConstArrayLength<N>;
+
+    #[inline]
+    fn evaluate(
+        &self,
+        params: &GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+        x: &Self::Scalar,
+    ) -> Self::Scalar {
+        (self.function)(params.clone().into_array(), *x)
+    }
+
+    #[inline]
+    fn set_params(
+        &mut self,
+        new_params: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) {
+        self.params = new_params.into_array();
+    }
+
+    #[inline]
+    fn get_params(
+        &self,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>> {
+        GenericArray::from_array(self.params)
+    }
+}
+
+/// Finite difference step used by [`NumericJacobian`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumericStep<Scalar> {
+    /// Step is `h * max(|p|, 1)` for a parameter value `p`.
+    Relative(Scalar),
+    /// Step is `h` regardless of parameter value.
+    Absolute(Scalar),
+}
+
+impl<Scalar: Float> NumericStep<Scalar> {
+    /// Step size at `value`.
+    #[inline]
+    pub fn at(&self, value: Scalar) -> Scalar {
+        match *self {
+            Self::Relative(h) => h * value.abs().max(Scalar::one()),
+            Self::Absolute(h) => h,
+        }
+    }
+}
+
+impl<Scalar: Float> Default for NumericStep<Scalar> {
+    /// Relative step of `cbrt(epsilon)`, which balances truncation and rounding errors of a central difference.
+    fn default() -> Self {
+        Self::Relative(Scalar::epsilon().cbrt())
+    }
+}
+
+/// Model computing jacobian and `x` derivative of [`NumericModel`] via central finite differences.
+///
+/// Each derivative costs two model evaluations, or four with [`NumericJacobian::richardson`] extrapolation enabled. Consider [`AutoDiff`](super::AutoDiff) in case your model can be evaluated generically - it's both faster and exact.
+///
+/// See [`FnModel`] for an example.
+#[derive(Debug, Clone, Copy)]
+pub struct NumericJacobian<Model: NumericModel> {
+    /// Model being wrapped
+    pub inner: Model,
+    /// Finite difference step
+    pub step: NumericStep<Model::Scalar>,
+    /// If set, derivatives are refined with one step of [Richardson extrapolation](https://en.wikipedia.org/wiki/Richardson_extrapolation), combining differences with steps `h` and `h/2`.
+    pub richardson: bool,
+}
+
+impl<Model: NumericModel> NumericJacobian<Model> {
+    /// Wraps the model, using [default](NumericStep::default) step and no extrapolation.
+    pub fn new(inner: Model) -> Self {
+        Self {
+            inner,
+            step: NumericStep::default(),
+            richardson: false,
+        }
+    }
+
+    /// Sets finite difference step.
+    #[must_use]
+    pub fn with_step(self, step: NumericStep<Model::Scalar>) -> Self {
+        Self { step, ..self }
+    }
+
+    /// Enables Richardson extrapolation.
+    #[must_use]
+    pub fn with_richardson(self) -> Self {
+        Self {
+            richardson: true,
+            ..self
+        }
+    }
+}
+
+/// Central difference of `f` at `at` with step `step`, optionally extrapolated.
+#[inline]
+fn central_difference<Scalar: Float>(
+    f: impl Fn(Scalar) -> Scalar,
+    at: Scalar,
+    step: Scalar,
+    richardson: bool,
+) -> Scalar {
+    let two = Scalar::one() + Scalar::one();
+    let diff = |h: Scalar| (f(at + h) - f(at - h)) / (two * h);
+    let coarse = diff(step);
+    if richardson {
+        // error is O(h^2), so (4 D(h/2) - D(h)) / 3 cancels it out
+        let fine = diff(step / two);
+        fine + (fine - coarse) / (two + Scalar::one())
+    } else {
+        coarse
+    }
+}
+
+impl<Model: NumericModel> FitModel for NumericJacobian<Model> {
+    type Scalar = Model::Scalar;
+    type ParamCount = Model::ParamCount;
+
+    #[inline]
+    fn evaluate(&self, x: &Self::Scalar) -> Self::Scalar {
+        let params = self.inner.get_params().into();
+        self.inner.evaluate(&params, x)
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>> {
+        let params: GenericArray<_, _> = self.inner.get_params().into();
+        GenericArray::generate(|i| {
+            let f = |value| {
+                let mut shifted = params.clone();
+                shifted[i] = value;
+                self.inner.evaluate(&shifted, x)
+            };
+            central_difference(f, params[i], self.step.at(params[i]), self.richardson)
+        })
+    }
+
+    #[inline]
+    fn set_params(
+        &mut self,
+        new_params: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) {
+        self.inner.set_params(new_params);
+    }
+
+    #[inline]
+    fn get_params(
+        &self,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>> {
+        self.inner.get_params()
+    }
+}
+
+impl<Model: NumericModel> FitModelXDeriv for NumericJacobian<Model> {
+    #[inline]
+    fn deriv_x(&self, x: &Self::Scalar) -> Self::Scalar {
+        let params = self.inner.get_params().into();
+        let f = |x| self.inner.evaluate(&params, &x);
+        central_difference(f, *x, self.step.at(*x), self.richardson)
+    }
+}
+
+impl<Model: NumericModel + Clone + 'static> FitModelErrors for NumericJacobian<Model> {
+    type OwnedModel = Model;
+
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) -> Self::OwnedModel {
+        let mut errors_model = self.inner.clone();
+        errors_model.set_params(errors);
+        errors_model
+    }
+}
+
+#[cfg(test)]
+mod tests;
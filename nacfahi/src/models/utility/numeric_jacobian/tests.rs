@@ -0,0 +1,61 @@
+use super::{FnModel, NumericJacobian, NumericStep};
+
+type Expo = FnModel<f64, 2, fn([f64; 2], f64) -> f64>;
+
+fn expo() -> Expo {
+    FnModel::new([-2.0, -1.5], |[a, b], x| a * (b * x).exp())
+}
+
+crate::test_model_derivative!(
+    central,
+    NumericJacobian<Expo>,
+    NumericJacobian::new(expo()),
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
+
+crate::test_model_derivative!(
+    richardson,
+    NumericJacobian<Expo>,
+    NumericJacobian::new(expo())
+        .with_step(NumericStep::Absolute(1e-3))
+        .with_richardson(),
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
+
+#[test]
+fn richardson_is_more_precise() {
+    use crate::models::{FitModel, FitModelXDeriv};
+    use generic_array::GenericArray;
+
+    let plain = NumericJacobian::new(expo()).with_step(NumericStep::Absolute(1e-2));
+    let extrapolated = plain.with_richardson();
+
+    let x = 1.3;
+    let exact_a = (-1.5f64 * x).exp();
+    let plain_jacobian: GenericArray<f64, _> = plain.jacobian(&x).into();
+    let extrapolated_jacobian: GenericArray<f64, _> = extrapolated.jacobian(&x).into();
+    assert!((extrapolated_jacobian[0] - exact_a).abs() < 1e-12);
+    let exact_b = -2.0 * x * exact_a;
+    assert!(
+        (extrapolated_jacobian[1] - exact_b).abs() < (plain_jacobian[1] - exact_b).abs() / 100.0
+    );
+
+    let exact_x = -2.0 * -1.5 * exact_a;
+    assert!(
+        (extrapolated.deriv_x(&x) - exact_x).abs() < (plain.deriv_x(&x) - exact_x).abs() / 100.0
+    );
+}
@@ -0,0 +1,388 @@
+use generic_array::{GenericArray, functional::FunctionalSequence, sequence::GenericSequence};
+use generic_array_storage::{Conv, GenericArrayStorage, GenericMatrix};
+use num_traits::{Float, One, Zero};
+use typenum::Unsigned;
+
+use crate::models::{FitModel, FitModelErrors, FitModelXDeriv};
+
+/// Relation of a single parameter to the rest of them, used by [`Tied`] model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Tie<Scalar> {
+    /// Parameter is fitted as usual.
+    #[default]
+    Free,
+    /// Parameter equals `scale * p[source] + offset`, where `p[source]` is a [`Tie::Free`] parameter.
+    Linear {
+        /// Index of the parameter this one depends on.
+        source: usize,
+        /// Multiplier of the source parameter.
+        scale: Scalar,
+        /// Value added to the scaled source parameter.
+        offset: Scalar,
+    },
+}
+
+/// Matrix with a row of reduced parameter coefficients per `Inner` model parameter.
+type TieMatrix<Inner, Reduced> = GenericArray<
+    GenericArray<<Inner as FitModel>::Scalar, <Reduced as Conv>::TNum>,
+    <<Inner as FitModel>::ParamCount as Conv>::TNum,
+>;
+
+/// Model imposing linear relations between `inner` model parameters, so that only `Reduced` independent parameters are actually fitted.
+///
+/// Inner model parameters are computed as `matrix * reduced + offset`, where `matrix` has a row per `inner` model parameter. Most of the time, you can describe the relations with [`Tie`]s, see [`Tied::new`].
+///
+/// [`FitStat`](crate::FitStat) reports errors of all the `inner` model parameters, with errors of dependent ones propagated from the covariance matrix. Covariance matrix itself is left in terms of reduced parameters.
+///
+/// ```rust
+/// # use approx::assert_ulps_eq;
+/// # use nacfahi::{models::{FitModel, basic::Gaussian, utility::{Tie, Tied}}, *};
+/// // two peaks with fixed distance between them, and a second one being twice as high
+/// let peaks = [
+///     Gaussian::<f64> { a: 1.0, x_c: 0.0, sigma: 1.0 },
+///     Gaussian::<f64> { a: 2.0, x_c: 3.2, sigma: 1.0 },
+/// ];
+/// let mut tied = Tied::<_, U<4>>::new(
+///     peaks,
+///     [
+///         Tie::Free,
+///         Tie::Free,
+///         Tie::Free,
+///         Tie::Linear { source: 0, scale: 2.0, offset: 0.0 },
+///         Tie::Linear { source: 1, scale: 1.0, offset: 3.2 },
+///         Tie::Free,
+///     ],
+/// );
+///
+/// let truth = [
+///     Gaussian::<f64> { a: 0.5, x_c: -1.0, sigma: 0.8 },
+///     Gaussian::<f64> { a: 1.0, x_c: 2.2, sigma: 1.2 },
+/// ];
+/// let x: [f64; 20] = core::array::from_fn(|i| -4.0 + i as f64 * 0.5);
+/// let y = x.map(|x| truth.iter().map(|g| g.evaluate(&x)).sum::<f64>());
+///
+/// let report = fit!(&mut tied, x, y);
+/// assert!(report.termination.was_successful());
+///
+/// let [first, second] = tied.into_inner();
+/// assert_ulps_eq!(second.x_c - first.x_c, 3.2);
+/// assert_ulps_eq!(second.a / first.a, 2.0);
+/// assert_ulps_eq!(second.sigma, 1.2, epsilon = 1e-9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tied<Inner: FitModel, Reduced: Conv> {
+    inner: Inner,
+    matrix: TieMatrix<Inner, Reduced>,
+    offset: GenericArray<Inner::Scalar, <Inner::ParamCount as Conv>::TNum>,
+    reduced: GenericArray<Inner::Scalar, Reduced::TNum>,
+}
+
+impl<Inner: FitModel, Reduced: Conv> Tied<Inner, Reduced>
+where
+    Inner::Scalar: Float,
+{
+    /// Imposes `ties` on `inner` model parameters, in order of [`FitModel::get_params`].
+    ///
+    /// Initial values of free parameters are taken from the `inner` model, and dependent ones are immediately recomputed from them.
+    ///
+    /// ### Panics
+    ///
+    /// - If number of [`Tie::Free`] parameters is not `Reduced`
+    /// - If [`Tie::Linear`] refers to a parameter out of bounds
+    /// - If [`Tie::Linear`] refers to non-free parameter
+    pub fn new(
+        inner: Inner,
+        ties: impl Into<GenericArray<Tie<Inner::Scalar>, <Inner::ParamCount as Conv>::TNum>>,
+    ) -> Self {
+        let ties = ties.into();
+        let param_count = ties.len();
+        for tie in &ties {
+            if let Tie::Linear { source, .. } = *tie {
+                assert!(
+                    source < param_count,
+                    "Parameters can only depend on existing ones"
+                );
+            }
+        }
+        let reduced_count = <Reduced::TNum as Unsigned>::USIZE;
+        let free_count = ties.iter().filter(|tie| matches!(tie, Tie::Free)).count();
+        assert_eq!(
+            free_count, reduced_count,
+            "Number of free parameters should match reduced parameter count"
+        );
+
+        // index of each free parameter among reduced ones
+        let reduced_index = |param: usize| {
+            assert!(
+                matches!(ties[param], Tie::Free),
+                "Parameters can only depend on free ones"
+            );
+            ties[..param]
+                .iter()
+                .filter(|tie| matches!(tie, Tie::Free))
+                .count()
+        };
+        let zero = Inner::Scalar::zero();
+        let one = Inner::Scalar::one();
+        let matrix = GenericArray::generate(|param| {
+            let (source, scale) = match ties[param] {
+                Tie::Free => (param, one),
+                Tie::Linear { source, scale, .. } => (source, scale),
+            };
+            let source = reduced_index(source);
+            GenericArray::generate(|i| if i == source { scale } else { zero })
+        });
+        let offset = (&ties).map(|tie| match *tie {
+            Tie::Free => zero,
+            Tie::Linear { offset, .. } => offset,
+        });
+
+        let params: GenericArray<_, _> = inner.get_params().into();
+        let mut free = params
+            .into_iter()
+            .zip(&ties)
+            .filter(|(_, tie)| matches!(tie, Tie::Free))
+            .map(|(p, _)| p);
+        let reduced = GenericArray::generate(|_| free.next().expect("Checked above"));
+
+        Self::from_matrix(inner, matrix, offset, reduced)
+    }
+
+    /// Imposes arbitrary linear relations on `inner` model parameters: `inner` parameters are set to `matrix * reduced + offset`, with `reduced` being the initial values of fitted parameters.
+    pub fn from_matrix(
+        mut inner: Inner,
+        matrix: TieMatrix<Inner, Reduced>,
+        offset: GenericArray<Inner::Scalar, <Inner::ParamCount as Conv>::TNum>,
+        reduced: GenericArray<Inner::Scalar, Reduced::TNum>,
+    ) -> Self {
+        inner.set_params(expand::<_, Inner::ParamCount, Reduced>(
+            &matrix, &offset, &reduced,
+        ));
+        Self {
+            inner,
+            matrix,
+            offset,
+            reduced,
+        }
+    }
+
+    /// Tied model.
+    pub fn inner(&self) -> &Inner {
+        &self.inner
+    }
+
+    /// Consumes the wrapper, returning tied model.
+    pub fn into_inner(self) -> Inner {
+        self.inner
+    }
+
+    /// Index of `inner` parameter equal to each of the reduced ones, if there is any.
+    ///
+    /// For ties made with [`Tied::new`], these are just [`Tie::Free`] parameters.
+    fn sources(&self) -> GenericArray<Option<usize>, Reduced::TNum> {
+        let zero = Inner::Scalar::zero();
+        let one = Inner::Scalar::one();
+        GenericArray::generate(|reduced| {
+            self.matrix
+                .iter()
+                .zip(&self.offset)
+                .position(|(row, &offset)| {
+                    offset == zero
+                        && row
+                            .iter()
+                            .enumerate()
+                            .all(|(i, &a)| if i == reduced { a == one } else { a == zero })
+                })
+        })
+    }
+}
+
+/// Computes `matrix * reduced + offset`.
+fn expand<Scalar: Float, Full: Conv, Reduced: Conv>(
+    matrix: &GenericArray<GenericArray<Scalar, Reduced::TNum>, Full::TNum>,
+    offset: &GenericArray<Scalar, Full::TNum>,
+    reduced: &GenericArray<Scalar, Reduced::TNum>,
+) -> GenericArray<Scalar, Full::TNum> {
+    matrix.zip(offset, |row, &b| {
+        row.iter().zip(reduced).fold(b, |acc, (&a, &r)| acc + a * r)
+    })
+}
+
+impl<Inner: FitModel, Reduced: Conv> FitModel for Tied<Inner, Reduced>
+where
+    Inner::Scalar: Float,
+{
+    type Scalar = Inner::Scalar;
+    type ParamCount = Reduced;
+
+    #[inline]
+    fn evaluate(&self, x: &Self::Scalar) -> Self::Scalar {
+        self.inner.evaluate(x)
+    }
+
+    #[inline]
+    fn jacobian(
+        &self,
+        x: &Self::Scalar,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>> {
+        // chain rule: d/d(reduced) = d/d(full) * matrix
+        let jacobian: GenericArray<_, <Inner::ParamCount as Conv>::TNum> =
+            self.inner.jacobian(x).into();
+        GenericArray::<_, Reduced::TNum>::generate(|i| {
+            jacobian
+                .iter()
+                .zip(&self.matrix)
+                .fold(Inner::Scalar::zero(), |acc, (&d, row)| acc + d * row[i])
+        })
+    }
+
+    #[inline]
+    fn set_params(
+        &mut self,
+        new_params: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) {
+        self.inner
+            .set_params(expand::<_, Inner::ParamCount, Reduced>(
+                &self.matrix,
+                &self.offset,
+                &new_params,
+            ));
+        self.reduced = new_params;
+    }
+
+    #[inline]
+    fn get_params(
+        &self,
+    ) -> impl Into<GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>> {
+        self.reduced.clone()
+    }
+}
+
+impl<Inner: FitModelXDeriv, Reduced: Conv> FitModelXDeriv for Tied<Inner, Reduced>
+where
+    Inner::Scalar: Float,
+{
+    #[inline]
+    fn deriv_x(&self, x: &Self::Scalar) -> Self::Scalar {
+        self.inner.deriv_x(x)
+    }
+}
+
+impl<Inner: FitModelErrors, Reduced: Conv> FitModelErrors for Tied<Inner, Reduced>
+where
+    Inner::Scalar: Float,
+{
+    type OwnedModel = Inner::OwnedModel;
+
+    /// Assumes reduced parameters to be uncorrelated. [`FitModelErrors::with_covariance`] should be preferred.
+    #[inline]
+    fn with_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) -> Self::OwnedModel {
+        let errors = (&self.matrix).map(|row| {
+            row.iter()
+                .zip(&errors)
+                .fold(Inner::Scalar::zero(), |acc, (&a, &e)| acc + a * a * e * e)
+                .sqrt()
+        });
+        self.inner.with_errors(errors)
+    }
+
+    #[inline]
+    fn with_covariance(
+        &self,
+        covariance: &GenericMatrix<Self::Scalar, Self::ParamCount, Self::ParamCount>,
+    ) -> Self::OwnedModel
+    where
+        Self::Scalar: Float,
+    {
+        // C(full) = A * C(reduced) * A^T
+        let zero = Inner::Scalar::zero();
+        let covariance =
+            GenericMatrix::from_data(GenericArrayStorage(GenericArray::generate(|col| {
+                GenericArray::generate(|row| {
+                    let a_row: &GenericArray<_, _> = &self.matrix[row];
+                    let a_col: &GenericArray<_, _> = &self.matrix[col];
+                    let mut acc = zero;
+                    for (i, &a_ri) in a_row.iter().enumerate() {
+                        for (j, &a_cj) in a_col.iter().enumerate() {
+                            acc = acc + a_ri * covariance[(i, j)] * a_cj;
+                        }
+                    }
+                    acc
+                })
+            })));
+        self.inner.with_covariance(&covariance)
+    }
+
+    /// Assumes reduced parameters to be uncorrelated, same as [`FitModelErrors::with_errors`].
+    #[inline]
+    fn with_external_errors(
+        &self,
+        errors: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) -> Self::OwnedModel {
+        let sources = self.sources();
+        let derivatives = self.inner.external_derivatives();
+        // dependent parameters are tied in fitted values, so errors are propagated through them
+        let fitted = (&errors).zip(&sources, |&e, source| match *source {
+            Some(param) => e / derivatives[param].abs(),
+            None => e,
+        });
+        let mut full = (&self.matrix).zip(&derivatives, |row, &derivative| {
+            row.iter()
+                .zip(&fitted)
+                .fold(Inner::Scalar::zero(), |acc, (&a, &e)| acc + a * a * e * e)
+                .sqrt()
+                * derivative.abs()
+        });
+        // source parameters are reduced ones themselves, so their errors need no propagation
+        for (&e, source) in errors.iter().zip(&sources) {
+            if let Some(param) = *source {
+                full[param] = e;
+            }
+        }
+        self.inner.with_external_errors(full)
+    }
+
+    #[inline]
+    fn external_derivatives(&self) -> GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum> {
+        let derivatives = self.inner.external_derivatives();
+        self.sources()
+            .map(|source| source.map_or_else(Inner::Scalar::one, |param| derivatives[param]))
+    }
+
+    /// Reduced parameters without an `inner` parameter equal to them (see [`Tied::from_matrix`]) are left as they are.
+    #[inline]
+    fn get_external_params(&self) -> GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum> {
+        let external = self.inner.get_external_params();
+        self.sources().zip(&self.reduced, |source, &value| {
+            source.map_or(value, |param| external[param])
+        })
+    }
+
+    #[inline]
+    fn set_external_params(
+        &mut self,
+        values: GenericArray<Self::Scalar, <Self::ParamCount as Conv>::TNum>,
+    ) {
+        let sources = self.sources();
+        let mut external = self.inner.get_external_params();
+        for (&value, source) in values.iter().zip(&sources) {
+            if let Some(param) = *source {
+                external[param] = value;
+            }
+        }
+        // inner model converts actual values into fitted ones, and then ties are reimposed from them
+        self.inner.set_external_params(external);
+        let fitted: GenericArray<_, <Inner::ParamCount as Conv>::TNum> =
+            self.inner.get_params().into();
+        let reduced = values.zip(&sources, |value, source| {
+            source.map_or(value, |param| fitted[param])
+        });
+        self.set_params(reduced);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,159 @@
+crate::test_model_derivative!(
+    ties,
+    Tied<[Gaussian<f64>; 2], typenum::U4>,
+    Tied::new(
+        [
+            Gaussian::<f64> {
+                a: -3.0,
+                x_c: 1.0,
+                sigma: 0.3,
+            },
+            Gaussian::<f64> {
+                a: 1.0,
+                x_c: 2.0,
+                sigma: 0.7,
+            },
+        ],
+        [
+            Tie::Free,
+            Tie::Free,
+            Tie::Free,
+            Tie::Linear {
+                source: 0,
+                scale: -0.5,
+                offset: 0.0,
+            },
+            Tie::Linear {
+                source: 1,
+                scale: 1.0,
+                offset: 1.5,
+            },
+            Tie::Free,
+        ],
+    ),
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
+
+crate::test_model_derivative!(
+    matrix,
+    Tied<Polynomial<3, f64>, typenum::U2>,
+    Tied::from_matrix(
+        Polynomial { params: [0.0; 3] },
+        [[1.0, 0.0], [0.5, 2.0], [-1.0, 1.0]].map(Into::into).into(),
+        [0.0, 1.0, -2.0].into(),
+        [1.5, -0.5].into(),
+    ),
+    [
+        (0.0, -1.0),
+        (1.0, -4.0),
+        (2.0, -5.0),
+        (3.0, 6.0),
+        (4.0, 2.0),
+        (5.0, 2.5)
+    ]
+);
+
+#[test]
+fn applies_ties() {
+    use super::{Tie, Tied};
+    use crate::models::{FitModel, basic::Linear};
+
+    let mut tied = Tied::<_, typenum::U1>::new(
+        Linear { a: 2.0, b: 0.0 },
+        [
+            Tie::Free,
+            Tie::Linear {
+                source: 0,
+                scale: 3.0,
+                offset: -1.0,
+            },
+        ],
+    );
+    approx::assert_ulps_eq!(tied.inner().b, 5.0);
+
+    tied.set_params([-1.0].into());
+    approx::assert_ulps_eq!(tied.inner().a, -1.0);
+    approx::assert_ulps_eq!(tied.inner().b, -4.0);
+}
+
+#[test]
+#[should_panic(expected = "Parameters can only depend on free ones")]
+fn chained_ties() {
+    use super::{Tie, Tied};
+    use crate::models::basic::Polynomial;
+
+    let _ = Tied::<_, typenum::U1>::new(
+        Polynomial { params: [0.0; 3] },
+        [
+            Tie::Free,
+            Tie::Linear {
+                source: 0,
+                scale: 1.0,
+                offset: 0.0,
+            },
+            Tie::Linear {
+                source: 1,
+                scale: 1.0,
+                offset: 0.0,
+            },
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Parameters can only depend on existing ones")]
+fn missing_source() {
+    use super::{Tie, Tied};
+    use crate::models::basic::Linear;
+
+    let _ = Tied::<_, typenum::U1>::new(
+        Linear { a: 2.0, b: 0.0 },
+        [
+            Tie::Free,
+            Tie::Linear {
+                source: 2,
+                scale: 1.0,
+                offset: 0.0,
+            },
+        ],
+    );
+}
+
+#[test]
+fn external_params() {
+    use super::{Tie, Tied};
+    use crate::models::{
+        FitModel, FitModelErrors,
+        basic::Linear,
+        utility::{Bounded, ParamBound},
+    };
+
+    let mut tied = Tied::<_, typenum::U1>::new(
+        Bounded::new(
+            Linear { a: 2.0, b: 0.0 },
+            [ParamBound::Lower(1.0), ParamBound::Free],
+        ),
+        [
+            Tie::Free,
+            Tie::Linear {
+                source: 0,
+                scale: 3.0,
+                offset: -1.0,
+            },
+        ],
+    );
+    // fitted parameter is transformed by the bound, but actual one is the slope itself
+    approx::assert_ulps_eq!(tied.get_external_params()[0], 2.0);
+
+    tied.set_external_params([4.0].into());
+    approx::assert_ulps_eq!(tied.inner().inner().a, 4.0);
+    let [fitted]: [f64; 1] = tied.get_params().into().into_array();
+    approx::assert_ulps_eq!(tied.inner().inner().b, 3.0 * fitted - 1.0);
+}
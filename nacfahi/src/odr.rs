@@ -0,0 +1,207 @@
+use core::borrow::Borrow;
+
+use generic_array::sequence::GenericSequence;
+use nalgebra::allocator::Allocator;
+use nalgebra::{DefaultAllocator, Dim, MatrixView, OVector};
+use num_traits::{Float, Zero};
+
+use crate::models::{FitModelErrors, FitModelXDeriv, LevMarModel};
+use crate::{
+    AsMatrixView, DataPoints, FitBound, FitErrBound, FitStat, FitterUnit, GenericArray,
+    LevenbergMarquardt, MinimizationReport, scalar_weights,
+};
+
+/// Maximum number of effective variance iterations performed by orthogonal distance regression.
+const ODR_ITERATIONS: usize = 50;
+
+/// Result of orthogonal distance regression (see [`function@fit_odr`]).
+#[derive(Debug)]
+pub struct OdrFit<Result, Scalar: nalgebra::Scalar, Points: Dim>
+where
+    DefaultAllocator: Allocator<Points>,
+{
+    /// Result of the final fit iteration: either [`MinimizationReport`], or [`FitStat`].
+    pub result: Result,
+    /// Effective errors of the data points, combining `y` errors with `x` errors propagated through the fitted model.
+    pub sigma: OVector<Scalar, Points>,
+    /// Number of fit iterations performed.
+    pub iterations: usize,
+}
+
+/// Effective variance errors $\sqrt{\sigma_{y}^{2} + (f'(x) \sigma_{x})^{2}}$ for the current model state.
+fn effective_sigma<Model, Points: Dim>(
+    model: &Model,
+    x: &MatrixView<'_, Model::Scalar, Points, nalgebra::U1>,
+    x_sigma: &MatrixView<'_, Model::Scalar, Points, nalgebra::U1>,
+    y_sigma: &MatrixView<'_, Model::Scalar, Points, nalgebra::U1>,
+) -> OVector<Model::Scalar, Points>
+where
+    Model: FitModelXDeriv + LevMarModel,
+    DefaultAllocator: Allocator<Points>,
+{
+    OVector::from_fn_generic(x.shape_generic().0, nalgebra::U1, |i, _| {
+        let propagated = FitModelXDeriv::deriv_x(model, &x[i]) * x_sigma[i];
+        Float::hypot(y_sigma[i], propagated)
+    })
+}
+
+/// Effective variance method: fits with `x` errors propagated into `y` ones, until these stop changing.
+fn odr_fit<Model, X, Y>(
+    model: &mut Model,
+    x: &X,
+    y: &Y,
+    x_sigma: &MatrixView<'_, Model::Scalar, DataPoints<X>, nalgebra::U1>,
+    y_sigma: &MatrixView<'_, Model::Scalar, DataPoints<X>, nalgebra::U1>,
+    minimizer: &LevenbergMarquardt<Model::Scalar>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> OdrFit<MinimizationReport<Model::Scalar>, Model::Scalar, DataPoints<X>>
+where
+    Model: FitModelXDeriv + LevMarModel,
+    FitterUnit: FitBound<Model, X, Y, Points = DataPoints<X>>,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    DefaultAllocator: Allocator<DataPoints<X>>,
+{
+    let x_view = x.convert();
+    let points = x_view.shape_generic().0;
+    let tolerance = Float::cbrt(Model::Scalar::epsilon());
+
+    let mut sigma = effective_sigma(model, &x_view, x_sigma, y_sigma);
+    let mut iterations = 0;
+    loop {
+        let report = FitterUnit::fit(
+            minimizer,
+            &mut *model,
+            x,
+            y,
+            Some(MatrixView::from_slice_generic(
+                sigma.as_slice(),
+                points,
+                nalgebra::U1,
+            )),
+            scalar_weights(&weights),
+        );
+        iterations += 1;
+
+        let updated = effective_sigma(model, &x_view, x_sigma, y_sigma);
+        let change = updated
+            .iter()
+            .zip(sigma.iter())
+            .fold(Model::Scalar::zero(), |acc, (&new, &old)| {
+                Float::max(acc, Float::abs(new - old) / old)
+            });
+        sigma = updated;
+
+        if change <= tolerance
+            || iterations >= ODR_ITERATIONS
+            || !report.termination.was_successful()
+        {
+            return OdrFit {
+                result: report,
+                sigma,
+                iterations,
+            };
+        }
+    }
+}
+
+/// Same as [`function@crate::fit_sigma`], but both `x` and `y` data have known errors (standard deviations) `x_sigma` and `y_sigma`.
+///
+/// This implements effective variance method: `x` errors are propagated into `y` ones as $\sigma_{i}^{2} = \sigma_{y,i}^{2} + (f'(x_{i}) \sigma_{x,i})^{2}$ using model derivative over `x` (see [`FitModelXDeriv`]), and the fit is repeated with updated effective errors until these stop changing, or for at most 50 iterations.
+///
+/// ```rust
+/// # use approx::assert_abs_diff_eq;
+/// # use nacfahi::{models::basic::Linear, *};
+/// let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+/// let y = [1.1, 2.9, 5.1, 6.9, 9.0];
+/// let x_sigma = [0.1; 5];
+/// let y_sigma = [0.1; 5];
+///
+/// let mut line = Linear { a: 0.0, b: 0.0 };
+/// let odr = fit_odr(&mut line, x, y, x_sigma, y_sigma, LevenbergMarquardt::new(), default_weights);
+///
+/// assert!(odr.result.termination.was_successful());
+/// // slope of the line amplifies `x` errors
+/// assert_abs_diff_eq!(odr.sigma[0], 0.1 * (1.0 + line.a * line.a).sqrt(), epsilon = 1e-6);
+/// ```
+#[must_use = "Minimization report is really important to check if approximation happened at all"]
+pub fn fit_odr<Model, X, Y, XSigma, YSigma>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    x_sigma: XSigma,
+    y_sigma: YSigma,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> OdrFit<MinimizationReport<Model::Scalar>, Model::Scalar, DataPoints<X>>
+where
+    Model: FitModelXDeriv + LevMarModel,
+    FitterUnit: FitBound<Model, X, Y, Points = DataPoints<X>>,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    XSigma: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    YSigma: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    DefaultAllocator: Allocator<DataPoints<X>>,
+{
+    odr_fit(
+        model,
+        &x,
+        &y,
+        &x_sigma.convert(),
+        &y_sigma.convert(),
+        minimizer.borrow(),
+        weights,
+    )
+}
+
+/// Same as [`function@crate::fit_stat_sigma`], but both `x` and `y` data have known errors (see [`function@fit_odr`]).
+///
+/// Statistics are computed with the final effective errors, so parameter errors account for both `x` and `y` errors.
+#[must_use = "Fit statistics are the only point to call this function specifically"]
+pub fn fit_stat_odr<Model, X, Y, XSigma, YSigma>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    x_sigma: XSigma,
+    y_sigma: YSigma,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> OdrFit<FitStat<Model>, Model::Scalar, DataPoints<X>>
+where
+    Model: FitModelErrors + FitModelXDeriv + LevMarModel,
+    FitterUnit: FitErrBound<Model, X, Y, Points = DataPoints<X>>,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    XSigma: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    YSigma: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    DefaultAllocator: Allocator<DataPoints<X>>,
+{
+    let odr = odr_fit(
+        model,
+        &x,
+        &y,
+        &x_sigma.convert(),
+        &y_sigma.convert(),
+        minimizer.borrow(),
+        &weights,
+    );
+    let stat = FitterUnit::produce_stat(
+        model,
+        odr.result,
+        x,
+        y,
+        Some(MatrixView::from_slice_generic(
+            odr.sigma.as_slice(),
+            odr.sigma.shape_generic().0,
+            nalgebra::U1,
+        )),
+        true,
+        weights,
+        &GenericArray::generate(|_| false),
+    );
+    OdrFit {
+        result: stat,
+        sigma: odr.sigma,
+        iterations: odr.iterations,
+    }
+}
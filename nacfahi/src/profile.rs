@@ -0,0 +1,275 @@
+use core::borrow::Borrow;
+
+use generic_array::sequence::GenericSequence;
+use generic_array_storage::Conv;
+use nalgebra::{Dim, Dyn, MatrixView, OMatrix};
+use num_traits::{Float, NumCast, One, Zero};
+use typenum::Unsigned;
+
+use crate::dyn_problem::DynFrozenProblem;
+use crate::models::{FitModel, LevMarModel};
+use crate::{AsMatrixView, DataPoints, GenericArray, LevenbergMarquardt, MinimizationReport};
+
+/// Maximum number of times profile search step is doubled before parameter is considered unbounded.
+const PROFILE_STEPS: usize = 30;
+
+/// Maximum number of bisection iterations locating profile crossing.
+const PROFILE_BISECTIONS: usize = 50;
+
+/// Result of profile likelihood analysis (see [`function@fit_profile`]).
+#[derive(Debug)]
+pub struct Profile<Model: LevMarModel> {
+    /// Report of the initial (unconstrained) fit
+    pub report: MinimizationReport<Model::Scalar>,
+    /// Minimal $\chi^{2}$ of the initial fit
+    pub chi2: Model::Scalar,
+    /// Distance from the best fit value to the lower interval bound for each parameter, in order of [`FitModel::get_params`].
+    ///
+    /// Infinite, if $\chi^{2}$ was never found to rise enough, and NaN if it can't be measured (see [`function@fit_profile`]).
+    pub lower: GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum>,
+    /// Distance from the best fit value to the upper interval bound for each parameter, in order of [`FitModel::get_params`].
+    ///
+    /// Infinite, if $\chi^{2}$ was never found to rise enough, and NaN if it can't be measured (see [`function@fit_profile`]).
+    pub upper: GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum>,
+}
+
+/// Sum of squared residuals, weighted the same way they are during the fit.
+fn weighted_chi2<Model: LevMarModel, Points: Dim>(
+    model: &Model,
+    x: &MatrixView<'_, Model::Scalar, Points, nalgebra::U1>,
+    y: &MatrixView<'_, Model::Scalar, Points, nalgebra::U1>,
+    sigma: Option<&MatrixView<'_, Model::Scalar, Points, nalgebra::U1>>,
+    weights: &impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> Model::Scalar {
+    x.iter()
+        .zip(y.iter())
+        .enumerate()
+        .fold(Model::Scalar::zero(), |acc, (i, (&x, &y))| {
+            let weight = sigma.map_or_else(|| weights(x, y), |sigma| weights(x, y) / sigma[i]);
+            let residual = weight * (FitModel::evaluate(model, &x) - y);
+            acc + residual * residual
+        })
+}
+
+/// Profile likelihood analysis for all the parameters. See [`function@fit_profile`].
+fn profile<Model, X, Y>(
+    model: &mut Model,
+    x: &X,
+    y: &Y,
+    sigma: Option<MatrixView<'_, Model::Scalar, DataPoints<X>, nalgebra::U1>>,
+    delta: Model::Scalar,
+    minimizer: &LevenbergMarquardt<Model::Scalar>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> Profile<Model>
+where
+    Model: LevMarModel,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+{
+    let x = x.convert();
+    let y = y.convert();
+    let params_count = <<Model::ParamCount as Conv>::TNum as Unsigned>::USIZE;
+    let zero = Model::Scalar::zero();
+    let one = Model::Scalar::one();
+    let two = one + one;
+    let tolerance = Float::cbrt(Model::Scalar::epsilon());
+
+    // fits the model with parameters marked in `frozen` kept at their current values
+    let fit = |model: &mut Model, frozen: GenericArray<bool, <Model::ParamCount as Conv>::TNum>| {
+        let problem = DynFrozenProblem {
+            model,
+            frozen,
+            x,
+            y,
+            sigma,
+            weights: &weights,
+        };
+        let (_, report) = LevenbergMarquardt::minimize::<Dyn, Dyn, _>(minimizer, problem);
+        report
+    };
+
+    let report = fit(model, GenericArray::generate(|_| false));
+    let best: GenericArray<_, <Model::ParamCount as Conv>::TNum> =
+        FitModel::get_params(model).into();
+    let chi2 = weighted_chi2(model, &x, &y, sigma.as_ref(), &weights);
+    // without known errors, data variance is estimated from the best fit
+    let variance = if sigma.is_some() {
+        one
+    } else {
+        let dof = x.len().saturating_sub(params_count);
+        if dof == 0 {
+            // there's nothing to estimate variance from, so chi2 rise can't be measured
+            let nan = GenericArray::generate(|_| Model::Scalar::nan());
+            return Profile {
+                report,
+                chi2,
+                lower: nan.clone(),
+                upper: nan,
+            };
+        }
+        chi2 / <Model::Scalar as NumCast>::from(dof).expect("Too many data points")
+    };
+
+    // parabolic approximation for initial search step
+    let jacobian = OMatrix::<Model::Scalar, Dyn, Dyn>::from_row_iterator(
+        x.len(),
+        params_count,
+        x.iter()
+            .zip(y.iter())
+            .enumerate()
+            .flat_map(|(i, (xi, &yi))| {
+                let weight =
+                    sigma.map_or_else(|| weights(*xi, yi), |sigma| weights(*xi, yi) / sigma[i]);
+                let jacobian: GenericArray<_, <Model::ParamCount as Conv>::TNum> =
+                    FitModel::jacobian(&*model, xi).into();
+                jacobian.into_iter().map(move |v| weight * v)
+            }),
+    );
+    let inverse = (jacobian.transpose() * jacobian).try_inverse();
+    let initial_step = |i: usize| {
+        let step = inverse.as_ref().map_or(zero, |inverse| {
+            Float::sqrt(inverse[(i, i)] * variance * delta)
+        });
+        if Float::is_finite(step) && step > zero {
+            step
+        } else {
+            Float::max(Float::abs(best[i]), one) * tolerance
+        }
+    };
+
+    // rise of the profile chi2 with parameter `i` set to `value`, with others refitted starting from `start`. Without the refit converging, profile chi2 is unknown.
+    let rise = |model: &mut Model, i: usize, value: Model::Scalar, start: &GenericArray<_, _>| {
+        let mut params: GenericArray<_, <Model::ParamCount as Conv>::TNum> = Clone::clone(start);
+        params[i] = value;
+        FitModel::set_params(model, params);
+        let report = fit(model, GenericArray::generate(|j| j == i));
+        report
+            .termination
+            .was_successful()
+            .then(|| (weighted_chi2(model, &x, &y, sigma.as_ref(), &weights) - chi2) / variance)
+    };
+
+    let mut errors = |direction: Model::Scalar| -> GenericArray<Model::Scalar, _> {
+        GenericArray::generate(|i| {
+            let step = initial_step(i);
+            // search for a point where chi2 rises enough, starting from each previous point
+            let (mut inside, mut outside) = (zero, step);
+            let mut start = best.clone();
+            let mut found = false;
+            for _ in 0..PROFILE_STEPS {
+                let Some(rise) = rise(model, i, best[i] + direction * outside, &start) else {
+                    return Model::Scalar::nan();
+                };
+                if rise >= delta {
+                    found = true;
+                    break;
+                }
+                start = FitModel::get_params(model).into();
+                inside = outside;
+                outside *= two;
+            }
+            if !found {
+                return Model::Scalar::infinity();
+            }
+            // bisect for the crossing itself
+            for _ in 0..PROFILE_BISECTIONS {
+                if outside - inside <= tolerance * outside {
+                    break;
+                }
+                let middle = (inside + outside) / two;
+                let Some(rise) = rise(model, i, best[i] + direction * middle, &start) else {
+                    return Model::Scalar::nan();
+                };
+                if rise >= delta {
+                    outside = middle;
+                } else {
+                    inside = middle;
+                    start = FitModel::get_params(model).into();
+                }
+            }
+            (inside + outside) / two
+        })
+    };
+    let lower = errors(-one);
+    let upper = errors(one);
+
+    FitModel::set_params(model, best);
+    Profile {
+        report,
+        chi2,
+        lower,
+        upper,
+    }
+}
+
+/// Fits the model, and computes profile likelihood intervals for each of the parameters.
+///
+/// For each parameter, its value is moved away from the best fit one (in both directions) with rest of the parameters refitted, until $\chi^{2}$ rises by `delta`. Unlike [`crate::FitStat::errors`], resulting intervals are asymmetric, and do not rely on model being approximately linear near the best fit.
+///
+/// Without known data errors, $\chi^{2}$ is measured in units of data variance estimated by the best fit, same as [`crate::FitStat::covariance_matrix`] is. Consequently, `delta = 1.0` corresponds to 1σ intervals (68.3% confidence), and generally `delta` should be [`chi2_quantile`](crate::stats::chi2_quantile)`(confidence, 1.0)`.
+///
+/// Model is left at the best fit after this function returns.
+///
+/// ### Outputs NaN
+///
+/// - As [`Profile::lower`] and [`Profile::upper`], if there are no degrees of freedom (data variance can't be estimated)
+/// - As a single bound, if any of the refits with parameter pinned did not converge (profile $\chi^{2}$ is unknown there)
+///
+/// ```rust
+/// # use nacfahi::{models::basic::Exponent, *};
+/// let x = [0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0];
+/// let y = [2.1, 2.4, 3.5, 4.2, 5.6, 7.4, 8.9];
+///
+/// let mut exponent = Exponent { a: 1.0, b: 1.0 };
+/// let profile = fit_profile(&mut exponent, x, y, 1.0, LevenbergMarquardt::new(), default_weights);
+///
+/// assert!(profile.report.termination.was_successful());
+/// // parameters are nonlinear, so intervals are asymmetric
+/// assert!(profile.lower[1] != profile.upper[1]);
+/// ```
+#[must_use = "Profile intervals are the only point to call this function specifically"]
+pub fn fit_profile<Model, X, Y>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    delta: Model::Scalar,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> Profile<Model>
+where
+    Model: LevMarModel,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+{
+    profile(model, &x, &y, None, delta, minimizer.borrow(), weights)
+}
+
+/// Same as [`function@fit_profile`], but each data point has a known error `sigma` (see [`function@crate::fit_sigma`]).
+///
+/// Here, $\chi^{2}$ is used as-is, so `delta = 1.0` corresponds to 1σ intervals.
+#[must_use = "Profile intervals are the only point to call this function specifically"]
+pub fn fit_profile_sigma<Model, X, Y, Sigma>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    sigma: Sigma,
+    delta: Model::Scalar,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> Profile<Model>
+where
+    Model: LevMarModel,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    Sigma: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+{
+    profile(
+        model,
+        &x,
+        &y,
+        Some(sigma.convert()),
+        delta,
+        minimizer.borrow(),
+        weights,
+    )
+}
@@ -0,0 +1,361 @@
+use core::borrow::Borrow;
+
+use generic_array::{ArrayLength, sequence::GenericSequence};
+use generic_array_storage::Conv;
+use nalgebra::{Dyn, MatrixView};
+use num_traits::{Float, NumCast, One};
+
+use crate::dyn_problem::DynFrozenProblem;
+use crate::models::{FitModel, FitModelErrors, LevMarModel};
+use crate::{AsMatrixView, GenericArray, LevenbergMarquardt, MinimizationReport, stats};
+
+use super::{Bootstrap, ResampleRng};
+
+/// Report of the original fit, parameters of successful resampled fits and number of failed ones.
+type ResampledFits<Model> = (
+    MinimizationReport<<Model as FitModel>::Scalar>,
+    alloc::vec::Vec<
+        GenericArray<<Model as FitModel>::Scalar, <<Model as FitModel>::ParamCount as Conv>::TNum>,
+    >,
+    usize,
+);
+
+/// Result of resampling analysis (see [`function@fit_bootstrap`] and [`function@fit_jackknife`]).
+#[derive(Debug)]
+pub struct Resampled<Model: FitModelErrors + LevMarModel> {
+    /// Report of the fit to original data
+    pub report: MinimizationReport<Model::Scalar>,
+    /// Parameters of each successful resampled fit, in order of [`FitModel::get_params`].
+    pub samples: alloc::vec::Vec<GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum>>,
+    /// Number of resampled fits that did not converge. These are not included into `samples`.
+    pub failed: usize,
+    /// Model with mean values of the resampled parameters.
+    ///
+    /// Mean is taken over actual parameter values (see [`FitModelErrors::get_external_params`]), so it's meaningful for models fitted in transformed parameters as well.
+    pub mean: Model,
+    /// Standard deviation of the resampled parameters, i.e. empirical parameter errors. Like `mean`, these are taken over actual parameter values, and converted into the error model with [`FitModelErrors::with_external_errors`].
+    ///
+    /// For jackknife, this is already scaled up, as leave-one-out estimates are much closer to each other than actual parameter distribution is wide.
+    pub std_dev: Model::OwnedModel,
+    /// Set for jackknife, which does not estimate parameter distribution
+    jackknife: bool,
+}
+
+impl<Model: FitModelErrors + LevMarModel + Clone> Resampled<Model> {
+    /// Collects resampling results, computing parameter moments.
+    fn new(
+        model: &Model,
+        (report, samples, failed): ResampledFits<Model>,
+        jackknife: bool,
+    ) -> Self {
+        let (mean, std_dev) = resampled_moments(&external_values(model, &samples), jackknife);
+        Self {
+            report,
+            samples,
+            failed,
+            mean: with_values(model, mean),
+            std_dev: model.with_external_errors(std_dev),
+            jackknife,
+        }
+    }
+
+    /// Models with lower and upper bounds of parameters at two-sided `confidence` level.
+    ///
+    /// For bootstrap, these are percentile intervals: quantiles of resampled parameter values, so they may be asymmetric. Jackknife does not produce parameter distribution, so intervals are symmetric around the mean, with Student's t-distribution quantile multiplying standard deviation of the parameters.
+    ///
+    /// Both are computed over actual parameter values (see [`FitModelErrors::get_external_params`]).
+    pub fn intervals(&self, confidence: Model::Scalar) -> (Model, Model) {
+        let one = Model::Scalar::one();
+        let two = one + one;
+        let values = external_values(&self.mean, &self.samples);
+        let count = values.len();
+        let (lower, upper) = if self.jackknife {
+            let dof = <Model::Scalar as NumCast>::from(count.saturating_sub(1))
+                .expect("Too many samples");
+            let quantile = stats::student_t_quantile((one + confidence) / two, dof);
+            let (mean, std_dev) = resampled_moments(&values, true);
+            (
+                GenericArray::generate(|i| mean[i] - quantile * std_dev[i]),
+                GenericArray::generate(|i| mean[i] + quantile * std_dev[i]),
+            )
+        } else {
+            let tail = (one - confidence) / two;
+            // linearly interpolated quantile of the sorted samples
+            let percentile = |sorted: &[Model::Scalar], p: Model::Scalar| {
+                let Some(&last) = sorted.last() else {
+                    return Model::Scalar::nan();
+                };
+                let position = p * <Model::Scalar as NumCast>::from(sorted.len() - 1)
+                    .expect("Too many samples");
+                let index = <usize as NumCast>::from(Float::floor(position)).unwrap_or(0);
+                if index + 1 >= sorted.len() {
+                    return last;
+                }
+                let fraction = position - Float::floor(position);
+                sorted[index] + fraction * (sorted[index + 1] - sorted[index])
+            };
+            let mut lower = GenericArray::generate(|_| Model::Scalar::nan());
+            let mut upper = lower.clone();
+            let mut sorted = alloc::vec::Vec::with_capacity(count);
+            for i in 0..lower.len() {
+                sorted.clear();
+                sorted.extend(values.iter().map(|value| value[i]));
+                sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+                lower[i] = percentile(&sorted, tail);
+                upper[i] = percentile(&sorted, one - tail);
+            }
+            (lower, upper)
+        };
+        (
+            with_values(&self.mean, lower),
+            with_values(&self.mean, upper),
+        )
+    }
+}
+
+/// Actual values (see [`FitModelErrors::get_external_params`]) of each of parameter `samples` of `model`.
+fn external_values<Model: FitModelErrors + LevMarModel + Clone>(
+    model: &Model,
+    samples: &[GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum>],
+) -> alloc::vec::Vec<GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum>> {
+    let mut model = model.clone();
+    samples
+        .iter()
+        .map(|sample| {
+            model.set_params(sample.clone());
+            model.get_external_params()
+        })
+        .collect()
+}
+
+/// Copy of `model` with actual parameter `values`.
+fn with_values<Model: FitModelErrors + Clone>(
+    model: &Model,
+    values: GenericArray<Model::Scalar, <Model::ParamCount as Conv>::TNum>,
+) -> Model {
+    let mut model = model.clone();
+    model.set_external_params(values);
+    model
+}
+
+/// Mean and standard deviation of resampled parameters. With `jackknife` set, standard deviation is scaled into jackknife error estimate.
+fn resampled_moments<Scalar: Float, N: ArrayLength>(
+    samples: &[GenericArray<Scalar, N>],
+    jackknife: bool,
+) -> (GenericArray<Scalar, N>, GenericArray<Scalar, N>) {
+    let count = <Scalar as NumCast>::from(samples.len()).expect("Too many samples");
+    let mean = GenericArray::generate(|i| {
+        samples
+            .iter()
+            .fold(Scalar::zero(), |acc, sample| acc + sample[i])
+            / count
+    });
+    let std_dev = GenericArray::generate(|i| {
+        let squares = samples.iter().fold(Scalar::zero(), |acc, sample| {
+            let deviation = sample[i] - mean[i];
+            acc + deviation * deviation
+        });
+        // jackknife variance is (n-1)/n * sum, while sample variance is sum/(n-1)
+        let scale = if jackknife {
+            (count - Scalar::one()) / count
+        } else {
+            (count - Scalar::one()).recip()
+        };
+        Float::sqrt(squares * scale)
+    });
+    (mean, std_dev)
+}
+
+/// Fits the model to data contained in slices, with all the parameters free.
+fn fit_slices<Model: LevMarModel>(
+    model: &mut Model,
+    x: &[Model::Scalar],
+    y: &[Model::Scalar],
+    minimizer: &LevenbergMarquardt<Model::Scalar>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> MinimizationReport<Model::Scalar> {
+    let problem = DynFrozenProblem {
+        model,
+        frozen: GenericArray::generate(|_| false),
+        x: MatrixView::from_slice_generic(x, Dyn(x.len()), nalgebra::U1),
+        y: MatrixView::from_slice_generic(y, Dyn(y.len()), nalgebra::U1),
+        sigma: None,
+        weights,
+    };
+    let (_, report) = LevenbergMarquardt::minimize::<Dyn, Dyn, _>(minimizer, problem);
+    report
+}
+
+/// Fits the model to original data, and then refits resampled data produced by `resample` `count` times, starting from the original fit each time.
+fn resampled<Model, X, Y>(
+    model: &mut Model,
+    x: &X,
+    y: &Y,
+    count: usize,
+    minimizer: &LevenbergMarquardt<Model::Scalar>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+    mut resample: impl FnMut(
+        &Model,
+        usize,
+        &mut alloc::vec::Vec<Model::Scalar>,
+        &mut alloc::vec::Vec<Model::Scalar>,
+    ),
+) -> ResampledFits<Model>
+where
+    Model: FitModelErrors + LevMarModel,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+{
+    let x: alloc::vec::Vec<_> = x.convert().iter().copied().collect();
+    let y: alloc::vec::Vec<_> = y.convert().iter().copied().collect();
+    let report = fit_slices(model, &x, &y, minimizer, &weights);
+    let best: GenericArray<_, <Model::ParamCount as Conv>::TNum> =
+        FitModel::get_params(model).into();
+
+    let mut samples = alloc::vec::Vec::with_capacity(count);
+    let mut failed = 0;
+    let (mut x_sample, mut y_sample) = (alloc::vec::Vec::new(), alloc::vec::Vec::new());
+    for i in 0..count {
+        FitModel::set_params(model, best.clone());
+        x_sample.clear();
+        y_sample.clear();
+        resample(model, i, &mut x_sample, &mut y_sample);
+        let report = fit_slices(model, &x_sample, &y_sample, minimizer, &weights);
+        if report.termination.was_successful() {
+            samples.push(FitModel::get_params(model).into());
+        } else {
+            failed += 1;
+        }
+    }
+    FitModel::set_params(model, best);
+    (report, samples, failed)
+}
+
+/// Estimates parameter errors by bootstrap: the model is refitted to `count` random resamples of the data, and spread of resulting parameters is used as an error estimate.
+///
+/// Unlike [`function@crate::fit_stat`], this makes no assumptions about residuals being normally distributed. See [`Bootstrap`] for available resampling schemes. With [`Bootstrap::Residuals`], residuals are resampled in units of `weights`, so weighting by inverse data errors keeps heteroscedastic data correct.
+///
+/// Random numbers are drawn from `R` seeded with `seed`, so results are reproducible. Model is left at the fit to original data.
+///
+/// ```rust
+/// # use nacfahi::{models::basic::Linear, resample::{Bootstrap, SplitMix64}, *};
+/// let x: [f64; 20] = core::array::from_fn(|i| i as f64);
+/// // residuals are far from normal here
+/// let y = x.map(|x| 2.0 * x + 1.0 + if x as usize % 3 == 0 { 1.0 } else { -0.5 });
+///
+/// let mut line = Linear { a: 0.0, b: 0.0 };
+/// let bootstrap = fit_bootstrap::<_, _, _, SplitMix64>(
+///     &mut line,
+///     x,
+///     y,
+///     Bootstrap::Residuals,
+///     200,
+///     42,
+///     LevenbergMarquardt::new(),
+///     default_weights,
+/// );
+///
+/// assert!(bootstrap.report.termination.was_successful());
+/// assert_eq!(bootstrap.samples.len() + bootstrap.failed, 200);
+/// let (lower, upper) = bootstrap.intervals(0.95);
+/// assert!(lower.a < line.a && line.a < upper.a);
+/// ```
+#[must_use = "Resampled parameters are the only point to call this function specifically"]
+#[allow(
+    clippy::too_many_arguments,
+    reason = "Arguments mirror the rest of fitting functions, and there's no point in grouping them"
+)]
+pub fn fit_bootstrap<Model, X, Y, R>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    method: Bootstrap,
+    count: usize,
+    seed: u64,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> Resampled<Model>
+where
+    Model: FitModelErrors + LevMarModel + Clone,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    R: ResampleRng,
+{
+    let mut rng = R::from_seed(seed);
+    let data_x = x.convert();
+    let data_y = y.convert();
+    let points = data_x.len();
+    // weighted residuals of the original fit are only known after it, so they are computed on first resample
+    let mut residuals: Option<alloc::vec::Vec<Model::Scalar>> = None;
+    let resampled = resampled(
+        model,
+        &x,
+        &y,
+        count,
+        minimizer.borrow(),
+        &weights,
+        |model, _, x_sample, y_sample| match method {
+            Bootstrap::Pairs => {
+                for _ in 0..points {
+                    let index = rng.below(points);
+                    x_sample.push(data_x[index]);
+                    y_sample.push(data_y[index]);
+                }
+            }
+            Bootstrap::Residuals => {
+                let residuals = residuals.get_or_insert_with(|| {
+                    data_x
+                        .iter()
+                        .zip(data_y.iter())
+                        .map(|(&x, &y)| weights(x, y) * (y - FitModel::evaluate(model, &x)))
+                        .collect()
+                });
+                for (&x, &y) in data_x.iter().zip(data_y.iter()) {
+                    let residual = residuals[rng.below(points)];
+                    x_sample.push(x);
+                    y_sample.push(FitModel::evaluate(model, &x) + residual / weights(x, y));
+                }
+            }
+        },
+    );
+    Resampled::new(model, resampled, false)
+}
+
+/// Estimates parameter errors by jackknife: the model is refitted to data with each of the points left out, and spread of resulting parameters is used as an error estimate.
+///
+/// This is deterministic and requires exactly as many refits as there are data points. Note that [`Resampled::mean`] is the mean of leave-one-out fits; bias-corrected parameter estimate can be obtained as $n \hat{\theta} - (n - 1) \bar{\theta}$.
+///
+/// Model is left at the fit to original data.
+#[must_use = "Resampled parameters are the only point to call this function specifically"]
+pub fn fit_jackknife<Model, X, Y>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> Resampled<Model>
+where
+    Model: FitModelErrors + LevMarModel + Clone,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+{
+    let data_x = x.convert();
+    let data_y = y.convert();
+    let resampled = resampled(
+        model,
+        &x,
+        &y,
+        data_x.len(),
+        minimizer.borrow(),
+        weights,
+        |_, left_out, x_sample, y_sample| {
+            for (i, (&x, &y)) in data_x.iter().zip(data_y.iter()).enumerate() {
+                if i != left_out {
+                    x_sample.push(x);
+                    y_sample.push(y);
+                }
+            }
+        },
+    );
+    Resampled::new(model, resampled, true)
+}
@@ -0,0 +1,73 @@
+/// Seedable source of random numbers used for resampling (see [`function@crate::fit_bootstrap`]).
+///
+/// This crate does not depend on any particular random number generator, so implement this for the one you prefer, or use [`SplitMix64`].
+pub trait ResampleRng {
+    /// Creates generator from a `seed`. Same seed should always produce the same sequence.
+    fn from_seed(seed: u64) -> Self
+    where
+        Self: Sized;
+
+    /// Next uniformly distributed 64-bit value.
+    fn next_u64(&mut self) -> u64;
+
+    /// Uniformly distributed index in `0..bound`.
+    ///
+    /// # Panics
+    /// If `bound` is zero.
+    fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "Can't sample from an empty range");
+        // Lemire's multiply-and-reject method, see https://arxiv.org/abs/1805.10941
+        let bound = bound as u64;
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let product = u128::from(self.next_u64()) * u128::from(bound);
+            #[allow(
+                clippy::cast_possible_truncation,
+                reason = "Lower and upper halves of the product are taken intentionally"
+            )]
+            if product as u64 >= threshold {
+                return (product >> 64) as usize;
+            }
+        }
+    }
+}
+
+/// Small and fast [SplitMix64](https://prng.di.unimi.it/splitmix64.c) generator.
+///
+/// It's by no means cryptographically secure, but is more than enough for resampling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SplitMix64(pub u64);
+
+impl ResampleRng for SplitMix64 {
+    #[inline]
+    fn from_seed(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Bootstrap resampling scheme (see [`function@crate::fit_bootstrap`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bootstrap {
+    /// Data `x` are kept, and residuals of the original fit are randomly reassigned to the fitted values.
+    ///
+    /// Assumes model to be correct and residuals to be identically distributed (after weighting).
+    Residuals,
+    /// Data points `(x, y)` are drawn with replacement.
+    ///
+    /// Makes no assumptions about residuals, but needs more data to be reliable.
+    Pairs,
+}
+
+pub(crate) mod fit;
+
+#[cfg(test)]
+mod tests;
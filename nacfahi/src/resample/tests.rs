@@ -0,0 +1,28 @@
+use super::{ResampleRng, SplitMix64};
+
+#[test]
+fn split_mix_reference() {
+    // reference values of splitmix64.c seeded with 1234567
+    let mut rng = SplitMix64::from_seed(1_234_567);
+    assert_eq!(rng.next_u64(), 6_457_827_717_110_365_317);
+    assert_eq!(rng.next_u64(), 3_203_168_211_198_807_973);
+    assert_eq!(rng.next_u64(), 9_817_491_932_198_370_423);
+}
+
+#[test]
+fn below_is_uniform() {
+    let mut rng = SplitMix64::from_seed(42);
+    let mut counts = [0usize; 7];
+    for _ in 0..70_000 {
+        counts[rng.below(7)] += 1;
+    }
+    for count in counts {
+        assert!(count.abs_diff(10_000) < 500, "{counts:?}");
+    }
+}
+
+#[test]
+#[should_panic = "Can't sample from an empty range"]
+fn below_zero() {
+    SplitMix64::from_seed(0).below(0);
+}
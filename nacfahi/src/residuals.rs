@@ -0,0 +1,289 @@
+use generic_array_storage::Conv;
+use nalgebra::allocator::Allocator;
+use nalgebra::{DefaultAllocator, Dim, MatrixView, OVector};
+use num_traits::{Float, NumCast, One, Zero};
+use typenum::Unsigned;
+
+use crate::models::LevMarModel;
+use crate::{
+    AsMatrixView, CreateProblem, DataPoints, FitterUnit, RealField, stats, weighted_jacobian,
+};
+
+/// [Wald-Wolfowitz runs test](https://en.wikipedia.org/wiki/Wald%E2%80%93Wolfowitz_runs_test) for residual signs (see [`ResidualReport::runs`]).
+///
+/// Too few runs mean residuals form long same-sign stretches, which is a typical sign of systematic misfit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunsTest<Scalar> {
+    /// Number of runs: maximal stretches of residuals of the same sign
+    pub runs: usize,
+    /// Number of positive residuals
+    pub positive: usize,
+    /// Number of negative residuals. Residuals that are exactly zero are not counted at all.
+    pub negative: usize,
+    /// Normal approximation z-score of the number of runs. Negative values mean there are fewer runs than expected.
+    ///
+    /// NaN, if all the residuals have the same sign.
+    pub z: Scalar,
+    /// Two-sided p-value of the test: probability to get at least this unusual number of runs for randomly ordered residuals.
+    pub p_value: Scalar,
+}
+
+impl<Scalar: Float> RunsTest<Scalar> {
+    /// Performs the test on a sequence of residuals.
+    fn of(residuals: impl Iterator<Item = Scalar>) -> Self {
+        let (mut runs, mut positive, mut negative) = (0, 0, 0);
+        let mut last = None;
+        for sign in residuals
+            .filter(|r| !r.is_zero())
+            .map(|r| r > Scalar::zero())
+        {
+            if sign {
+                positive += 1;
+            } else {
+                negative += 1;
+            }
+            if last != Some(sign) {
+                runs += 1;
+            }
+            last = Some(sign);
+        }
+        let float = |value: usize| <Scalar as NumCast>::from(value).expect("Too many data points");
+        let one = Scalar::one();
+        let two = one + one;
+        let (n_positive, n_negative) = (float(positive), float(negative));
+        let n = n_positive + n_negative;
+        let mean = two * n_positive * n_negative / n + one;
+        let variance = (mean - one) * (mean - two) / (n - one);
+        let z = if variance > Scalar::zero() {
+            (float(runs) - mean) / variance.sqrt()
+        } else {
+            Scalar::nan()
+        };
+        Self {
+            runs,
+            positive,
+            negative,
+            z,
+            p_value: two * stats::normal_cdf(-z.abs()),
+        }
+    }
+}
+
+/// Residual diagnostics of a fit (see [`function@residual_report`]).
+#[derive(Debug)]
+pub struct ResidualReport<Scalar: nalgebra::Scalar, Points: Dim>
+where
+    DefaultAllocator: Allocator<Points>,
+{
+    /// Residuals $y_i - f(x_i)$
+    pub residuals: OVector<Scalar, Points>,
+    /// Residuals weighted the same way they were during the fit. These are the ones $\chi^{2}$ is computed from.
+    pub weighted: OVector<Scalar, Points>,
+    /// Internally studentized residuals: weighted residuals, divided by their expected standard deviation $s \sqrt{1 - h_i}$.
+    ///
+    /// For a correct model, these should be about standard normal; values above 3 or so likely indicate outliers.
+    pub standardized: OVector<Scalar, Points>,
+    /// Leverage $h_i$ of each data point: diagonal of the hat matrix. Shows how much point affects its own fitted value, sums up to number of parameters.
+    pub leverage: OVector<Scalar, Points>,
+    /// [Cook's distance](https://en.wikipedia.org/wiki/Cook%27s_distance): influence of each data point on the fit. Values around 1 and above are suspicious.
+    pub cooks_distance: OVector<Scalar, Points>,
+    /// [Durbin-Watson statistic](https://en.wikipedia.org/wiki/Durbin%E2%80%93Watson_statistic) of weighted residuals, in data order.
+    ///
+    /// Is about 2 for independent residuals; values well below 2 indicate that neighbouring residuals are correlated (i.e. model misses some structure). Only makes sense if data is sorted by `x`.
+    pub durbin_watson: Scalar,
+    /// Runs test of residual signs, in data order. Only makes sense if data is sorted by `x`.
+    pub runs: RunsTest<Scalar>,
+}
+
+/// A helper trait to simplify type bounds for a user. You probably should no see this.
+///
+/// In case you do get a "type does not implement" type or error with this trait... I'm sorry.
+pub trait FitResidualBound<Model: LevMarModel, X, Y = X> {
+    #[doc(hidden)]
+    type Points: Dim;
+    #[doc(hidden)]
+    fn produce_residuals(
+        model: &Model,
+        x: X,
+        y: Y,
+        sigma: Option<MatrixView<'_, Model::Scalar, Self::Points, nalgebra::U1>>,
+        absolute_sigma: bool,
+        weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+    ) -> ResidualReport<Model::Scalar, Self::Points>
+    where
+        DefaultAllocator: Allocator<Self::Points>;
+}
+
+impl<Model, X, Y> FitResidualBound<Model, X, Y> for FitterUnit
+where
+    Model: LevMarModel,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    X::Points: CreateProblem,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    DefaultAllocator: Allocator<DataPoints<X>>
+        + Allocator<<Model::ParamCount as Conv>::Nalg, DataPoints<X>>
+        + Allocator<DataPoints<X>, <Model::ParamCount as Conv>::Nalg>
+        + Allocator<<Model::ParamCount as Conv>::Nalg, <Model::ParamCount as Conv>::Nalg>,
+{
+    type Points = DataPoints<X>;
+
+    #[allow(
+        clippy::inline_always,
+        reason = "This function is used in a single place, and only exists to extract the type bounds."
+    )]
+    #[inline(always)]
+    fn produce_residuals(
+        model: &Model,
+        x: X,
+        y: Y,
+        sigma: Option<MatrixView<'_, Model::Scalar, Self::Points, nalgebra::U1>>,
+        absolute_sigma: bool,
+        weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+    ) -> ResidualReport<Model::Scalar, Self::Points> {
+        residuals(
+            model,
+            x.convert(),
+            y.convert(),
+            sigma,
+            absolute_sigma,
+            weights,
+        )
+    }
+}
+
+/// Residual diagnostics of `model` at data points. See [`function@residual_report`].
+fn residuals<Model, Points>(
+    model: &Model,
+    x: MatrixView<'_, Model::Scalar, Points, nalgebra::U1>,
+    y: MatrixView<'_, Model::Scalar, Points, nalgebra::U1>,
+    sigma: Option<MatrixView<'_, Model::Scalar, Points, nalgebra::U1>>,
+    absolute_sigma: bool,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> ResidualReport<Model::Scalar, Points>
+where
+    Model: LevMarModel,
+    Model::Scalar: RealField + Float,
+    Points: Dim,
+    DefaultAllocator: Allocator<Points>
+        + Allocator<<Model::ParamCount as Conv>::Nalg, Points>
+        + Allocator<Points, <Model::ParamCount as Conv>::Nalg>
+        + Allocator<<Model::ParamCount as Conv>::Nalg, <Model::ParamCount as Conv>::Nalg>,
+{
+    let weight = |i: usize| {
+        let weight = weights(x[i], y[i]);
+        sigma.as_ref().map_or(weight, |sigma| weight / sigma[i])
+    };
+    let zero = Model::Scalar::zero();
+    let one = Model::Scalar::one();
+    let shape = x.shape_generic().0;
+    let u_params = <<Model::ParamCount as Conv>::TNum as Unsigned>::USIZE;
+    let params = <Model::Scalar as NumCast>::from(u_params).expect("Too many parameters");
+
+    let residuals =
+        OVector::from_fn_generic(shape, nalgebra::U1, |i, _| y[i] - model.evaluate(&x[i]));
+    let weighted = OVector::from_fn_generic(shape, nalgebra::U1, |i, _| weight(i) * residuals[i]);
+
+    // hat matrix is J^T (J J^T)^-1 J, only diagonal is needed
+    let jacobian = weighted_jacobian(model, &x, weight);
+    let inverse = (jacobian.clone() * jacobian.transpose()).try_inverse();
+    let leverage = OVector::from_fn_generic(shape, nalgebra::U1, |i, _| {
+        inverse.as_ref().map_or(Model::Scalar::nan(), |inverse| {
+            (0..u_params)
+                .flat_map(|a| (0..u_params).map(move |b| (a, b)))
+                .fold(zero, |acc, (a, b)| {
+                    acc + jacobian[(a, i)] * inverse[(a, b)] * jacobian[(b, i)]
+                })
+        })
+    });
+
+    let chi2 = weighted.iter().fold(zero, |acc, &e| acc + e * e);
+    let variance = if absolute_sigma {
+        one
+    } else if x.len() > u_params {
+        chi2 / <Model::Scalar as NumCast>::from(x.len() - u_params).expect("Too many data points")
+    } else {
+        Model::Scalar::nan()
+    };
+    let standardized = OVector::from_fn_generic(shape, nalgebra::U1, |i, _| {
+        weighted[i] / Float::sqrt(variance * (one - leverage[i]))
+    });
+    let cooks_distance = OVector::from_fn_generic(shape, nalgebra::U1, |i, _| {
+        let r = standardized[i];
+        r * r * leverage[i] / (params * (one - leverage[i]))
+    });
+
+    let differences = weighted
+        .iter()
+        .zip(weighted.iter().skip(1))
+        .fold(zero, |acc, (&prev, &next)| {
+            acc + (next - prev) * (next - prev)
+        });
+    let durbin_watson = differences / chi2;
+    let runs = RunsTest::of(weighted.iter().copied());
+
+    ResidualReport {
+        residuals,
+        weighted,
+        standardized,
+        leverage,
+        cooks_distance,
+        durbin_watson,
+        runs,
+    }
+}
+
+/// Computes residual diagnostics of a fitted model: residuals, their standardized versions, leverage and influence of each data point, and tests for systematic misfit.
+///
+/// Call this after the fit, with the same data and `weights`. Data variance is estimated from the residuals, same as [`function@crate::fit_stat`] does.
+///
+/// ```rust
+/// # use nacfahi::{models::basic::Linear, *};
+/// // data is a parabola, so line leaves systematic residuals
+/// let x: [f64; 20] = core::array::from_fn(|i| i as f64);
+/// let y = x.map(|x| x * x);
+///
+/// let mut line = Linear { a: 0.0, b: 0.0 };
+/// let report = fit!(&mut line, x, y);
+/// assert!(report.termination.was_successful());
+///
+/// let residuals = residual_report(&line, x, y, default_weights);
+/// assert!(residuals.durbin_watson < 1.0);
+/// assert_eq!(residuals.runs.runs, 3);
+/// assert!(residuals.runs.p_value < 0.001);
+/// ```
+#[must_use = "Residual report is the only point to call this function"]
+pub fn residual_report<Model, X, Y>(
+    model: &Model,
+    x: X,
+    y: Y,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> ResidualReport<Model::Scalar, DataPoints<X>>
+where
+    Model: LevMarModel,
+    FitterUnit: FitResidualBound<Model, X, Y, Points = DataPoints<X>>,
+    X: AsMatrixView,
+    DefaultAllocator: Allocator<DataPoints<X>>,
+{
+    FitterUnit::produce_residuals(model, x, y, None, false, weights)
+}
+
+/// Same as [`function@residual_report`], but each data point has a known error `sigma` (see [`function@crate::fit_sigma`]).
+///
+/// Here, residuals are standardized by known data errors directly.
+#[must_use = "Residual report is the only point to call this function"]
+pub fn residual_report_sigma<Model, X, Y, Sigma>(
+    model: &Model,
+    x: X,
+    y: Y,
+    sigma: Sigma,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> ResidualReport<Model::Scalar, DataPoints<X>>
+where
+    Model: LevMarModel,
+    FitterUnit: FitResidualBound<Model, X, Y, Points = DataPoints<X>>,
+    X: AsMatrixView,
+    Sigma: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    DefaultAllocator: Allocator<DataPoints<X>>,
+{
+    FitterUnit::produce_residuals(model, x, y, Some(sigma.convert()), true, weights)
+}
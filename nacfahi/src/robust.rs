@@ -0,0 +1,324 @@
+use core::borrow::Borrow;
+
+use generic_array::sequence::GenericSequence;
+use nalgebra::allocator::Allocator;
+use nalgebra::{DefaultAllocator, Dim, MatrixView, OVector};
+use num_traits::{Float, NumCast, One, Zero};
+
+use crate::loss::Loss;
+use crate::models::{FitModel, FitModelErrors, LevMarModel};
+use crate::{
+    AsMatrixView, DataPoints, FitBound, FitErrBound, FitStat, FitterUnit, GenericArray,
+    LevenbergMarquardt, MinimizationReport, scalar_weights,
+};
+
+/// Maximum number of reweighting iterations performed by robust fit.
+const ROBUST_ITERATIONS: usize = 50;
+
+/// Result of robust fit (see [`function@fit_robust`]).
+#[derive(Debug)]
+pub struct RobustFit<Result, Scalar: nalgebra::Scalar, Points: Dim>
+where
+    DefaultAllocator: Allocator<Points>,
+{
+    /// Result of the final fit iteration: either [`MinimizationReport`], or [`FitStat`].
+    pub result: Result,
+    /// Robust estimate of residual scale (normalized median absolute deviation), as of the final iteration.
+    pub scale: Scalar,
+    /// Final weights assigned to each of the data points by the loss function. Points with weights close to zero are effectively ignored as outliers.
+    pub weights: OVector<Scalar, Points>,
+    /// Number of reweighting iterations performed.
+    pub iterations: usize,
+}
+
+/// Normalized median absolute deviation. For normally distributed values, this is an estimate of standard deviation.
+fn median_abs_deviation<Scalar: Float + nalgebra::Scalar, Points: Dim>(
+    mut values: OVector<Scalar, Points>,
+) -> Scalar
+where
+    DefaultAllocator: Allocator<Points>,
+{
+    let values = values.as_mut_slice();
+    if values.is_empty() {
+        return Scalar::nan();
+    }
+    for value in values.iter_mut() {
+        *value = value.abs();
+    }
+    values.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+    let n = values.len();
+    let median = if n % 2 == 1 {
+        values[n / 2]
+    } else {
+        (values[n / 2 - 1] + values[n / 2]) / (Scalar::one() + Scalar::one())
+    };
+    // 1 / Φ^-1(3/4)
+    let normalization: Scalar =
+        NumCast::from(1.482_602_218_505_602_f64).expect("Should be able to represent constant");
+    median * normalization
+}
+
+/// Combines known data errors with robust weights.
+fn robust_sigma<Scalar: Float + nalgebra::Scalar, Points: Dim>(
+    sigma: Option<&MatrixView<'_, Scalar, Points, nalgebra::U1>>,
+    robust_weights: &OVector<Scalar, Points>,
+) -> OVector<Scalar, Points>
+where
+    DefaultAllocator: Allocator<Points>,
+{
+    OVector::from_fn_generic(robust_weights.shape_generic().0, nalgebra::U1, |i, _| {
+        let sigma = sigma.map_or_else(Scalar::one, |sigma| sigma[i]);
+        sigma / robust_weights[i].sqrt()
+    })
+}
+
+/// Iteratively reweighted least squares.
+fn robust_fit<Model, X, Y>(
+    model: &mut Model,
+    x: &X,
+    y: &Y,
+    sigma: Option<&MatrixView<'_, Model::Scalar, DataPoints<X>, nalgebra::U1>>,
+    loss: impl Loss<Model::Scalar>,
+    minimizer: &LevenbergMarquardt<Model::Scalar>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> RobustFit<MinimizationReport<Model::Scalar>, Model::Scalar, DataPoints<X>>
+where
+    Model: FitModel + LevMarModel,
+    FitterUnit: FitBound<Model, X, Y, Points = DataPoints<X>>,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    DefaultAllocator: Allocator<DataPoints<X>>,
+{
+    let x_view = x.convert();
+    let y_view = y.convert();
+    let points = x_view.shape_generic().0;
+    let tolerance = Float::cbrt(Model::Scalar::epsilon());
+
+    // robust weights and scale estimate for the current model state
+    let reweight = |model: &Model| {
+        let residuals = OVector::<Model::Scalar, DataPoints<X>>::from_fn_generic(
+            points,
+            nalgebra::U1,
+            |i, _| {
+                let (x, y) = (x_view[i], y_view[i]);
+                let sigma = sigma.map_or_else(Model::Scalar::one, |sigma| sigma[i]);
+                weights(x, y) * (FitModel::evaluate(model, &x) - y) / sigma
+            },
+        );
+        let scale = median_abs_deviation(residuals.clone());
+        let robust_weights = if scale > Model::Scalar::zero() {
+            residuals.map(|r| loss.weight(r / scale))
+        } else {
+            // perfect fit, no outliers to speak of
+            residuals.map(|_| Model::Scalar::one())
+        };
+        (robust_weights, scale)
+    };
+
+    // initial guess is usually good enough to spot the outliers right away
+    let (mut robust_weights, _) = reweight(model);
+    let mut iterations = 0;
+    loop {
+        let effective_sigma = robust_sigma(sigma, &robust_weights);
+        let report = FitterUnit::fit(
+            minimizer,
+            &mut *model,
+            x,
+            y,
+            Some(MatrixView::from_slice_generic(
+                effective_sigma.as_slice(),
+                points,
+                nalgebra::U1,
+            )),
+            scalar_weights(&weights),
+        );
+        iterations += 1;
+
+        let (updated, scale) = reweight(model);
+        let change = updated
+            .iter()
+            .zip(robust_weights.iter())
+            .fold(Model::Scalar::zero(), |acc, (&new, &old)| {
+                Float::max(acc, Float::abs(new - old))
+            });
+        robust_weights = updated;
+
+        if change <= tolerance
+            || iterations >= ROBUST_ITERATIONS
+            || !report.termination.was_successful()
+        {
+            return RobustFit {
+                result: report,
+                scale,
+                weights: robust_weights,
+                iterations,
+            };
+        }
+    }
+}
+
+/// Same as [`function@crate::fit`], but outliers are suppressed according to the supplied `loss` function (see [`loss`](crate::loss) module).
+///
+/// Internally, this performs iteratively reweighted least squares: starting with the initial model state, and after each fit, residuals are scaled by their robust scale estimate (normalized median absolute deviation), and data points are re-weighted according to the `loss`. This repeats until weights stop changing, or for at most 50 iterations.
+///
+/// ```rust
+/// # use approx::assert_abs_diff_eq;
+/// # use nacfahi::{loss::Huber, models::basic::Linear, *};
+/// let x = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+/// // presumably 2x + 1, with a single spike
+/// let y = [1.02, 2.97, 5.01, 7.03, 100.0, 10.98, 13.0, 15.01];
+///
+/// let mut line = Linear { a: 0.0, b: 0.0 };
+/// let robust = fit_robust(&mut line, x, y, Huber(1.345), LevenbergMarquardt::new(), default_weights);
+///
+/// assert!(robust.result.termination.was_successful());
+/// assert!(robust.weights[4] < 0.01, "Spike should be suppressed");
+/// assert_abs_diff_eq!(line.a, 2.0, epsilon = 0.05);
+/// assert_abs_diff_eq!(line.b, 1.0, epsilon = 0.05);
+/// ```
+#[must_use = "Minimization report is really important to check if approximation happened at all"]
+pub fn fit_robust<Model, X, Y>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    loss: impl Loss<Model::Scalar>,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> RobustFit<MinimizationReport<Model::Scalar>, Model::Scalar, DataPoints<X>>
+where
+    Model: FitModel + LevMarModel,
+    FitterUnit: FitBound<Model, X, Y, Points = DataPoints<X>>,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    DefaultAllocator: Allocator<DataPoints<X>>,
+{
+    robust_fit(model, &x, &y, None, loss, minimizer.borrow(), weights)
+}
+
+/// Same as [`function@fit_robust`], but each data point has a known error `sigma` (see [`function@crate::fit_sigma`]).
+///
+/// Here, residuals are divided by `sigma` before computing robust scale and weights.
+#[must_use = "Minimization report is really important to check if approximation happened at all"]
+pub fn fit_robust_sigma<Model, X, Y, Sigma>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    sigma: Sigma,
+    loss: impl Loss<Model::Scalar>,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> RobustFit<MinimizationReport<Model::Scalar>, Model::Scalar, DataPoints<X>>
+where
+    Model: FitModel + LevMarModel,
+    FitterUnit: FitBound<Model, X, Y, Points = DataPoints<X>>,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    Sigma: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    DefaultAllocator: Allocator<DataPoints<X>>,
+{
+    let sigma = sigma.convert();
+    robust_fit(
+        model,
+        &x,
+        &y,
+        Some(&sigma),
+        loss,
+        minimizer.borrow(),
+        weights,
+    )
+}
+
+/// Same as [`function@crate::fit_stat`], but outliers are suppressed according to the supplied `loss` function (see [`function@fit_robust`]).
+///
+/// Statistics are computed with the final robust weights, so suppressed outliers do not contribute to $\chi^{2}$ and parameter errors.
+#[must_use = "Fit statistics are the only point to call this function specifically"]
+pub fn fit_stat_robust<Model, X, Y>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    loss: impl Loss<Model::Scalar>,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> RobustFit<FitStat<Model>, Model::Scalar, DataPoints<X>>
+where
+    Model: FitModelErrors + LevMarModel,
+    FitterUnit: FitErrBound<Model, X, Y, Points = DataPoints<X>>,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    DefaultAllocator: Allocator<DataPoints<X>>,
+{
+    let robust = robust_fit(model, &x, &y, None, loss, minimizer.borrow(), &weights);
+    let sigma = robust_sigma(None, &robust.weights);
+    let stat = FitterUnit::produce_stat(
+        model,
+        robust.result,
+        x,
+        y,
+        Some(MatrixView::from_slice_generic(
+            sigma.as_slice(),
+            sigma.shape_generic().0,
+            nalgebra::U1,
+        )),
+        false,
+        weights,
+        &GenericArray::generate(|_| false),
+    );
+    RobustFit {
+        result: stat,
+        scale: robust.scale,
+        weights: robust.weights,
+        iterations: robust.iterations,
+    }
+}
+
+/// Same as [`function@fit_stat_robust`], but each data point has a known error `sigma` (see [`function@crate::fit_stat_sigma`]).
+#[must_use = "Fit statistics are the only point to call this function specifically"]
+pub fn fit_stat_robust_sigma<Model, X, Y, Sigma>(
+    model: &mut Model,
+    x: X,
+    y: Y,
+    sigma: Sigma,
+    loss: impl Loss<Model::Scalar>,
+    minimizer: impl Borrow<LevenbergMarquardt<Model::Scalar>>,
+    weights: impl Fn(Model::Scalar, Model::Scalar) -> Model::Scalar,
+) -> RobustFit<FitStat<Model>, Model::Scalar, DataPoints<X>>
+where
+    Model: FitModelErrors + LevMarModel,
+    FitterUnit: FitErrBound<Model, X, Y, Points = DataPoints<X>>,
+    X: AsMatrixView<Scalar = Model::Scalar, Columns = nalgebra::U1>,
+    Y: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    Sigma: AsMatrixView<Scalar = Model::Scalar, Points = X::Points, Columns = nalgebra::U1>,
+    DefaultAllocator: Allocator<DataPoints<X>>,
+{
+    let sigma = sigma.convert();
+    let robust = robust_fit(
+        model,
+        &x,
+        &y,
+        Some(&sigma),
+        loss,
+        minimizer.borrow(),
+        &weights,
+    );
+    let sigma = robust_sigma(Some(&sigma), &robust.weights);
+    let stat = FitterUnit::produce_stat(
+        model,
+        robust.result,
+        x,
+        y,
+        Some(MatrixView::from_slice_generic(
+            sigma.as_slice(),
+            sigma.shape_generic().0,
+            nalgebra::U1,
+        )),
+        true,
+        weights,
+        &GenericArray::generate(|_| false),
+    );
+    RobustFit {
+        result: stat,
+        scale: robust.scale,
+        weights: robust.weights,
+        iterations: robust.iterations,
+    }
+}
@@ -0,0 +1,173 @@
+use super::{
+    chi2_cdf, chi2_quantile, chi2_sf, erf, f_cdf, f_sf, faddeeva, ln_gamma, normal_cdf,
+    normal_quantile, regularized_beta, regularized_gamma, regularized_gamma_upper, student_t_cdf,
+    student_t_quantile,
+};
+
+#[test]
+fn gamma() {
+    // Γ(1/2) = √π
+    approx::assert_relative_eq!(
+        ln_gamma(0.5f64),
+        core::f64::consts::PI.sqrt().ln(),
+        max_relative = 1e-14
+    );
+    let mut factorial = 1.0f64;
+    for n in 1..20 {
+        approx::assert_relative_eq!(
+            ln_gamma(f64::from(n)),
+            factorial.ln(),
+            max_relative = 1e-13,
+            epsilon = 1e-14
+        );
+        factorial *= f64::from(n);
+    }
+    // reflection: Γ(-1/2) = -2√π
+    approx::assert_relative_eq!(
+        ln_gamma(-0.5f64),
+        (2.0 * core::f64::consts::PI.sqrt()).ln(),
+        max_relative = 1e-13
+    );
+}
+
+#[test]
+fn incomplete_functions() {
+    // I_x(a, 1) = x^a
+    approx::assert_relative_eq!(
+        regularized_beta(2.5f64, 1.0, 0.7),
+        0.7f64.powf(2.5),
+        max_relative = 1e-13
+    );
+    // symmetry
+    approx::assert_relative_eq!(
+        regularized_beta(3.0f64, 5.0, 0.4),
+        1.0 - regularized_beta(5.0f64, 3.0, 0.6),
+        max_relative = 1e-13
+    );
+    assert!(regularized_beta(-1.0f64, 1.0, 0.5).is_nan());
+
+    // P(n, x) = 1 - exp(-x) Σ x^k / k! (k < n)
+    let x = 3.7f64;
+    let expected = 1.0 - (-x).exp() * (1.0 + x + x * x / 2.0);
+    approx::assert_relative_eq!(regularized_gamma(3.0f64, x), expected, max_relative = 1e-13);
+    let x = 1.2f64;
+    let expected = 1.0 - (-x).exp() * (1.0 + x + x * x / 2.0 + x * x * x / 6.0);
+    approx::assert_relative_eq!(regularized_gamma(4.0f64, x), expected, max_relative = 1e-13);
+
+    approx::assert_relative_eq!(erf(-0.5f64), -0.520_499_877_813_046_5, max_relative = 1e-14);
+    approx::assert_relative_eq!(erf(2.5f64), 0.999_593_047_982_555, max_relative = 1e-14);
+}
+
+#[test]
+fn distributions() {
+    approx::assert_relative_eq!(normal_cdf(0.0f64), 0.5);
+    approx::assert_relative_eq!(
+        normal_cdf(-1.0f64),
+        0.158_655_253_931_457_05,
+        max_relative = 1e-13
+    );
+    approx::assert_relative_eq!(normal_quantile(0.5f64), 0.0, epsilon = 1e-15);
+    approx::assert_relative_eq!(
+        normal_quantile(0.001f64),
+        -3.090_232_306_167_813_5,
+        max_relative = 1e-12
+    );
+
+    approx::assert_relative_eq!(student_t_cdf(0.0f64, 3.0), 0.5);
+    // Cauchy distribution
+    approx::assert_relative_eq!(student_t_cdf(1.0f64, 1.0), 0.75, max_relative = 1e-14);
+    for (dof, expected) in [
+        (2.0, 4.302_652_729_749_464),
+        (10.0, 2.228_138_851_986_273_3),
+        (30.0, 2.042_272_456_301_238),
+    ] {
+        approx::assert_relative_eq!(
+            student_t_quantile(0.975f64, dof),
+            expected,
+            max_relative = 1e-10
+        );
+        approx::assert_relative_eq!(
+            student_t_quantile(0.025f64, dof),
+            -expected,
+            max_relative = 1e-10
+        );
+    }
+    assert!(student_t_quantile(1.0f64, 5.0).is_infinite());
+    // cdf itself is NaN here, so there's nothing to invert
+    assert!(student_t_quantile(0.975f64, 0.0).is_nan());
+    assert!(student_t_quantile(0.975f64, f64::NAN).is_nan());
+}
+
+#[test]
+fn chi2() {
+    // dof = 2 is exponential distribution with mean 2
+    for x in [0.1f64, 1.0, 5.0, 30.0] {
+        approx::assert_relative_eq!(chi2_cdf(x, 2.0), -(-x / 2.0).exp_m1(), max_relative = 1e-13);
+        approx::assert_relative_eq!(chi2_sf(x, 2.0), (-x / 2.0).exp(), max_relative = 1e-13);
+    }
+    approx::assert_relative_eq!(chi2_cdf(-1.0f64, 3.0), 0.0);
+    approx::assert_relative_eq!(
+        regularized_gamma_upper(5.0f64, 100.0),
+        (-100.0f64).exp() * (1.0 + 100.0 + 1e4 / 2.0 + 1e6 / 6.0 + 1e8 / 24.0),
+        max_relative = 1e-12
+    );
+
+    for (dof, expected) in [
+        (1.0, 6.634_896_601_021_214),
+        (5.0, 15.086_272_469_388_987),
+        (20.0, 37.566_234_786_625_88),
+    ] {
+        approx::assert_relative_eq!(chi2_quantile(0.99f64, dof), expected, max_relative = 1e-10);
+    }
+}
+
+#[test]
+fn f_distribution() {
+    // with dof1 = 2, survival function is (1 + 2x/dof2)^(-dof2/2)
+    for x in [0.1f64, 1.0, 3.0, 20.0] {
+        let expected = (1.0 + 2.0 * x / 7.0).powf(-3.5);
+        approx::assert_relative_eq!(f_sf(x, 2.0, 7.0), expected, max_relative = 1e-12);
+        approx::assert_relative_eq!(f_cdf(x, 2.0, 7.0), 1.0 - expected, max_relative = 1e-12);
+    }
+    approx::assert_relative_eq!(f_cdf(0.0f64, 3.0, 4.0), 0.0);
+    approx::assert_relative_eq!(f_sf(-1.0f64, 3.0, 4.0), 1.0);
+    // 95% critical value of F(3, 12)
+    approx::assert_relative_eq!(
+        f_sf(3.490_294_819_497_605f64, 3.0, 12.0),
+        0.05,
+        max_relative = 1e-10
+    );
+}
+
+#[test]
+fn faddeeva_function() {
+    // w(x) = exp(-x^2) for imaginary argument x, w(iy) = exp(y^2) erfc(y)
+    let (re, im) = faddeeva(0.0f64, 0.0);
+    approx::assert_relative_eq!(re, 1.0, max_relative = 1e-14);
+    approx::assert_abs_diff_eq!(im, 0.0, epsilon = 1e-15);
+    for y in [0.1f64, 0.5, 1.0] {
+        let (re, _) = faddeeva(0.0, y);
+        approx::assert_relative_eq!(re, (y * y).exp() * (1.0 - erf(y)), max_relative = 1e-12);
+    }
+
+    for (x, y, expected_re, expected_im) in [
+        (
+            -3.0f64,
+            2.0,
+            0.092_710_766_426_443_33,
+            -0.128_316_962_228_261_6,
+        ),
+        (
+            10.0,
+            0.01,
+            5.728_711_622_490_08e-5,
+            0.056_705_336_054_809_61,
+        ),
+        // lower half-plane
+        (0.3, -0.2, 1.135_882_784_508_345, 0.457_400_700_439_367_8),
+    ] {
+        let (re, im) = faddeeva(x, y);
+        approx::assert_relative_eq!(re, expected_re, max_relative = 1e-12);
+        approx::assert_relative_eq!(im, expected_im, max_relative = 1e-12);
+    }
+}